@@ -0,0 +1,302 @@
+use crate::proto::{self, semantic};
+use std::fmt::Write;
+
+const UI_PAIRS: &[(&str, &str)] = &[
+    ("editor.foreground", "editor.background"),
+    ("editorLineNumber.foreground", "editor.background"),
+    ("editorLineNumber.activeForeground", "editor.background"),
+    ("editorWidget.foreground", "editorWidget.background"),
+    ("activityBar.foreground", "activityBar.background"),
+    ("sideBar.foreground", "sideBar.background"),
+    ("sideBarTitle.foreground", "sideBar.background"),
+    ("titleBar.activeForeground", "titleBar.activeBackground"),
+    ("tab.activeForeground", "tab.activeBackground"),
+    ("tab.inactiveForeground", "tab.inactiveBackground"),
+    ("panelTitle.activeForeground", "panel.background"),
+    ("statusBar.foreground", "statusBar.background"),
+    ("list.activeSelectionForeground", "list.activeSelectionBackground"),
+    ("input.foreground", "input.background"),
+    ("button.foreground", "button.background"),
+    ("badge.foreground", "badge.background"),
+    ("menu.foreground", "menu.background"),
+    ("terminal.foreground", "terminal.background"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub theme: String,
+    pub pairs: Vec<Pair>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pair {
+    pub kind: PairKind,
+    pub foreground_label: String,
+    pub background_label: String,
+    pub foreground: proto::Color,
+    pub background: proto::Color,
+    pub ratio: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PairKind {
+    Token,
+    Ui,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Fail,
+    AaLarge,
+    Aa,
+    Aaa,
+}
+
+impl Pair {
+    pub fn level(&self) -> Level {
+        match self.ratio {
+            r if r >= 7.0 => Level::Aaa,
+            r if r >= 4.5 => Level::Aa,
+            r if r >= 3.0 => Level::AaLarge,
+            _ => Level::Fail,
+        }
+    }
+
+    pub fn passes(&self) -> bool {
+        self.level() >= Level::Aa
+    }
+}
+
+pub fn report(theme: &proto::Theme) -> Report {
+    let workbench = |key: &str| match theme.workbench_rules.get(key) {
+        Some(Some(color)) => Some(*color),
+        _ => None,
+    };
+    let editor_background = workbench("editor.background")
+        .or_else(|| theme.global_token_defaults.and_then(|d| d.background));
+
+    let mut pairs = Vec::new();
+    let mut push = |kind, fg_label: String, fg: proto::Color, bg_label: &str, bg: proto::Color| {
+        let background = match editor_background {
+            Some(editor_background) => bg.over(editor_background),
+            None => bg.over(proto::Color { a: 0xFF, ..bg }),
+        };
+        let foreground = fg.over(background);
+
+        pairs.push(Pair {
+            kind,
+            foreground_label: fg_label,
+            background_label: bg_label.to_string(),
+            foreground: fg,
+            background: bg,
+            ratio: foreground.contrast_ratio(background),
+        });
+    };
+
+    if let Some(background) = editor_background {
+        for rule in &theme.textmate_rules {
+            if let Some(foreground) = rule.settings.foreground {
+                push(
+                    PairKind::Token,
+                    rule.scope.join(", "),
+                    foreground,
+                    "editor.background",
+                    background,
+                );
+            }
+        }
+
+        if let semantic::Highlighting::On { rules } = &theme.semantic_highlighting {
+            for (selector, style) in rules {
+                if let Some(foreground) = style.foreground {
+                    push(
+                        PairKind::Token,
+                        format!("semantic {selector}"),
+                        foreground,
+                        "editor.background",
+                        background,
+                    );
+                }
+            }
+        }
+    }
+
+    for (fg_key, bg_key) in UI_PAIRS {
+        if let (Some(foreground), Some(background)) = (workbench(fg_key), workbench(bg_key)) {
+            push(PairKind::Ui, fg_key.to_string(), foreground, bg_key, background);
+        }
+    }
+
+    Report { theme: theme.name.clone(), pairs }
+}
+
+impl Report {
+    pub fn failures(&self) -> impl Iterator<Item = &Pair> {
+        self.pairs.iter().filter(|pair| !pair.passes())
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let failures = self.failures().count();
+
+        writeln!(out, "# Accessibility report for {}", self.theme).unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "{failures} of {} pairs are below WCAG AA (4.5:1).", self.pairs.len())
+            .unwrap();
+
+        for (kind, title) in [(PairKind::Token, "Tokens"), (PairKind::Ui, "Interface")] {
+            let pairs: Vec<_> = self.pairs.iter().filter(|p| p.kind == kind).collect();
+            if pairs.is_empty() {
+                continue;
+            }
+
+            writeln!(out, "\n## {title}\n").unwrap();
+            writeln!(out, "| Foreground | Background | Colors | Ratio | Level |").unwrap();
+            writeln!(out, "|------------|------------|--------|------:|-------|").unwrap();
+
+            for pair in pairs {
+                writeln!(
+                    out,
+                    "| `{}` | `{}` | `{}` on `{}` | {:.2}:1 | {} |",
+                    pair.foreground_label,
+                    pair.background_label,
+                    pair.foreground,
+                    pair.background,
+                    pair.ratio,
+                    level_label(pair.level()),
+                )
+                .unwrap();
+            }
+        }
+
+        out
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        let failures = self.failures().count();
+
+        writeln!(out, "<!DOCTYPE html>").unwrap();
+        writeln!(out, "<html>").unwrap();
+        writeln!(out, "<head>").unwrap();
+        writeln!(out, "<meta charset=\"utf-8\">").unwrap();
+        writeln!(out, "<title>Accessibility report for {}</title>", escape(&self.theme)).unwrap();
+        writeln!(
+            out,
+            "<style>body{{font-family:sans-serif}}td,th{{padding:4px 8px;text-align:left}}.fail{{color:#C00;font-weight:bold}}.sample{{padding:2px 8px;font-family:monospace}}</style>"
+        )
+        .unwrap();
+        writeln!(out, "</head>").unwrap();
+        writeln!(out, "<body>").unwrap();
+        writeln!(out, "<h1>Accessibility report for {}</h1>", escape(&self.theme)).unwrap();
+        writeln!(out, "<p>{failures} of {} pairs are below WCAG AA (4.5:1).</p>", self.pairs.len())
+            .unwrap();
+
+        for (kind, title) in [(PairKind::Token, "Tokens"), (PairKind::Ui, "Interface")] {
+            let pairs: Vec<_> = self.pairs.iter().filter(|p| p.kind == kind).collect();
+            if pairs.is_empty() {
+                continue;
+            }
+
+            writeln!(out, "<h2>{title}</h2>").unwrap();
+            writeln!(out, "<table>").unwrap();
+            writeln!(
+                out,
+                "<tr><th>Foreground</th><th>Background</th><th>Sample</th><th>Ratio</th><th>Level</th></tr>"
+            )
+            .unwrap();
+
+            for pair in pairs {
+                let class = if pair.passes() { "" } else { " class=\"fail\"" };
+                writeln!(
+                    out,
+                    "<tr{class}><td><code>{}</code></td><td><code>{}</code></td><td><span class=\"sample\" style=\"color:{};background:{}\">Sample</span></td><td>{:.2}:1</td><td>{}</td></tr>",
+                    escape(&pair.foreground_label),
+                    escape(&pair.background_label),
+                    css(pair.foreground),
+                    css(pair.background),
+                    pair.ratio,
+                    level_label(pair.level()),
+                )
+                .unwrap();
+            }
+
+            writeln!(out, "</table>").unwrap();
+        }
+
+        writeln!(out, "</body>").unwrap();
+        writeln!(out, "</html>").unwrap();
+
+        out
+    }
+}
+
+fn level_label(level: Level) -> &'static str {
+    match level {
+        Level::Fail => "❌ fail",
+        Level::AaLarge => "❌ AA large text only",
+        Level::Aa => "AA",
+        Level::Aaa => "AAA",
+    }
+}
+
+fn css(color: proto::Color) -> String {
+    format!("rgba({},{},{},{:.3})", color.r, color.g, color.b, f64::from(color.a) / 255.0)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{s, tm, ThemeBuilder};
+    use expect_test::expect;
+
+    fn theme() -> proto::Theme {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x1E1E1E);
+        t.w(["editor.foreground"], 0xD4D4D4);
+        t.w(["statusBar.background"], 0x007ACC);
+        t.w(["statusBar.foreground"], 0xFFFFFF);
+        t.a([tm("comment")], 0x6A9955);
+        t.a([tm("keyword"), s("keyword")], 0x3A3A3A);
+        t.build("My cool theme")
+    }
+
+    #[test]
+    fn markdown() {
+        expect![[r#"
+            # Accessibility report for My cool theme
+
+            2 of 5 pairs are below WCAG AA (4.5:1).
+
+            ## Tokens
+
+            | Foreground | Background | Colors | Ratio | Level |
+            |------------|------------|--------|------:|-------|
+            | `comment` | `editor.background` | `#6A9955` on `#1E1E1E` | 5.00:1 | AA |
+            | `keyword` | `editor.background` | `#3A3A3A` on `#1E1E1E` | 1.47:1 | ❌ fail |
+            | `semantic keyword` | `editor.background` | `#3A3A3A` on `#1E1E1E` | 1.47:1 | ❌ fail |
+
+            ## Interface
+
+            | Foreground | Background | Colors | Ratio | Level |
+            |------------|------------|--------|------:|-------|
+            | `editor.foreground` | `editor.background` | `#D4D4D4` on `#1E1E1E` | 11.25:1 | AAA |
+            | `statusBar.foreground` | `statusBar.background` | `#FFFFFF` on `#007ACC` | 4.51:1 | AA |
+        "#]]
+        .assert_eq(&report(&theme()).to_markdown());
+    }
+
+    #[test]
+    fn html_flags_failures() {
+        let html = report(&theme()).to_html();
+
+        assert_eq!(html.matches("<tr class=\"fail\">").count(), 2);
+        assert!(
+            html.contains("style=\"color:rgba(58,58,58,1.000);background:rgba(30,30,30,1.000)\"")
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use crate::palette::Palette;
+
+#[cfg(feature = "import")]
+use crate::proto;
+#[cfg(feature = "import")]
+use thiserror::Error;
+
+const SIGNATURE: &[u8; 4] = b"ASEF";
+#[cfg(feature = "export")]
+const VERSION: (u16, u16) = (1, 0);
+#[cfg(feature = "export")]
+const GROUP_START: u16 = 0xC001;
+#[cfg(feature = "export")]
+const GROUP_END: u16 = 0xC002;
+const COLOR_ENTRY: u16 = 0x0001;
+#[cfg(feature = "export")]
+const NORMAL_COLOR: u16 = 2;
+
+#[cfg(feature = "export")]
+pub fn encode(name: &str, palette: &Palette) -> Vec<u8> {
+    let mut out = Vec::new();
+
+    out.extend_from_slice(SIGNATURE);
+    out.extend_from_slice(&VERSION.0.to_be_bytes());
+    out.extend_from_slice(&VERSION.1.to_be_bytes());
+    out.extend_from_slice(&(palette.len() as u32 + 2).to_be_bytes());
+
+    block(&mut out, GROUP_START, &utf16_name(name));
+
+    for (name, color) in palette.iter() {
+        let mut body = utf16_name(name);
+        body.extend_from_slice(b"RGB ");
+        for c in [color.r, color.g, color.b] {
+            body.extend_from_slice(&(f32::from(c) / 255.0).to_be_bytes());
+        }
+        body.extend_from_slice(&NORMAL_COLOR.to_be_bytes());
+
+        block(&mut out, COLOR_ENTRY, &body);
+    }
+
+    block(&mut out, GROUP_END, &[]);
+
+    out
+}
+
+#[cfg(feature = "import")]
+pub fn decode(bytes: &[u8]) -> Result<Palette, AseError> {
+    let mut r = Reader { bytes };
+
+    if r.take(4)? != SIGNATURE {
+        return Err(AseError::InvalidSignature);
+    }
+    let _version = (r.u16()?, r.u16()?);
+    let block_count = r.u32()?;
+
+    let mut palette = Palette::new();
+
+    for _ in 0..block_count {
+        let kind = r.u16()?;
+        let len = r.u32()? as usize;
+        let mut body = Reader { bytes: r.take(len)? };
+
+        if kind != COLOR_ENTRY {
+            continue;
+        }
+
+        let name = body.name()?;
+        let model = body.take(4)?;
+        let mut channel =
+            || -> Result<u8, AseError> { Ok((body.f32()?.clamp(0.0, 1.0) * 255.0).round() as u8) };
+
+        let (r, g, b) = match model {
+            b"RGB " => (channel()?, channel()?, channel()?),
+            b"Gray" => {
+                let v = channel()?;
+                (v, v, v)
+            }
+            b"CMYK" => {
+                let [c, m, y, k] = [channel()?, channel()?, channel()?, channel()?];
+                let rgb = |v: u8| ((255 - v) as u16 * (255 - k) as u16 / 255) as u8;
+                (rgb(c), rgb(m), rgb(y))
+            }
+            _ => return Err(AseError::UnsupportedColorModel(name)),
+        };
+
+        palette.insert(name, proto::Color { r, g, b, a: 0xFF });
+    }
+
+    Ok(palette)
+}
+
+#[cfg(feature = "import")]
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+#[cfg(feature = "import")]
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], AseError> {
+        if self.bytes.len() < n {
+            return Err(AseError::UnexpectedEof);
+        }
+
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u16(&mut self) -> Result<u16, AseError> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, AseError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> Result<f32, AseError> {
+        Ok(f32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn name(&mut self) -> Result<String, AseError> {
+        let len = self.u16()? as usize;
+        let units: Vec<u16> = (0..len).map(|_| self.u16()).collect::<Result<_, _>>()?;
+        let units = units.strip_suffix(&[0]).unwrap_or(&units);
+
+        String::from_utf16(units).map_err(|_| AseError::InvalidName)
+    }
+}
+
+#[cfg(feature = "export")]
+fn block(out: &mut Vec<u8>, kind: u16, body: &[u8]) {
+    out.extend_from_slice(&kind.to_be_bytes());
+    out.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    out.extend_from_slice(body);
+}
+
+#[cfg(feature = "export")]
+fn utf16_name(name: &str) -> Vec<u8> {
+    let units: Vec<u16> = name.encode_utf16().chain([0]).collect();
+    let mut out = Vec::with_capacity(2 + units.len() * 2);
+
+    out.extend_from_slice(&(units.len() as u16).to_be_bytes());
+    for unit in units {
+        out.extend_from_slice(&unit.to_be_bytes());
+    }
+
+    out
+}
+
+#[cfg(feature = "import")]
+#[derive(Debug, Error)]
+pub enum AseError {
+    #[error("not an ASE file (expected `ASEF` signature)")]
+    InvalidSignature,
+    #[error("ASE file ended unexpectedly")]
+    UnexpectedEof,
+    #[error("swatch name is not valid UTF-16")]
+    InvalidName,
+    #[error("swatch `{0}` uses an unsupported color model")]
+    UnsupportedColorModel(String),
+}
+
+#[cfg(all(test, feature = "export", feature = "import"))]
+mod tests {
+    use super::*;
+    use expect_test::expect;
+
+    fn hex_dump(bytes: &[u8]) -> String {
+        let mut out = String::new();
+
+        for line in bytes.chunks(16) {
+            let line: Vec<_> = line.iter().map(|b| format!("{b:02x}")).collect();
+            out.push_str(&line.join(" "));
+            out.push('\n');
+        }
+
+        out
+    }
+
+    #[test]
+    fn swatch_file() {
+        let mut palette = Palette::new();
+        palette.insert("red", 0xFF0000);
+        palette.insert("bg", 0x1E1E1E);
+
+        let encoded = encode("Mottle", &palette);
+        assert_eq!(decode(&encoded).unwrap(), palette);
+
+        expect![[r#"
+            41 53 45 46 00 01 00 00 00 00 00 04 c0 01 00 00
+            00 10 00 07 00 4d 00 6f 00 74 00 74 00 6c 00 65
+            00 00 00 01 00 00 00 1c 00 04 00 72 00 65 00 64
+            00 00 52 47 42 20 3f 80 00 00 00 00 00 00 00 00
+            00 00 00 02 00 01 00 00 00 1a 00 03 00 62 00 67
+            00 00 52 47 42 20 3d f0 f0 f1 3d f0 f0 f1 3d f0
+            f0 f1 00 02 c0 02 00 00 00 00
+        "#]]
+        .assert_eq(&hex_dump(&encoded));
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert!(matches!(decode(b"GPL "), Err(AseError::InvalidSignature)));
+        assert!(matches!(decode(b"ASEF\0\x01\0\0\0\0\0\x01\0\x01"), Err(AseError::UnexpectedEof)));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::dsl::{s, tm, Color, FontStyle, ThemeBuilder};
+use crate::oklab::{mix, Oklch};
+use crate::proto;
+
+pub fn minimal(
+    dark: bool,
+    background: impl Into<Color>,
+    foreground: impl Into<Color>,
+    accent: impl Into<Color>,
+) -> ThemeBuilder {
+    let bg: proto::Color = background.into().into();
+    let fg: proto::Color = foreground.into().into();
+    let accent: proto::Color = accent.into().into();
+
+    let surface = mix(bg, fg, 0.04);
+    let hover = mix(bg, fg, 0.08);
+    let border = mix(bg, fg, 0.14);
+    let muted = mix(fg, bg, 0.4);
+    let on_accent = if accent.contrast_ratio(bg) > accent.contrast_ratio(fg) { bg } else { fg };
+    let selection = proto::Color { a: if dark { 0x50 } else { 0x40 }, ..accent };
+    let shadow = proto::Color { r: 0, g: 0, b: 0, a: if dark { 0x5C } else { 0x29 } };
+
+    let mut t = ThemeBuilder::default();
+
+    t.w(["foreground", "editor.foreground", "sideBar.foreground", "titleBar.activeForeground"], fg);
+    t.w(["focusBorder", "editorCursor.foreground", "tab.activeBorderTop"], accent);
+    t.w(["widget.shadow", "scrollbar.shadow"], shadow);
+
+    t.w(["editor.background", "panel.background", "tab.activeBackground"], bg);
+    t.w(["editor.lineHighlightBackground"], hover);
+    t.w(["editor.selectionBackground"], selection);
+    t.w(["editorLineNumber.foreground"], muted);
+    t.w(["editorLineNumber.activeForeground"], fg);
+    t.w(["editorWidget.background", "input.background"], surface);
+
+    t.w(["sideBar.background", "activityBar.background", "titleBar.activeBackground"], surface);
+    t.w(["activityBar.foreground"], fg);
+    t.w(["activityBar.inactiveForeground"], muted);
+    t.w(["activityBarBadge.background", "button.background"], accent);
+    t.w(["activityBarBadge.foreground", "button.foreground"], on_accent);
+    t.w(["list.hoverBackground"], hover);
+    t.w(["list.activeSelectionBackground"], mix(bg, accent, 0.3));
+    t.w(["list.activeSelectionForeground"], fg);
+
+    t.w(["statusBar.background"], surface);
+    t.w(["statusBar.foreground"], muted);
+    t.w(["statusBarItem.remoteBackground"], accent);
+    t.w(["statusBarItem.remoteForeground"], on_accent);
+
+    t.w(["editorGroupHeader.tabsBackground", "tab.inactiveBackground"], surface);
+    t.w(["tab.activeForeground"], fg);
+    t.w(["tab.inactiveForeground"], muted);
+
+    t.w(
+        [
+            "sideBar.border",
+            "activityBar.border",
+            "statusBar.border",
+            "titleBar.border",
+            "panel.border",
+            "tab.border",
+            "editorGroupHeader.tabsBorder",
+            "editorGroup.border",
+            "input.border",
+        ],
+        border,
+    );
+
+    t.global_token_defaults(fg, bg);
+    t.a([tm("comment"), s("comment")], (muted, FontStyle::Italic));
+    t.a([tm("keyword"), tm("storage"), s("keyword")], accent);
+    t.a([tm("string"), tm("constant"), s("string"), s("number")], rotate_hue(accent, 150.0));
+    t.a([tm("entity.name.type"), tm("support.type"), s("type")], rotate_hue(accent, -60.0));
+    t.a([tm("entity.name.function"), s("function"), s("method")], FontStyle::Bold);
+    t.a([tm("invalid")], FontStyle::Underline);
+
+    t
+}
+
+fn rotate_hue(color: proto::Color, degrees: f64) -> proto::Color {
+    let oklch = Oklch::from(color);
+    Oklch { h: (oklch.h + degrees).rem_euclid(360.0), ..oklch }.to_color(color.a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workbench;
+    use expect_test::expect;
+
+    #[test]
+    fn minimal_dark() {
+        let t = minimal(true, 0x1E1E2E, 0xCDD6F4, 0x89B4FA);
+        assert!(t.workbench_rules.keys().all(|key| workbench::is_known_key(key)));
+        assert!(t.verify().is_empty(), "{:?}", t.verify());
+
+        expect![[r#"
+            #CDD6F4
+              defaults   foreground
+              workbench  foreground
+              workbench  editor.foreground
+              workbench  sideBar.foreground
+              workbench  titleBar.activeForeground
+              workbench  editorLineNumber.activeForeground
+              workbench  activityBar.foreground
+              workbench  list.activeSelectionForeground
+              workbench  tab.activeForeground
+            #1E1E2E
+              defaults   background
+              workbench  editor.background
+              workbench  panel.background
+              workbench  tab.activeBackground
+              workbench  activityBarBadge.foreground
+              workbench  button.foreground
+              workbench  statusBarItem.remoteForeground
+            #81869F
+              textmate   comment                            italic
+              semantic   comment                            italic
+              workbench  editorLineNumber.foreground
+              workbench  activityBar.inactiveForeground
+              workbench  statusBar.foreground
+              workbench  tab.inactiveForeground
+            #89B4FA
+              textmate   keyword, storage
+              semantic   keyword
+              workbench  focusBorder
+              workbench  editorCursor.foreground
+              workbench  tab.activeBorderTop
+              workbench  activityBarBadge.background
+              workbench  button.background
+              workbench  statusBarItem.remoteBackground
+            #EC9E71
+              textmate   string, constant
+              semantic   string
+              semantic   number
+            #46C8CF
+              textmate   entity.name.type, support.type
+              semantic   type
+            (no color)
+              textmate   entity.name.function               bold
+              textmate   invalid                            underline
+              semantic   function                           bold
+              semantic   method                             bold
+            #0000005C
+              workbench  widget.shadow
+              workbench  scrollbar.shadow
+            #2A2A3C
+              workbench  editor.lineHighlightBackground
+              workbench  list.hoverBackground
+            #89B4FA50
+              workbench  editor.selectionBackground
+            #242435
+              workbench  editorWidget.background
+              workbench  input.background
+              workbench  sideBar.background
+              workbench  activityBar.background
+              workbench  titleBar.activeBackground
+              workbench  statusBar.background
+              workbench  editorGroupHeader.tabsBackground
+              workbench  tab.inactiveBackground
+            #3C4665
+              workbench  list.activeSelectionBackground
+            #333446
+              workbench  sideBar.border
+              workbench  activityBar.border
+              workbench  statusBar.border
+              workbench  titleBar.border
+              workbench  panel.border
+              workbench  tab.border
+              workbench  editorGroupHeader.tabsBorder
+              workbench  editorGroup.border
+              workbench  input.border
+        "#]]
+        .assert_eq(&t.dump());
+    }
+}
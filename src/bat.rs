@@ -0,0 +1,97 @@
+use crate::proto;
+use crate::tmtheme::tm_theme;
+use crate::{slugify, SaveThemeError};
+
+#[cfg(feature = "std-fs")]
+use std::fs;
+#[cfg(feature = "std-fs")]
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bundle {
+    pub theme_name: String,
+    pub tm_theme: String,
+    pub config: String,
+}
+
+impl Bundle {
+    pub fn theme_file_name(&self) -> String {
+        format!("{}.tmTheme", self.theme_name)
+    }
+}
+
+pub fn bundle(theme: &proto::Theme) -> Result<Bundle, SaveThemeError> {
+    let theme_name =
+        slugify(&theme.name).ok_or_else(|| SaveThemeError::InvalidThemeName(theme.name.clone()))?;
+
+    let config = format!(
+        "# Do not edit directly; this file is generated.\n\
+         # Copy `themes/` into `$(bat --config-dir)`, run `bat cache --build`\n\
+         # and add the following line to `$(bat --config-file)`.\n\
+         --theme=\"{theme_name}\"\n"
+    );
+
+    Ok(Bundle { theme_name, tm_theme: tm_theme(theme), config })
+}
+
+#[cfg(feature = "std-fs")]
+pub fn write_bundle(
+    dir: impl AsRef<Path>,
+    theme: &proto::Theme,
+) -> Result<Vec<PathBuf>, SaveThemeError> {
+    let bundle = bundle(theme)?;
+    let dir = dir.as_ref();
+    let themes_dir = crate::prepare_themes_dir(&dir.join("themes"))?.to_path_buf();
+
+    let files = [
+        (themes_dir.join(bundle.theme_file_name()), bundle.tm_theme),
+        (dir.join("config"), bundle.config),
+    ];
+
+    let mut written = Vec::with_capacity(files.len());
+    for (path, contents) in files {
+        fs::write(&path, contents).map_err(|e| SaveThemeError::WriteTheme(e, path.clone()))?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{tm, ThemeBuilder};
+    use expect_test::expect;
+
+    fn theme() -> proto::Theme {
+        let mut t = ThemeBuilder::default();
+        t.a([tm("keyword")], 0x569CD6);
+        t.build("My cool theme")
+    }
+
+    #[test]
+    fn config_line() {
+        let bundle = bundle(&theme()).unwrap();
+
+        assert_eq!(bundle.theme_file_name(), "my-cool-theme.tmTheme");
+        expect![[r##"
+            # Do not edit directly; this file is generated.
+            # Copy `themes/` into `$(bat --config-dir)`, run `bat cache --build`
+            # and add the following line to `$(bat --config-file)`.
+            --theme="my-cool-theme"
+        "##]]
+        .assert_eq(&bundle.config);
+    }
+
+    #[cfg(feature = "std-fs")]
+    #[test]
+    fn install_layout() {
+        let dir = crate::tests::temp_dir("bat");
+        let written = write_bundle(&dir, &theme()).unwrap();
+
+        let relative: Vec<_> =
+            written.iter().map(|path| path.strip_prefix(&dir).unwrap().to_path_buf()).collect();
+        assert_eq!(relative, [Path::new("themes/my-cool-theme.tmTheme"), Path::new("config")]);
+        assert_eq!(fs::read_to_string(&written[0]).unwrap(), tm_theme(&theme()));
+    }
+}
@@ -0,0 +1,67 @@
+use mottle::lint::ThemeLinter;
+use std::error::Error;
+use std::fs;
+use std::process::ExitCode;
+
+pub(crate) fn run(paths: &[String]) -> ExitCode {
+    if paths.is_empty() {
+        eprintln!("error: `check` expects at least one theme file");
+        return ExitCode::FAILURE;
+    }
+
+    let linter = ThemeLinter::default();
+    let mut failed = false;
+
+    for path in paths {
+        let src = match fs::read_to_string(path) {
+            Ok(src) => src,
+            Err(e) => {
+                eprintln!("{path}: error: failed reading file: {e}");
+                failed = true;
+                continue;
+            }
+        };
+
+        let violations = mottle::validate::validate_theme_json(&src);
+        if !violations.is_empty() {
+            for violation in violations {
+                eprintln!("{path}: error[schema]: {violation}");
+            }
+            failed = true;
+            continue;
+        }
+
+        let theme = match mottle::parse::parse_theme(&src) {
+            Ok(theme) => theme,
+            Err(e) => {
+                let mut message = e.to_string();
+                let mut source = e.source();
+                while let Some(e) = source {
+                    message.push_str(&format!(": {e}"));
+                    source = e.source();
+                }
+
+                eprintln!("{path}: error: {message}");
+                failed = true;
+                continue;
+            }
+        };
+
+        let diagnostics = linter.lint(&theme);
+        if diagnostics.is_empty() {
+            println!("{path}: ok");
+            continue;
+        }
+
+        for diagnostic in diagnostics {
+            eprintln!("{path}: {diagnostic}");
+        }
+        failed = true;
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
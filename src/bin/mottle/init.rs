@@ -0,0 +1,95 @@
+use mottle::dsl::ThemeBuilder;
+use mottle::manifest::Manifest;
+use mottle::proto::ThemeType;
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+const MAIN_RS: &str = r#"use mottle::dsl::{s, tm, ThemeBuilder};
+use mottle::proto::ThemeType;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut t = ThemeBuilder::default();
+
+    t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+    t.w(["editor.background"], 0x1E1E1E);
+    t.w(["editor.foreground"], 0xD4D4D4);
+
+    t.a([tm("comment"), s("*.documentation")], 0x6A9955);
+    t.a([tm("keyword"), s("keyword")], 0x569CD6);
+    t.a([tm("string"), s("string")], 0xCE9178);
+
+    mottle::save_theme(&t.build_with_type(THEME_NAME, ThemeType::Dark))?;
+
+    Ok(())
+}
+"#;
+
+const VSCODEIGNORE: &str = "\
+src/**
+target/**
+Cargo.toml
+Cargo.lock
+";
+
+pub(crate) fn run(args: &[String]) -> ExitCode {
+    let [name] = args else {
+        eprintln!("error: `init` expects exactly one theme name");
+        return ExitCode::FAILURE;
+    };
+
+    let dir = Path::new(name);
+    if dir.exists() {
+        eprintln!("error: `{name}` already exists");
+        return ExitCode::FAILURE;
+    }
+
+    let Some(theme_name) = dir.file_name().and_then(|n| n.to_str()) else {
+        eprintln!("error: `{name}` does not end in a directory name");
+        return ExitCode::FAILURE;
+    };
+    let Some(package_name) = mottle::slugify(theme_name) else {
+        eprintln!("error: cannot derive a package name from `{theme_name}`");
+        return ExitCode::FAILURE;
+    };
+
+    let mut manifest = Manifest::new(&package_name, "your-publisher-name");
+    manifest.display_name = theme_name.to_string();
+    let package_json = ThemeBuilder::default().build_with_type(theme_name, ThemeType::Dark);
+    let package_json = match manifest.generate(&[package_json]) {
+        Ok(package_json) => package_json,
+        Err(e) => {
+            eprintln!("error: failed generating package.json: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let files = [
+        ("Cargo.toml", cargo_toml(&package_name)),
+        ("src/main.rs", MAIN_RS.replace("THEME_NAME", &format!("{theme_name:?}"))),
+        ("package.json", package_json),
+        (".vscodeignore", VSCODEIGNORE.to_string()),
+    ];
+
+    for (path, contents) in files {
+        let path = dir.join(path);
+        let result =
+            fs::create_dir_all(path.parent().unwrap()).and_then(|()| fs::write(&path, contents));
+
+        if let Err(e) = result {
+            eprintln!("error: failed writing `{}`: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+    }
+
+    println!("Created theme crate in `{name}`. Run `cargo run` inside it to generate the theme.");
+
+    ExitCode::SUCCESS
+}
+
+fn cargo_toml(package_name: &str) -> String {
+    format!(
+        "[package]\nname = \"{package_name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\npublish = false\n\n[dependencies]\nmottle = \"{}\"\n",
+        env!("CARGO_PKG_VERSION")
+    )
+}
@@ -0,0 +1,44 @@
+mod check;
+mod init;
+mod palette;
+mod preview;
+
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+usage: mottle <command> [args]
+
+commands:
+    check <theme.json>...       parse, validate and lint generated themes
+    init <name>                 scaffold a new theme crate
+    palette <theme.json>        print the clustered palette with suggested roles
+    palette --from-image <png>  extract a palette from a screenshot
+    preview [dir] [--port N]    serve a live-reloading HTML preview of a theme crate
+    schema                      print the JSON Schema for theme spec files";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("check") => check::run(&args[1..]),
+        Some("init") => init::run(&args[1..]),
+        Some("palette") => palette::run(&args[1..]),
+        Some("preview") => preview::run(&args[1..]),
+        Some("schema") => {
+            println!("{:#}", mottle::spec::json_schema());
+            ExitCode::SUCCESS
+        }
+        Some("-h" | "--help") => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Some(command) => {
+            eprintln!("error: unknown command `{command}`\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+        None => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
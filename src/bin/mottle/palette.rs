@@ -0,0 +1,58 @@
+use mottle::palette::{self, Swatch};
+use std::error::Error;
+use std::fs;
+use std::process::ExitCode;
+
+const MAX_IMAGE_SWATCHES: usize = 16;
+
+pub(crate) fn run(args: &[String]) -> ExitCode {
+    let swatches = match args {
+        [flag, path] if flag == "--from-image" => from_image(path),
+        [path] => from_theme(path),
+        _ => {
+            eprintln!("error: `palette` expects a theme file or `--from-image <image.png>`");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let swatches = match swatches {
+        Ok(swatches) => swatches,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let roles = palette::suggest_roles(&swatches);
+
+    for swatch in &swatches {
+        let role =
+            roles.iter().find(|(_, color)| *color == swatch.color).map_or("", |(name, _)| name);
+        let usages = swatch.usages.join(", ");
+
+        println!("{:<10} {:>7}  {role:<12} {usages}", swatch.color.to_string(), swatch.count);
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn from_theme(path: &str) -> Result<Vec<Swatch>, String> {
+    let src = fs::read_to_string(path).map_err(|e| format!("failed reading `{path}`: {e}"))?;
+    let theme = mottle::parse::parse_theme(&src).map_err(|e| format!("{path}: {e}"))?;
+
+    Ok(palette::extract(&theme))
+}
+
+fn from_image(path: &str) -> Result<Vec<Swatch>, String> {
+    let pixels = palette::read_png(path).map_err(|e| {
+        let mut message = e.to_string();
+        let mut source = e.source();
+        while let Some(e) = source {
+            message.push_str(&format!(": {e}"));
+            source = e.source();
+        }
+        message
+    })?;
+
+    Ok(palette::extract_from_pixels(pixels, MAX_IMAGE_SWATCHES))
+}
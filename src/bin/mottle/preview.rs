@@ -0,0 +1,165 @@
+use notify::{RecursiveMode, Watcher};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+const DEFAULT_PORT: u16 = 7878;
+const DEBOUNCE: Duration = Duration::from_millis(100);
+const RELOAD_SCRIPT: &str = "setInterval(async () => { const r = await fetch('/version'); if ((await r.text()) !== VERSION) location.reload(); }, 500);";
+
+pub(crate) fn run(args: &[String]) -> ExitCode {
+    let mut dir = PathBuf::from(".");
+    let mut port = DEFAULT_PORT;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--port" => match args.next().map(|p| p.parse()) {
+                Some(Ok(p)) => port = p,
+                _ => {
+                    eprintln!("error: `--port` expects a port number");
+                    return ExitCode::FAILURE;
+                }
+            },
+            _ => dir = PathBuf::from(arg),
+        }
+    }
+
+    let version = Arc::new(AtomicU64::new(0));
+    regenerate(&dir);
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            eprintln!("error: failed creating filesystem watcher: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for path in [dir.join("src"), dir.join("themes")] {
+        if path.exists() {
+            if let Err(e) = watcher.watch(&path, RecursiveMode::Recursive) {
+                eprintln!("error: failed watching `{}`: {e}", path.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    thread::spawn({
+        let dir = dir.clone();
+        let version = Arc::clone(&version);
+        move || {
+            while let Ok(event) = rx.recv() {
+                let mut events = vec![event];
+                events.extend(std::iter::from_fn(|| rx.recv_timeout(DEBOUNCE).ok()));
+
+                let sources_changed = events.iter().flatten().any(|event| {
+                    !event.kind.is_access()
+                        && event.paths.iter().any(|p| p.extension() == Some("rs".as_ref()))
+                });
+
+                if sources_changed {
+                    regenerate(&dir);
+                }
+                version.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    });
+
+    let listener = match TcpListener::bind(("127.0.0.1", port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("error: failed listening on port {port}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("Serving preview at http://127.0.0.1:{port}");
+
+    for stream in listener.incoming().flatten() {
+        if let Err(e) = respond(stream, &dir, version.load(Ordering::SeqCst)) {
+            eprintln!("error: failed responding to request: {e}");
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn regenerate(dir: &Path) {
+    if !dir.join("Cargo.toml").exists() {
+        return;
+    }
+
+    match Command::new("cargo").args(["run", "--quiet"]).current_dir(dir).status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!("error: regenerating themes failed ({status})"),
+        Err(e) => eprintln!("error: failed running cargo: {e}"),
+    }
+}
+
+fn respond(mut stream: TcpStream, dir: &Path, version: u64) -> std::io::Result<()> {
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+
+    let (status, content_type, body) = match request_line.split_whitespace().nth(1) {
+        Some("/") => ("200 OK", "text/html; charset=utf-8", page(dir, version)),
+        Some("/version") => ("200 OK", "text/plain", version.to_string()),
+        _ => ("404 Not Found", "text/plain", "not found".to_string()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+fn page(dir: &Path, version: u64) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "<!DOCTYPE html>").unwrap();
+    writeln!(out, "<html>").unwrap();
+    writeln!(out, "<head>").unwrap();
+    writeln!(out, "<meta charset=\"utf-8\">").unwrap();
+    writeln!(out, "<title>mottle preview</title>").unwrap();
+    writeln!(out, "<script>const VERSION = \"{version}\"; {RELOAD_SCRIPT}</script>").unwrap();
+    writeln!(out, "</head>").unwrap();
+    writeln!(out, "<body style=\"font-family:sans-serif\">").unwrap();
+
+    let mut paths: Vec<_> = match fs::read_dir(dir.join("themes")) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.to_string_lossy().ends_with("-color-theme.json"))
+            .collect(),
+        Err(e) => {
+            writeln!(out, "<p>failed reading themes directory: {e}</p>").unwrap();
+            Vec::new()
+        }
+    };
+    paths.sort();
+
+    for path in paths {
+        let theme = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|src| mottle::parse::parse_theme(&src).map_err(|e| e.to_string()));
+
+        match theme {
+            Ok(theme) => out.push_str(&mottle::preview::html(&theme)),
+            Err(e) => writeln!(out, "<p>{}: {e}</p>", path.display()).unwrap(),
+        }
+    }
+
+    writeln!(out, "</body>").unwrap();
+    writeln!(out, "</html>").unwrap();
+
+    out
+}
@@ -0,0 +1,186 @@
+use crate::dsl::{s, tm, Selector};
+use crate::proto::{self, semantic, textmate};
+use indexmap::IndexMap;
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureMap {
+    captures: IndexMap<Cow<'static, str>, Vec<Selector>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CaptureStyle {
+    pub foreground: Option<proto::Color>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+impl Default for CaptureMap {
+    fn default() -> Self {
+        let mut map = Self::empty();
+
+        map.register("attribute", [tm("entity.other.attribute-name"), s("decorator")]);
+        map.register("boolean", [tm("constant.language.boolean"), s("boolean")]);
+        map.register("comment", [tm("comment"), s("comment")]);
+        map.register(
+            "comment.documentation",
+            [tm("comment.block.documentation"), s("comment.documentation")],
+        );
+        map.register(
+            "constant",
+            [tm("variable.other.constant"), s("variable.readonly"), tm("constant")],
+        );
+        map.register("constant.builtin", [tm("constant.language")]);
+        map.register("constructor", [tm("entity.name.function.constructor")]);
+        map.register("function", [tm("entity.name.function"), s("function")]);
+        map.register("function.builtin", [tm("support.function"), s("function.defaultLibrary")]);
+        map.register("function.macro", [tm("entity.name.function.macro"), s("macro")]);
+        map.register("function.method", [tm("entity.name.function.member"), s("method")]);
+        map.register("keyword", [tm("keyword"), s("keyword")]);
+        map.register("keyword.storage", [tm("storage"), s("keyword")]);
+        map.register("label", [tm("entity.name.label"), s("label")]);
+        map.register("module", [tm("entity.name.namespace"), s("namespace")]);
+        map.register("number", [tm("constant.numeric"), s("number")]);
+        map.register("operator", [tm("keyword.operator"), s("operator")]);
+        map.register("property", [tm("variable.other.property"), s("property")]);
+        map.register("punctuation", [tm("punctuation")]);
+        map.register("string", [tm("string"), s("string")]);
+        map.register("string.escape", [tm("constant.character.escape")]);
+        map.register("string.regexp", [tm("string.regexp"), s("regexp")]);
+        map.register("string.special", [tm("constant.other.symbol"), tm("string.regexp")]);
+        map.register("tag", [tm("entity.name.tag")]);
+        map.register("type", [tm("entity.name.type"), s("type")]);
+        map.register("type.builtin", [tm("support.type"), s("type.defaultLibrary")]);
+        map.register("variable", [tm("variable"), s("variable")]);
+        map.register("variable.builtin", [tm("variable.language"), s("variable.defaultLibrary")]);
+        map.register("variable.member", [tm("variable.other.member"), s("property")]);
+        map.register("variable.parameter", [tm("variable.parameter"), s("parameter")]);
+
+        map
+    }
+}
+
+impl CaptureMap {
+    pub fn empty() -> Self {
+        Self { captures: IndexMap::new() }
+    }
+
+    pub fn register(
+        &mut self,
+        capture: impl Into<Cow<'static, str>>,
+        sources: impl IntoIterator<Item = Selector>,
+    ) {
+        self.captures.insert(capture.into(), sources.into_iter().collect());
+    }
+
+    pub fn resolve(&self, theme: &proto::Theme) -> IndexMap<Cow<'static, str>, CaptureStyle> {
+        let semantic_rules = match &theme.semantic_highlighting {
+            semantic::Highlighting::On { rules } => Some(rules),
+            _ => None,
+        };
+
+        let mut resolved = IndexMap::with_capacity(self.captures.len());
+
+        for (capture, sources) in &self.captures {
+            let style = sources.iter().find_map(|source| match source {
+                Selector::Semantic(selector) => {
+                    semantic_rules.and_then(|rules| rules.get(selector)).map(from_semantic)
+                }
+                Selector::TextMate(scope) => {
+                    from_textmate(textmate::resolve(&theme.textmate_rules, &[scope]))
+                }
+            });
+
+            if let Some(style) = style {
+                resolved.insert(capture.clone(), style);
+            }
+        }
+
+        resolved
+    }
+}
+
+fn from_semantic(style: &semantic::Style) -> CaptureStyle {
+    let is_set = |setting| setting == semantic::FontStyleSetting::True;
+
+    CaptureStyle {
+        foreground: style.foreground,
+        bold: is_set(style.font_style.bold),
+        italic: is_set(style.font_style.italic),
+        underline: is_set(style.font_style.underline),
+    }
+}
+
+fn from_textmate(settings: textmate::RuleSettings) -> Option<CaptureStyle> {
+    match settings.font_style {
+        textmate::FontStyle::Inherit if settings.foreground.is_none() => None,
+        textmate::FontStyle::Inherit => Some(CaptureStyle {
+            foreground: settings.foreground,
+            bold: false,
+            italic: false,
+            underline: false,
+        }),
+        textmate::FontStyle::Set { bold, italic, underline } => {
+            Some(CaptureStyle { foreground: settings.foreground, bold, italic, underline })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{FontStyle, ThemeBuilder};
+    use expect_test::expect;
+    use std::fmt::Write;
+
+    #[test]
+    fn resolve_captures() {
+        let mut t = ThemeBuilder::default();
+        t.a([tm("keyword"), s("keyword")], 0x569CD6);
+        t.a([tm("comment")], (0x6A9955, FontStyle::Italic));
+        t.a([tm("entity.name.function")], 0xDCDCAA);
+        t.a([s("function.defaultLibrary")], (0xDCDCAA, FontStyle::Bold));
+        t.a([tm("string"), tm("constant.character.escape")], 0xCE9178);
+        t.a([tm("entity.name.lifetime")], 0x4EC9B0);
+        let theme = t.build("My cool theme");
+
+        let mut captures = CaptureMap::default();
+        captures.register("lifetime", [tm("entity.name.lifetime")]);
+
+        let mut actual = String::new();
+        for (capture, style) in captures.resolve(&theme) {
+            write!(actual, "@{capture}:").unwrap();
+            if let Some(foreground) = style.foreground {
+                write!(actual, " {foreground}").unwrap();
+            }
+            for (set, name) in
+                [(style.bold, "bold"), (style.italic, "italic"), (style.underline, "underline")]
+            {
+                if set {
+                    write!(actual, " {name}").unwrap();
+                }
+            }
+            writeln!(actual).unwrap();
+        }
+
+        expect![[r#"
+            @comment: #6A9955 italic
+            @comment.documentation: #6A9955 italic
+            @constructor: #DCDCAA
+            @function: #DCDCAA
+            @function.builtin: #DCDCAA bold
+            @function.macro: #DCDCAA
+            @function.method: #DCDCAA
+            @keyword: #569CD6
+            @keyword.storage: #569CD6
+            @operator: #569CD6
+            @string: #CE9178
+            @string.escape: #CE9178
+            @string.regexp: #CE9178
+            @string.special: #CE9178
+            @lifetime: #4EC9B0
+        "#]]
+        .assert_eq(&actual);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::proto::{self, semantic, textmate};
+use std::fmt;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoverageGaps {
+    pub workbench_keys: Vec<String>,
+    pub textmate_scopes: Vec<String>,
+    pub semantic_selectors: Vec<semantic::Selector>,
+}
+
+impl CoverageGaps {
+    pub fn is_empty(&self) -> bool {
+        self.workbench_keys.is_empty()
+            && self.textmate_scopes.is_empty()
+            && self.semantic_selectors.is_empty()
+    }
+}
+
+pub fn compare_coverage(mine: &proto::Theme, reference: &proto::Theme) -> CoverageGaps {
+    let mut gaps = CoverageGaps::default();
+
+    for key in reference.workbench_rules.keys() {
+        if !mine.workbench_rules.contains_key(key) {
+            gaps.workbench_keys.push(key.to_string());
+        }
+    }
+
+    for rule in &reference.textmate_rules {
+        for scope in &rule.scope {
+            let stack: Vec<_> = scope.split_whitespace().collect();
+            let settings = textmate::resolve(&mine.textmate_rules, &stack);
+            let covered = settings.foreground.is_some()
+                || settings.font_style != textmate::FontStyle::Inherit;
+
+            if !covered && !gaps.textmate_scopes.iter().any(|s| s == scope) {
+                gaps.textmate_scopes.push(scope.to_string());
+            }
+        }
+    }
+
+    let rules = |theme: &proto::Theme| match &theme.semantic_highlighting {
+        semantic::Highlighting::On { rules } => rules.keys().cloned().collect(),
+        _ => Vec::new(),
+    };
+    let mine_rules = rules(mine);
+
+    for selector in rules(reference) {
+        if !mine_rules.iter().any(|rule| selector.refines(rule)) {
+            gaps.semantic_selectors.push(selector);
+        }
+    }
+
+    gaps
+}
+
+impl fmt::Display for CoverageGaps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sections: [(&str, Vec<String>); 3] = [
+            ("workbench colors", self.workbench_keys.clone()),
+            ("textmate scopes", self.textmate_scopes.clone()),
+            (
+                "semantic selectors",
+                self.semantic_selectors.iter().map(ToString::to_string).collect(),
+            ),
+        ];
+
+        for (title, missing) in sections {
+            if missing.is_empty() {
+                continue;
+            }
+
+            writeln!(f, "missing {title} ({}):", missing.len())?;
+            for item in missing {
+                writeln!(f, "  {item}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{s, tm, ThemeBuilder};
+    use expect_test::expect;
+
+    #[test]
+    fn gaps_against_reference() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x1E1E1E);
+        t.w(["editor.foreground"], 0xD4D4D4);
+        t.a([tm("keyword"), tm("storage"), tm("comment")], 0x569CD6);
+        t.a([tm("string"), tm("constant")], 0xCE9178);
+        t.a([s("*")], 0xD4D4D4);
+        let mine = t.build("Mine");
+
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background", "editor.foreground", "sideBar.background"], 0x252526);
+        t.a([tm("keyword.control"), tm("source.cpp keyword.operator.new")], 0xC586C0);
+        t.a([tm("entity.name.function"), tm("constant.numeric")], 0xDCDCAA);
+        t.a([tm("variable"), tm("support.variable")], 0x9CDCFE);
+        t.a([s("newOperator")], 0xC586C0);
+        let reference = t.build("Reference");
+
+        let gaps = compare_coverage(&mine, &reference);
+        expect![[r#"
+            missing workbench colors (1):
+              sideBar.background
+            missing textmate scopes (3):
+              entity.name.function
+              variable
+              support.variable
+        "#]]
+        .assert_eq(&gaps.to_string());
+
+        assert!(compare_coverage(&reference, &reference).is_empty());
+    }
+}
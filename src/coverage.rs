@@ -0,0 +1,248 @@
+use crate::proto;
+use indexmap::IndexMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+use syntect::parsing::{ParseState, ScopeStack, SyntaxSet};
+use syntect::util::LinesWithEndings;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Report {
+    pub files: usize,
+    pub tokens: usize,
+    pub unstyled: Vec<UnstyledScope>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnstyledScope {
+    pub scope: String,
+    pub count: usize,
+}
+
+pub fn analyze_dir(theme: &proto::Theme, dir: impl AsRef<Path>) -> Result<Report, AnalyzeError> {
+    let syntax_set = SyntaxSet::load_defaults_newlines();
+    let mut analyzer = Analyzer::new(theme);
+    let mut paths = Vec::new();
+    collect_files(dir.as_ref(), &mut paths)?;
+    paths.sort();
+
+    for path in paths {
+        let syntax = match syntax_set.find_syntax_for_file(&path) {
+            Ok(Some(syntax)) => syntax,
+            Ok(None) => continue,
+            Err(e) => return Err(AnalyzeError::ReadFile(e, path)),
+        };
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => continue,
+            Err(e) => return Err(AnalyzeError::ReadFile(e, path)),
+        };
+
+        analyzer
+            .analyze_file(&syntax_set, ParseState::new(syntax), &text)
+            .map_err(|e| AnalyzeError::Parse(e, path))?;
+    }
+
+    Ok(analyzer.finish())
+}
+
+fn collect_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), AnalyzeError> {
+    let entries = fs::read_dir(dir).map_err(|e| AnalyzeError::ReadDir(e, dir.to_path_buf()))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| AnalyzeError::ReadDir(e, dir.to_path_buf()))?;
+        let path = entry.path();
+
+        if path.is_dir() {
+            collect_files(&path, paths)?;
+        } else {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+struct Analyzer<'a> {
+    foreground_selectors: Vec<&'a str>,
+    unstyled: IndexMap<String, usize>,
+    report: Report,
+}
+
+impl<'a> Analyzer<'a> {
+    fn new(theme: &'a proto::Theme) -> Self {
+        let foreground_selectors = theme
+            .textmate_rules
+            .iter()
+            .filter(|rule| rule.settings.foreground.is_some())
+            .flat_map(|rule| rule.scope.iter())
+            .flat_map(|scope| scope.split(','))
+            .map(str::trim)
+            .filter(|selector| !selector.is_empty())
+            .collect();
+
+        Self { foreground_selectors, unstyled: IndexMap::new(), report: Report::default() }
+    }
+
+    fn analyze_file(
+        &mut self,
+        syntax_set: &SyntaxSet,
+        mut state: ParseState,
+        text: &str,
+    ) -> Result<(), syntect::Error> {
+        self.report.files += 1;
+
+        let mut stack = ScopeStack::new();
+
+        for line in LinesWithEndings::from(text) {
+            let ops = state.parse_line(line, syntax_set)?;
+            let mut start = 0;
+
+            for (idx, op) in ops {
+                self.token(&line[start..idx], &stack);
+                stack.apply(&op)?;
+                start = idx;
+            }
+
+            self.token(&line[start..], &stack);
+        }
+
+        Ok(())
+    }
+
+    fn token(&mut self, text: &str, stack: &ScopeStack) {
+        if text.trim().is_empty() {
+            return;
+        }
+
+        self.report.tokens += 1;
+
+        let scopes: Vec<_> = stack.as_slice().iter().map(|s| s.build_string()).collect();
+        let scopes: Vec<_> = scopes.iter().map(String::as_str).collect();
+
+        let is_styled = self
+            .foreground_selectors
+            .iter()
+            .any(|selector| proto::textmate::selector_matches(selector, &scopes));
+
+        if !is_styled {
+            let innermost = scopes.last().copied().unwrap_or_default();
+            *self.unstyled.entry(innermost.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    fn finish(mut self) -> Report {
+        self.unstyled.sort_by(|_, a, _, b| b.cmp(a));
+        self.report.unstyled = self
+            .unstyled
+            .into_iter()
+            .map(|(scope, count)| UnstyledScope { scope, count })
+            .collect();
+
+        self.report
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unstyled: usize = self.unstyled.iter().map(|s| s.count).sum();
+        let files = if self.files == 1 { "file" } else { "files" };
+        writeln!(
+            f,
+            "{unstyled} of {} tokens in {} {files} use the default foreground",
+            self.tokens, self.files
+        )?;
+
+        for UnstyledScope { scope, count } in &self.unstyled {
+            writeln!(f, "{count:>8}  {scope}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AnalyzeError {
+    #[error("failed reading directory `{1}`")]
+    ReadDir(#[source] io::Error, PathBuf),
+    #[error("failed reading `{1}`")]
+    ReadFile(#[source] io::Error, PathBuf),
+    #[error("failed tokenizing `{1}`")]
+    Parse(#[source] syntect::Error, PathBuf),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{tm, ThemeBuilder};
+    use crate::tests::temp_dir;
+    use expect_test::expect;
+
+    fn analyze(theme: &proto::Theme, extension: &str, text: &str) -> Report {
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+        let syntax = syntax_set.find_syntax_by_extension(extension).unwrap();
+        let mut analyzer = Analyzer::new(theme);
+        analyzer.analyze_file(&syntax_set, ParseState::new(syntax), text).unwrap();
+
+        analyzer.finish()
+    }
+
+    #[test]
+    fn selector_matching() {
+        let stack = ["source.rust", "meta.function.rust", "entity.name.function.rust"];
+
+        assert!(proto::textmate::selector_matches("entity.name", &stack));
+        assert!(proto::textmate::selector_matches("meta.function entity", &stack));
+        assert!(!proto::textmate::selector_matches("entity.name.func", &stack));
+        assert!(!proto::textmate::selector_matches("entity meta.function", &stack));
+    }
+
+    #[test]
+    fn reports_unstyled_scopes_by_count() {
+        let mut t = ThemeBuilder::default();
+        t.a([tm("keyword")], 0xFF0000);
+        let theme = t.build("My cool theme");
+
+        let report = analyze(&theme, "rs", "fn main() {\n    let x = 1;\n    let y = 2;\n}\n");
+
+        expect![[r#"
+            14 of 16 tokens in 1 file use the default foreground
+                   2  storage.type.rust
+                   2  meta.block.rust
+                   2  constant.numeric.integer.decimal.rust
+                   2  punctuation.terminator.rust
+                   1  storage.type.function.rust
+                   1  entity.name.function.rust
+                   1  punctuation.section.parameters.begin.rust
+                   1  punctuation.section.parameters.end.rust
+                   1  punctuation.section.block.begin.rust
+                   1  punctuation.section.block.end.rust
+        "#]]
+        .assert_eq(&report.to_string());
+    }
+
+    #[test]
+    fn analyze_dir_walks_nested_files() {
+        let dir = temp_dir("coverage-analyze-dir");
+        fs::create_dir_all(dir.join("src/nested")).unwrap();
+        fs::write(dir.join("src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(dir.join("src/nested/lib.rs"), "struct Empty;\n").unwrap();
+        fs::write(dir.join("notes.unknown-extension"), "ignored\n").unwrap();
+
+        let mut t = ThemeBuilder::default();
+        t.a([tm("storage.type"), tm("entity.name")], 0x569CD6);
+        let theme = t.build("My cool theme");
+
+        expect![[r#"
+            5 of 9 tokens in 2 files use the default foreground
+                   1  punctuation.section.parameters.begin.rust
+                   1  punctuation.section.parameters.end.rust
+                   1  punctuation.section.block.begin.rust
+                   1  punctuation.section.block.end.rust
+                   1  punctuation.terminator.rust
+        "#]]
+        .assert_eq(&analyze_dir(&theme, &dir).unwrap().to_string());
+    }
+}
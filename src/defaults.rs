@@ -0,0 +1,662 @@
+use crate::dsl::{s, tm, Color, FontStyle, Style, ThemeBuilder};
+use crate::proto::{self, ThemeType};
+
+type Rules = &'static [(&'static [&'static str], Style)];
+
+pub fn dark_plus() -> proto::Theme {
+    let mut t = ThemeBuilder::default();
+    t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+    font_styles(&mut t);
+    add(&mut t, DARK_VS_RULES, DARK_PLUS_RULES, DARK_PLUS_SEMANTIC, DARK_VS_COLORS);
+    t.build_with_type("Dark+", ThemeType::Dark)
+}
+
+pub fn light_plus() -> proto::Theme {
+    let mut t = ThemeBuilder::default();
+    t.global_token_defaults(0x000000, 0xFFFFFF);
+    font_styles(&mut t);
+    add(&mut t, LIGHT_VS_RULES, LIGHT_PLUS_RULES, LIGHT_PLUS_SEMANTIC, LIGHT_VS_COLORS);
+    t.build_with_type("Light+", ThemeType::Light)
+}
+
+fn font_styles(t: &mut ThemeBuilder) {
+    t.a([tm("emphasis")], FontStyle::Italic);
+    t.a([tm("strong")], FontStyle::Bold);
+    t.a([tm("markup.underline")], FontStyle::Underline);
+    t.a([tm("markup.italic")], FontStyle::Italic);
+}
+
+fn add(
+    t: &mut ThemeBuilder,
+    base: Rules,
+    plus: Rules,
+    semantic: &[(&'static str, u32)],
+    colors: &[(&str, u32)],
+) {
+    for (scopes, style) in base.iter().chain(plus) {
+        t.a(scopes.iter().copied().map(tm), *style);
+    }
+
+    for (selector, color) in semantic {
+        t.a([s(selector)], *color);
+    }
+
+    for (key, color) in colors {
+        t.w([*key], Color::rgba(*color));
+    }
+}
+
+const DARK_VS_RULES: Rules = &[
+    (
+        &[
+            "meta.embedded",
+            "source.groovy.embedded",
+            "string meta.image.inline.markdown",
+            "variable.legacy.builtin.python",
+        ],
+        Style::rgb(0xD4D4D4),
+    ),
+    (&["header"], Style::rgb(0x000080)),
+    (&["comment"], Style::rgb(0x6A9955)),
+    (&["constant.language"], Style::rgb(0x569CD6)),
+    (
+        &[
+            "constant.numeric",
+            "variable.other.enummember",
+            "keyword.operator.plus.exponent",
+            "keyword.operator.minus.exponent",
+        ],
+        Style::rgb(0xB5CEA8),
+    ),
+    (&["constant.regexp"], Style::rgb(0x646695)),
+    (&["entity.name.tag"], Style::rgb(0x569CD6)),
+    (&["entity.name.tag.css", "entity.name.tag.less"], Style::rgb(0xD7BA7D)),
+    (&["entity.other.attribute-name"], Style::rgb(0x9CDCFE)),
+    (
+        &[
+            "entity.other.attribute-name.class.css",
+            "source.css entity.other.attribute-name.class",
+            "entity.other.attribute-name.id.css",
+            "entity.other.attribute-name.parent-selector.css",
+            "entity.other.attribute-name.parent.less",
+            "source.css entity.other.attribute-name.pseudo-class",
+            "entity.other.attribute-name.pseudo-element.css",
+            "source.css.less entity.other.attribute-name.id",
+            "entity.other.attribute-name.scss",
+        ],
+        Style::rgb(0xD7BA7D),
+    ),
+    (&["invalid"], Style::rgb(0xF44747)),
+    (&["markup.bold"], Style::rgb(0x569CD6).with(FontStyle::Bold)),
+    (&["markup.heading"], Style::rgb(0x569CD6).with(FontStyle::Bold)),
+    (&["markup.inserted"], Style::rgb(0xB5CEA8)),
+    (&["markup.deleted"], Style::rgb(0xCE9178)),
+    (&["markup.changed"], Style::rgb(0x569CD6)),
+    (&["punctuation.definition.quote.begin.markdown"], Style::rgb(0x6A9955)),
+    (&["punctuation.definition.list.begin.markdown"], Style::rgb(0x6796E6)),
+    (&["markup.inline.raw"], Style::rgb(0xCE9178)),
+    (&["punctuation.definition.tag"], Style::rgb(0x808080)),
+    (&["meta.preprocessor", "entity.name.function.preprocessor"], Style::rgb(0x569CD6)),
+    (&["meta.preprocessor.string"], Style::rgb(0xCE9178)),
+    (&["meta.preprocessor.numeric"], Style::rgb(0xB5CEA8)),
+    (&["meta.structure.dictionary.key.python"], Style::rgb(0x9CDCFE)),
+    (&["meta.diff.header"], Style::rgb(0x569CD6)),
+    (&["storage"], Style::rgb(0x569CD6)),
+    (&["storage.type"], Style::rgb(0x569CD6)),
+    (&["storage.modifier", "keyword.operator.noexcept"], Style::rgb(0x569CD6)),
+    (&["string", "meta.embedded.assembly"], Style::rgb(0xCE9178)),
+    (&["string.tag"], Style::rgb(0xCE9178)),
+    (&["string.value"], Style::rgb(0xCE9178)),
+    (&["string.regexp"], Style::rgb(0xD16969)),
+    (
+        &[
+            "punctuation.definition.template-expression.begin",
+            "punctuation.definition.template-expression.end",
+            "punctuation.section.embedded",
+        ],
+        Style::rgb(0x569CD6),
+    ),
+    (&["meta.template.expression"], Style::rgb(0xD4D4D4)),
+    (
+        &[
+            "support.type.vendored.property-name",
+            "support.type.property-name",
+            "source.css variable",
+            "source.coffee.embedded",
+        ],
+        Style::rgb(0x9CDCFE),
+    ),
+    (&["keyword"], Style::rgb(0x569CD6)),
+    (&["keyword.control"], Style::rgb(0x569CD6)),
+    (&["keyword.operator"], Style::rgb(0xD4D4D4)),
+    (
+        &[
+            "keyword.operator.new",
+            "keyword.operator.expression",
+            "keyword.operator.cast",
+            "keyword.operator.sizeof",
+            "keyword.operator.alignof",
+            "keyword.operator.typeid",
+            "keyword.operator.alignas",
+            "keyword.operator.instanceof",
+            "keyword.operator.logical.python",
+            "keyword.operator.wordlike",
+        ],
+        Style::rgb(0x569CD6),
+    ),
+    (&["keyword.other.unit"], Style::rgb(0xB5CEA8)),
+    (
+        &["punctuation.section.embedded.begin.php", "punctuation.section.embedded.end.php"],
+        Style::rgb(0x569CD6),
+    ),
+    (&["support.function.git-rebase"], Style::rgb(0x9CDCFE)),
+    (&["constant.sha.git-rebase"], Style::rgb(0xB5CEA8)),
+    (
+        &[
+            "storage.modifier.import.java",
+            "variable.language.wildcard.java",
+            "storage.modifier.package.java",
+        ],
+        Style::rgb(0xD4D4D4),
+    ),
+    (&["variable.language"], Style::rgb(0x569CD6)),
+];
+
+const DARK_PLUS_RULES: Rules = &[
+    (
+        &[
+            "entity.name.function",
+            "support.function",
+            "support.constant.handlebars",
+            "source.powershell variable.other.member",
+            "entity.name.operator.custom-literal",
+        ],
+        Style::rgb(0xDCDCAA),
+    ),
+    (
+        &[
+            "support.class",
+            "support.type",
+            "entity.name.type",
+            "entity.name.namespace",
+            "entity.other.attribute",
+            "entity.name.scope-resolution",
+            "entity.name.class",
+            "storage.type.numeric.go",
+            "storage.type.byte.go",
+            "storage.type.boolean.go",
+            "storage.type.string.go",
+            "storage.type.uintptr.go",
+            "storage.type.error.go",
+            "storage.type.rune.go",
+            "storage.type.cs",
+            "storage.type.generic.cs",
+            "storage.type.modifier.cs",
+            "storage.type.variable.cs",
+            "storage.type.annotation.java",
+            "storage.type.generic.java",
+            "storage.type.java",
+            "storage.type.object.array.java",
+            "storage.type.primitive.array.java",
+            "storage.type.primitive.java",
+            "storage.type.token.java",
+            "storage.type.groovy",
+            "storage.type.annotation.groovy",
+            "storage.type.parameters.groovy",
+            "storage.type.generic.groovy",
+            "storage.type.object.array.groovy",
+            "storage.type.primitive.array.groovy",
+            "storage.type.primitive.groovy",
+        ],
+        Style::rgb(0x4EC9B0),
+    ),
+    (
+        &[
+            "meta.type.cast.expr",
+            "meta.type.new.expr",
+            "support.constant.math",
+            "support.constant.dom",
+            "support.constant.json",
+            "entity.other.inherited-class",
+            "punctuation.separator.namespace.ruby",
+        ],
+        Style::rgb(0x4EC9B0),
+    ),
+    (
+        &[
+            "keyword.control",
+            "source.cpp keyword.operator.new",
+            "keyword.operator.delete",
+            "keyword.other.using",
+            "keyword.other.directive.using",
+            "keyword.other.operator",
+            "entity.name.operator",
+        ],
+        Style::rgb(0xC586C0),
+    ),
+    (
+        &[
+            "variable",
+            "meta.definition.variable.name",
+            "support.variable",
+            "entity.name.variable",
+            "constant.other.placeholder",
+        ],
+        Style::rgb(0x9CDCFE),
+    ),
+    (&["variable.other.constant", "variable.other.enummember"], Style::rgb(0x4FC1FF)),
+    (&["meta.object-literal.key"], Style::rgb(0x9CDCFE)),
+    (
+        &[
+            "support.constant.property-value",
+            "support.constant.font-name",
+            "support.constant.media-type",
+            "support.constant.media",
+            "constant.other.color.rgb-value",
+            "constant.other.rgb-value",
+            "support.constant.color",
+        ],
+        Style::rgb(0xCE9178),
+    ),
+    (
+        &[
+            "punctuation.definition.group.regexp",
+            "punctuation.definition.group.assertion.regexp",
+            "punctuation.definition.character-class.regexp",
+            "punctuation.character.set.begin.regexp",
+            "punctuation.character.set.end.regexp",
+            "keyword.operator.negation.regexp",
+            "support.other.parenthesis.regexp",
+        ],
+        Style::rgb(0xCE9178),
+    ),
+    (
+        &[
+            "constant.character.character-class.regexp",
+            "constant.other.character-class.set.regexp",
+            "constant.other.character-class.regexp",
+            "constant.character.set.regexp",
+        ],
+        Style::rgb(0xD16969),
+    ),
+    (&["keyword.operator.or.regexp", "keyword.control.anchor.regexp"], Style::rgb(0xDCDCAA)),
+    (&["keyword.operator.quantifier.regexp"], Style::rgb(0xD7BA7D)),
+    (&["constant.character", "constant.other.option"], Style::rgb(0x569CD6)),
+    (&["constant.character.escape"], Style::rgb(0xD7BA7D)),
+    (&["entity.name.label"], Style::rgb(0xC8C8C8)),
+];
+
+const DARK_PLUS_SEMANTIC: &[(&str, u32)] = &[
+    ("newOperator", 0xC586C0),
+    ("stringLiteral", 0xCE9178),
+    ("customLiteral", 0xDCDCAA),
+    ("numberLiteral", 0xB5CEA8),
+];
+
+const DARK_VS_COLORS: &[(&str, u32)] = &[
+    ("checkbox.border", 0x6B6B6BFF),
+    ("editor.background", 0x1E1E1EFF),
+    ("editor.foreground", 0xD4D4D4FF),
+    ("editor.inactiveSelectionBackground", 0x3A3D41FF),
+    ("editorIndentGuide.background1", 0x404040FF),
+    ("editorIndentGuide.activeBackground1", 0x707070FF),
+    ("editor.selectionHighlightBackground", 0xADD6FF26),
+    ("list.dropBackground", 0x383B3DFF),
+    ("activityBarBadge.background", 0x007ACCFF),
+    ("sideBarTitle.foreground", 0xBBBBBBFF),
+    ("input.placeholderForeground", 0xA6A6A6FF),
+    ("menu.background", 0x252526FF),
+    ("menu.foreground", 0xCCCCCCFF),
+    ("menu.separatorBackground", 0x454545FF),
+    ("menu.border", 0x454545FF),
+    ("statusBarItem.remoteForeground", 0xFFFFFFFF),
+    ("statusBarItem.remoteBackground", 0x16825DFF),
+    ("ports.iconRunningProcessForeground", 0x369432FF),
+    ("sideBarSectionHeader.background", 0x00000000),
+    ("sideBarSectionHeader.border", 0xCCCCCC33),
+    ("tab.lastPinnedBorder", 0xCCCCCC33),
+    ("list.activeSelectionIconForeground", 0xFFFFFFFF),
+    ("terminal.inactiveSelectionBackground", 0x3A3D41FF),
+    ("widget.border", 0x303031FF),
+];
+
+const LIGHT_VS_RULES: Rules = &[
+    (
+        &[
+            "meta.embedded",
+            "source.groovy.embedded",
+            "string meta.image.inline.markdown",
+            "variable.legacy.builtin.python",
+        ],
+        Style::rgb(0x000000),
+    ),
+    (&["meta.diff.header"], Style::rgb(0x000080)),
+    (&["comment"], Style::rgb(0x008000)),
+    (&["constant.language"], Style::rgb(0x0000FF)),
+    (
+        &[
+            "constant.numeric",
+            "variable.other.enummember",
+            "keyword.operator.plus.exponent",
+            "keyword.operator.minus.exponent",
+        ],
+        Style::rgb(0x098658),
+    ),
+    (&["constant.regexp"], Style::rgb(0x811F3F)),
+    (&["entity.name.tag"], Style::rgb(0x800000)),
+    (&["entity.name.selector"], Style::rgb(0x800000)),
+    (&["entity.other.attribute-name"], Style::rgb(0xE50000)),
+    (
+        &[
+            "entity.other.attribute-name.class.css",
+            "source.css entity.other.attribute-name.class",
+            "entity.other.attribute-name.id.css",
+            "entity.other.attribute-name.parent-selector.css",
+            "entity.other.attribute-name.parent.less",
+            "source.css entity.other.attribute-name.pseudo-class",
+            "entity.other.attribute-name.pseudo-element.css",
+            "source.css.less entity.other.attribute-name.id",
+            "entity.other.attribute-name.scss",
+        ],
+        Style::rgb(0x800000),
+    ),
+    (&["invalid"], Style::rgb(0xCD3131)),
+    (&["markup.bold"], Style::rgb(0x000080).with(FontStyle::Bold)),
+    (&["markup.heading"], Style::rgb(0x800000).with(FontStyle::Bold)),
+    (&["markup.inserted"], Style::rgb(0x098658)),
+    (&["markup.deleted"], Style::rgb(0xA31515)),
+    (&["markup.changed"], Style::rgb(0x0451A5)),
+    (
+        &[
+            "punctuation.definition.quote.begin.markdown",
+            "punctuation.definition.list.begin.markdown",
+        ],
+        Style::rgb(0x0451A5),
+    ),
+    (&["markup.inline.raw"], Style::rgb(0x800000)),
+    (&["punctuation.definition.tag"], Style::rgb(0x800000)),
+    (&["meta.preprocessor", "entity.name.function.preprocessor"], Style::rgb(0x0000FF)),
+    (&["meta.preprocessor.string"], Style::rgb(0xA31515)),
+    (&["meta.preprocessor.numeric"], Style::rgb(0x098658)),
+    (&["meta.structure.dictionary.key.python"], Style::rgb(0x0451A5)),
+    (&["storage"], Style::rgb(0x0000FF)),
+    (&["storage.type"], Style::rgb(0x0000FF)),
+    (&["storage.modifier", "keyword.operator.noexcept"], Style::rgb(0x0000FF)),
+    (&["string", "meta.embedded.assembly"], Style::rgb(0xA31515)),
+    (
+        &[
+            "string.comment.buffered.block.pug",
+            "string.quoted.pug",
+            "string.interpolated.pug",
+            "string.unquoted.plain.in.yaml",
+            "string.unquoted.plain.out.yaml",
+            "string.unquoted.block.yaml",
+            "string.quoted.single.yaml",
+            "string.quoted.double.xml",
+            "string.quoted.single.xml",
+            "string.unquoted.cdata.xml",
+            "string.quoted.double.html",
+            "string.quoted.single.html",
+            "string.unquoted.html",
+            "string.quoted.single.handlebars",
+            "string.quoted.double.handlebars",
+        ],
+        Style::rgb(0x0000FF),
+    ),
+    (&["string.regexp"], Style::rgb(0x811F3F)),
+    (
+        &[
+            "punctuation.definition.template-expression.begin",
+            "punctuation.definition.template-expression.end",
+            "punctuation.section.embedded",
+        ],
+        Style::rgb(0x0000FF),
+    ),
+    (&["meta.template.expression"], Style::rgb(0x000000)),
+    (
+        &[
+            "support.constant.property-value",
+            "support.constant.font-name",
+            "support.constant.media-type",
+            "support.constant.media",
+            "constant.other.color.rgb-value",
+            "constant.other.rgb-value",
+            "support.constant.color",
+        ],
+        Style::rgb(0x0451A5),
+    ),
+    (
+        &[
+            "support.type.vendored.property-name",
+            "support.type.property-name",
+            "source.css variable",
+            "source.coffee.embedded",
+        ],
+        Style::rgb(0xE50000),
+    ),
+    (&["support.type.property-name.json"], Style::rgb(0x0451A5)),
+    (&["keyword"], Style::rgb(0x0000FF)),
+    (&["keyword.control"], Style::rgb(0x0000FF)),
+    (&["keyword.operator"], Style::rgb(0x000000)),
+    (
+        &[
+            "keyword.operator.new",
+            "keyword.operator.expression",
+            "keyword.operator.cast",
+            "keyword.operator.sizeof",
+            "keyword.operator.alignof",
+            "keyword.operator.typeid",
+            "keyword.operator.alignas",
+            "keyword.operator.instanceof",
+            "keyword.operator.logical.python",
+            "keyword.operator.wordlike",
+        ],
+        Style::rgb(0x0000FF),
+    ),
+    (&["keyword.other.unit"], Style::rgb(0x098658)),
+    (
+        &["punctuation.section.embedded.begin.php", "punctuation.section.embedded.end.php"],
+        Style::rgb(0x800000),
+    ),
+    (&["support.function.git-rebase"], Style::rgb(0x0451A5)),
+    (&["constant.sha.git-rebase"], Style::rgb(0x098658)),
+    (
+        &[
+            "storage.modifier.import.java",
+            "variable.language.wildcard.java",
+            "storage.modifier.package.java",
+        ],
+        Style::rgb(0x000000),
+    ),
+    (&["variable.language"], Style::rgb(0x0000FF)),
+];
+
+const LIGHT_PLUS_RULES: Rules = &[
+    (
+        &[
+            "entity.name.function",
+            "support.function",
+            "support.constant.handlebars",
+            "source.powershell variable.other.member",
+            "entity.name.operator.custom-literal",
+        ],
+        Style::rgb(0x795E26),
+    ),
+    (
+        &[
+            "support.class",
+            "support.type",
+            "entity.name.type",
+            "entity.name.namespace",
+            "entity.other.attribute",
+            "entity.name.scope-resolution",
+            "entity.name.class",
+            "storage.type.numeric.go",
+            "storage.type.byte.go",
+            "storage.type.boolean.go",
+            "storage.type.string.go",
+            "storage.type.uintptr.go",
+            "storage.type.error.go",
+            "storage.type.rune.go",
+            "storage.type.cs",
+            "storage.type.generic.cs",
+            "storage.type.modifier.cs",
+            "storage.type.variable.cs",
+            "storage.type.annotation.java",
+            "storage.type.generic.java",
+            "storage.type.java",
+            "storage.type.object.array.java",
+            "storage.type.primitive.array.java",
+            "storage.type.primitive.java",
+            "storage.type.token.java",
+            "storage.type.groovy",
+            "storage.type.annotation.groovy",
+            "storage.type.parameters.groovy",
+            "storage.type.generic.groovy",
+            "storage.type.object.array.groovy",
+            "storage.type.primitive.array.groovy",
+            "storage.type.primitive.groovy",
+        ],
+        Style::rgb(0x267F99),
+    ),
+    (
+        &[
+            "meta.type.cast.expr",
+            "meta.type.new.expr",
+            "support.constant.math",
+            "support.constant.dom",
+            "support.constant.json",
+            "entity.other.inherited-class",
+            "punctuation.separator.namespace.ruby",
+        ],
+        Style::rgb(0x267F99),
+    ),
+    (
+        &[
+            "keyword.control",
+            "source.cpp keyword.operator.new",
+            "source.cpp keyword.operator.delete",
+            "keyword.other.using",
+            "keyword.other.directive.using",
+            "keyword.other.operator",
+            "entity.name.operator",
+        ],
+        Style::rgb(0xAF00DB),
+    ),
+    (
+        &[
+            "variable",
+            "meta.definition.variable.name",
+            "support.variable",
+            "entity.name.variable",
+            "constant.other.placeholder",
+        ],
+        Style::rgb(0x001080),
+    ),
+    (&["variable.other.constant", "variable.other.enummember"], Style::rgb(0x0070C1)),
+    (&["meta.object-literal.key"], Style::rgb(0x001080)),
+    (
+        &[
+            "punctuation.definition.group.regexp",
+            "punctuation.definition.group.assertion.regexp",
+            "punctuation.definition.character-class.regexp",
+            "punctuation.character.set.begin.regexp",
+            "punctuation.character.set.end.regexp",
+            "keyword.operator.negation.regexp",
+            "support.other.parenthesis.regexp",
+        ],
+        Style::rgb(0xD16969),
+    ),
+    (
+        &[
+            "constant.character.character-class.regexp",
+            "constant.other.character-class.set.regexp",
+            "constant.other.character-class.regexp",
+            "constant.character.set.regexp",
+        ],
+        Style::rgb(0x811F3F),
+    ),
+    (&["keyword.operator.quantifier.regexp"], Style::rgb(0x000000)),
+    (&["keyword.operator.or.regexp", "keyword.control.anchor.regexp"], Style::rgb(0xEE0000)),
+    (&["constant.character", "constant.other.option"], Style::rgb(0x0000FF)),
+    (&["constant.character.escape"], Style::rgb(0xEE0000)),
+    (&["entity.name.label"], Style::rgb(0x000000)),
+];
+
+const LIGHT_PLUS_SEMANTIC: &[(&str, u32)] = &[
+    ("newOperator", 0xAF00DB),
+    ("stringLiteral", 0xA31515),
+    ("customLiteral", 0x795E26),
+    ("numberLiteral", 0x098658),
+];
+
+const LIGHT_VS_COLORS: &[(&str, u32)] = &[
+    ("checkbox.border", 0x919191FF),
+    ("editor.background", 0xFFFFFFFF),
+    ("editor.foreground", 0x000000FF),
+    ("editor.inactiveSelectionBackground", 0xE5EBF1FF),
+    ("editorIndentGuide.background1", 0xD3D3D3FF),
+    ("editorIndentGuide.activeBackground1", 0x939393FF),
+    ("editor.selectionHighlightBackground", 0xADD6FF80),
+    ("editorSuggestWidget.background", 0xF3F3F3FF),
+    ("activityBarBadge.background", 0x007ACCFF),
+    ("sideBarTitle.foreground", 0x6F6F6FFF),
+    ("list.hoverBackground", 0xE8E8E8FF),
+    ("list.focusAndSelectionOutline", 0x90C2F9FF),
+    ("input.placeholderForeground", 0x767676FF),
+    ("searchEditor.textInputBorder", 0xCECECEFF),
+    ("settings.numberInputBorder", 0xCECECEFF),
+    ("statusBarItem.remoteForeground", 0xFFFFFFFF),
+    ("statusBarItem.remoteBackground", 0x16825DFF),
+    ("ports.iconRunningProcessForeground", 0x369432FF),
+    ("sideBarSectionHeader.background", 0x00000000),
+    ("sideBarSectionHeader.border", 0x61616130),
+    ("tab.lastPinnedBorder", 0x61616130),
+    ("notebook.cellBorderColor", 0xE8E8E8FF),
+    ("notebook.selectedCellBackground", 0xC8DDF150),
+    ("statusBarItem.errorBackground", 0xC72E0FFF),
+    ("list.activeSelectionIconForeground", 0xFFFFFFFF),
+    ("terminal.inactiveSelectionBackground", 0xE5EBF1FF),
+    ("widget.border", 0xD4D4D4FF),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workbench;
+
+    #[test]
+    fn known_workbench_keys() {
+        for theme in [dark_plus(), light_plus()] {
+            let unknown: Vec<_> =
+                theme.workbench_rules.keys().filter(|key| !workbench::is_known_key(key)).collect();
+            assert!(unknown.is_empty(), "{}: {unknown:?}", theme.name);
+        }
+    }
+
+    #[test]
+    fn token_colors() {
+        let dark = dark_plus();
+        let light = light_plus();
+        let foreground = |theme: &proto::Theme, scope| {
+            proto::textmate::resolve_foreground(&theme.textmate_rules, &[scope])
+                .unwrap()
+                .to_string()
+        };
+
+        let scopes = ["keyword.control.rust", "entity.name.function", "comment.line", "variable"];
+        let actual: Vec<_> = scopes
+            .iter()
+            .map(|scope| (foreground(&dark, scope), foreground(&light, scope)))
+            .collect();
+        assert_eq!(
+            actual,
+            [
+                ("#C586C0".to_string(), "#AF00DB".to_string()),
+                ("#DCDCAA".to_string(), "#795E26".to_string()),
+                ("#6A9955".to_string(), "#008000".to_string()),
+                ("#9CDCFE".to_string(), "#001080".to_string()),
+            ]
+        );
+    }
+}
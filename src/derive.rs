@@ -0,0 +1,228 @@
+use crate::dsl::{Color, SemanticHighlighting, ThemeBuilder};
+use crate::oklab::{mix, Oklch};
+use crate::proto;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct States {
+    pub base: proto::Color,
+    pub hover: proto::Color,
+    pub active: proto::Color,
+    pub border: proto::Color,
+}
+
+const HOVER_SHIFT: f64 = 0.06;
+const ACTIVE_SHIFT: f64 = 0.12;
+const BORDER_SHIFT: f64 = 0.18;
+
+impl<C> From<C> for States
+where
+    C: Into<Color>,
+{
+    fn from(base: C) -> Self {
+        let base: proto::Color = base.into().into();
+        let oklch = Oklch::from(base);
+        let direction = if oklch.l < 0.5 { 1.0 } else { -1.0 };
+        let shift =
+            |amount: f64| Oklch { l: oklch.l + direction * amount, ..oklch }.to_color(base.a);
+
+        Self {
+            base,
+            hover: shift(HOVER_SHIFT),
+            active: shift(ACTIVE_SHIFT),
+            border: shift(BORDER_SHIFT),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GuideLevels {
+    pub whitespace: f64,
+    pub indent_guide: f64,
+    pub active_indent_guide: f64,
+    pub bracket_guide: f64,
+    pub active_bracket_guide: f64,
+    pub ruler: f64,
+}
+
+impl Default for GuideLevels {
+    fn default() -> Self {
+        Self {
+            whitespace: 0.2,
+            indent_guide: 0.1,
+            active_indent_guide: 0.25,
+            bracket_guide: 0.1,
+            active_bracket_guide: 0.25,
+            ruler: 0.08,
+        }
+    }
+}
+
+pub fn guides<S: SemanticHighlighting>(
+    t: &mut ThemeBuilder<S>,
+    background: impl Into<Color>,
+    levels: GuideLevels,
+) {
+    let background: proto::Color = background.into().into();
+    let level = |amount| away_from(background, amount);
+
+    t.w(["editorWhitespace.foreground"], level(levels.whitespace));
+    t.w(["editorRuler.foreground"], level(levels.ruler));
+
+    for (prefix, amount) in [
+        ("editorIndentGuide.background", levels.indent_guide),
+        ("editorIndentGuide.activeBackground", levels.active_indent_guide),
+        ("editorBracketPairGuide.background", levels.bracket_guide),
+        ("editorBracketPairGuide.activeBackground", levels.active_bracket_guide),
+    ] {
+        let keys: Vec<_> = (1..=6).map(|n| format!("{prefix}{n}")).collect();
+        t.w(keys.iter().map(String::as_str), level(amount));
+    }
+}
+
+const SELECTION_ALPHAS: &[(&str, u8, u8)] = &[
+    ("editor.inactiveSelectionBackground", 0x40, 0x50),
+    ("editor.selectionHighlightBackground", 0x26, 0x40),
+    ("editor.wordHighlightBackground", 0x30, 0x40),
+    ("editor.wordHighlightStrongBackground", 0x50, 0x60),
+    ("editor.wordHighlightTextBackground", 0x30, 0x40),
+    ("editor.findMatchHighlightBackground", 0x55, 0x55),
+    ("editor.findRangeHighlightBackground", 0x20, 0x20),
+    ("editor.hoverHighlightBackground", 0x40, 0x26),
+];
+
+pub fn selection<S: SemanticHighlighting>(
+    t: &mut ThemeBuilder<S>,
+    accent: impl Into<Color>,
+    background: impl Into<Color>,
+) {
+    let accent: proto::Color = accent.into().into();
+    let background: proto::Color = background.into().into();
+    let dark = Oklch::from(background).l < 0.5;
+
+    t.w(["editor.selectionBackground"], mix(background, accent, 0.4));
+    t.w(["editor.findMatchBackground"], mix(background, accent, 0.6));
+
+    for (key, dark_alpha, light_alpha) in SELECTION_ALPHAS {
+        let a = if dark { *dark_alpha } else { *light_alpha };
+        t.w([*key], proto::Color { a, ..accent });
+    }
+}
+
+fn away_from(background: proto::Color, amount: f64) -> proto::Color {
+    let oklch = Oklch::from(background);
+    let direction = if oklch.l < 0.5 { 1.0 } else { -1.0 };
+
+    Oklch { l: oklch.l + direction * amount, ..oklch }.to_color(0xFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lint, workbench};
+    use expect_test::expect;
+
+    #[test]
+    fn guide_colors() {
+        let mut t = ThemeBuilder::default();
+        guides(&mut t, 0x1E1E1E, GuideLevels::default());
+        assert!(t.workbench_rules.keys().all(|key| workbench::is_known_key(key)));
+        assert_eq!(t.workbench_rules.len(), 26);
+
+        let mut textmate_only = ThemeBuilder::textmate_only();
+        guides(&mut textmate_only, 0x1E1E1E, GuideLevels::default());
+        assert_eq!(textmate_only.workbench_rules, t.workbench_rules);
+
+        let mut light = ThemeBuilder::default();
+        guides(&mut light, 0xFFFFFF, GuideLevels { ruler: 0.2, ..GuideLevels::default() });
+
+        let colors = |t: &ThemeBuilder| {
+            [
+                "editorWhitespace.foreground",
+                "editorIndentGuide.background3",
+                "editorRuler.foreground",
+            ]
+            .map(|key| t.workbench_rules[key].unwrap().to_string())
+            .join(" ")
+        };
+        expect!["#515151 #373737 #313131"].assert_eq(&colors(&t));
+        expect!["#BEBEBE #DEDEDE #BEBEBE"].assert_eq(&colors(&light));
+    }
+
+    #[test]
+    fn selection_family() {
+        let mut t = ThemeBuilder::default();
+        selection(&mut t, 0x007ACC, 0x1E1E1E);
+        let mut linter = lint::ThemeLinter::empty();
+        linter.add(lint::AlphaUsage);
+        assert!(t.verify_with(&linter).is_empty());
+
+        expect![[r#"
+            #23425E
+              workbench  editor.selectionBackground
+            #215481
+              workbench  editor.findMatchBackground
+            #007ACC40
+              workbench  editor.inactiveSelectionBackground
+              workbench  editor.hoverHighlightBackground
+            #007ACC26
+              workbench  editor.selectionHighlightBackground
+            #007ACC30
+              workbench  editor.wordHighlightBackground
+              workbench  editor.wordHighlightTextBackground
+            #007ACC50
+              workbench  editor.wordHighlightStrongBackground
+            #007ACC55
+              workbench  editor.findMatchHighlightBackground
+            #007ACC20
+              workbench  editor.findRangeHighlightBackground
+        "#]]
+        .assert_eq(&t.dump());
+    }
+
+    #[test]
+    fn interaction_states() {
+        let states = States::from(0x0E639C);
+        let colors = [states.base, states.hover, states.active, states.border];
+        let colors = colors.map(|c| c.to_string()).join(" ");
+        expect!["#0E639C #2875AF #3C87C3 #4F9AD6"].assert_eq(&colors);
+
+        let mut t = ThemeBuilder::default();
+        t.w(["button.background"], states);
+        t.w(["button.secondaryBackground"], States::from(0x3A3D41));
+        t.w(["scrollbarSlider.background"], States::from((0x797979, 0x66)));
+        expect![[r#"
+            #0E639C
+              workbench  button.background
+            #2875AF
+              workbench  button.hoverBackground
+            #4F9AD6
+              workbench  button.border
+            #3A3D41
+              workbench  button.secondaryBackground
+            #4A4D51
+              workbench  button.secondaryHoverBackground
+            #79797966
+              workbench  scrollbarSlider.background
+            #68686866
+              workbench  scrollbarSlider.hoverBackground
+            #57575766
+              workbench  scrollbarSlider.activeBackground
+        "#]]
+        .assert_eq(&t.dump());
+    }
+
+    #[test]
+    fn interaction_states_on_unsupported_keys() {
+        let mut t = ThemeBuilder::default();
+        t.w(["button.foreground"], States::from(0xFFFFFF));
+        t.w(["editor.background"], States::from(0x1E1E1E));
+
+        let diagnostics: String = t.verify().iter().map(|d| format!("{d}\n")).collect();
+        expect![[r#"
+            error[interaction-states]: interaction states need a background key, found `button.foreground` (workbench color `button.foreground`)
+            warning[interaction-states]: `editor.background` has no hover, active or border keys to derive states for (workbench color `editor.background`)
+        "#]]
+        .assert_eq(&diagnostics);
+        assert_eq!(t.workbench_rules.len(), 2);
+    }
+}
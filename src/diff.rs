@@ -0,0 +1,175 @@
+use std::fmt::Write;
+
+const CONTEXT: usize = 3;
+
+pub(crate) fn unified(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let old: Vec<_> = old.lines().collect();
+    let new: Vec<_> = new.lines().collect();
+    let edits = edits(&old, &new);
+
+    let mut positions = Vec::with_capacity(edits.len() + 1);
+    let (mut old_line, mut new_line) = (0, 0);
+    for edit in &edits {
+        positions.push((old_line, new_line));
+        match edit {
+            Edit::Equal => {
+                old_line += 1;
+                new_line += 1;
+            }
+            Edit::Delete => old_line += 1,
+            Edit::Insert => new_line += 1,
+        }
+    }
+    positions.push((old_line, new_line));
+
+    let mut out = String::new();
+    let mut changes =
+        edits.iter().enumerate().filter(|(_, edit)| **edit != Edit::Equal).map(|(idx, _)| idx);
+
+    let mut next = changes.next();
+    if next.is_none() {
+        return out;
+    }
+
+    writeln!(out, "--- {old_label}").unwrap();
+    writeln!(out, "+++ {new_label}").unwrap();
+
+    while let Some(first) = next {
+        let mut last = first;
+        next = changes.next();
+        while let Some(idx) = next {
+            if idx - last > 2 * CONTEXT {
+                break;
+            }
+            last = idx;
+            next = changes.next();
+        }
+
+        let start = first.saturating_sub(CONTEXT);
+        let end = (last + 1 + CONTEXT).min(edits.len());
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+
+        writeln!(
+            out,
+            "@@ -{} +{} @@",
+            hunk_range(old_start, old_end - old_start),
+            hunk_range(new_start, new_end - new_start)
+        )
+        .unwrap();
+
+        for (edit, &(old_line, new_line)) in edits[start..end].iter().zip(&positions[start..end]) {
+            match edit {
+                Edit::Equal => writeln!(out, " {}", old[old_line]),
+                Edit::Delete => writeln!(out, "-{}", old[old_line]),
+                Edit::Insert => writeln!(out, "+{}", new[new_line]),
+            }
+            .unwrap();
+        }
+    }
+
+    out
+}
+
+fn hunk_range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{len}", start + 1),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Equal,
+    Delete,
+    Insert,
+}
+
+fn edits(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+    let (n, m) = (old_mid.len(), new_mid.len());
+
+    let mut lcs = vec![0_u32; (n + 1) * (m + 1)];
+    let at = |i: usize, j: usize| i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[at(i, j)] = if old_mid[i] == new_mid[j] {
+                lcs[at(i + 1, j + 1)] + 1
+            } else {
+                lcs[at(i + 1, j)].max(lcs[at(i, j + 1)])
+            };
+        }
+    }
+
+    let mut edits = vec![Edit::Equal; prefix];
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_mid[i] == new_mid[j] {
+            edits.push(Edit::Equal);
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[at(i + 1, j)] >= lcs[at(i, j + 1)]) {
+            edits.push(Edit::Delete);
+            i += 1;
+        } else {
+            edits.push(Edit::Insert);
+            j += 1;
+        }
+    }
+    edits.extend(std::iter::repeat_n(Edit::Equal, suffix));
+
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::expect;
+
+    #[test]
+    fn identical() {
+        assert_eq!(unified("a\nb\n", "a\nb\n", "old", "new"), "");
+    }
+
+    #[test]
+    fn separate_hunks() {
+        let old = (1..=20).map(|n| format!("{n}\n")).collect::<String>();
+        let new = (1..=21)
+            .filter(|n| *n != 17)
+            .map(|n| if n == 3 { "three\n".to_string() } else { format!("{n}\n") })
+            .collect::<String>();
+
+        expect![[r#"
+            --- old
+            +++ new
+            @@ -1,6 +1,6 @@
+             1
+             2
+            -3
+            +three
+             4
+             5
+             6
+            @@ -14,7 +14,7 @@
+             14
+             15
+             16
+            -17
+             18
+             19
+             20
+            +21
+        "#]]
+        .assert_eq(&unified(&old, &new, "old", "new"));
+    }
+}
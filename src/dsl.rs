@@ -1,165 +1,694 @@
-use crate::proto;
+mod semantic_highlighting_typestate;
+
+pub use semantic_highlighting_typestate::{
+    SemanticHighlighting, SemanticHighlightingDisabled, SemanticHighlightingEnabled,
+};
+
+use crate::{derive, lint, proto, workbench};
 use indexmap::IndexMap;
 use std::borrow::Cow;
-
-#[derive(Debug, Default)]
-pub struct ThemeBuilder {
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Add;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Clone)]
+pub struct ThemeBuilder<S: SemanticHighlighting = SemanticHighlightingEnabled> {
+    pub global_token_defaults: Option<proto::textmate::GlobalDefaults>,
     pub textmate_rules: Vec<proto::textmate::Rule>,
     pub semantic_rules: IndexMap<proto::semantic::Selector, proto::semantic::Style>,
-    pub workbench_rules: IndexMap<Cow<'static, str>, proto::Color>,
+    pub workbench_rules: IndexMap<Cow<'static, str>, Option<proto::Color>>,
+    workbench_references: IndexMap<Cow<'static, str>, Cow<'static, str>>,
+    variables: IndexMap<Cow<'static, str>, Variable>,
+    problems: Vec<Problem>,
+    hooks: Hooks,
+    state: PhantomData<S>,
+}
+
+impl Default for ThemeBuilder {
+    fn default() -> Self {
+        Self::with_capacity(0, 0, 0)
+    }
+}
+
+pub enum RuleMut<'a> {
+    TextMate(&'a mut proto::textmate::Rule),
+    Semantic(&'a proto::semantic::Selector, &'a mut proto::semantic::Style),
+    Workbench(&'a str, &'a mut Option<proto::Color>),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Variable {
+    color: Result<proto::Color, TooManyHexDigits>,
+    used: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Problem {
+    CyclicReference { key: Cow<'static, str>, through: Cow<'static, str> },
+    MissingReference { key: Cow<'static, str>, target: Cow<'static, str> },
+    UndefinedVariable(String),
+    NotABackgroundKey(String),
+    UnusedStates(String),
+    InvalidColor(TooManyHexDigits, lint::Location),
+}
+
+impl Problem {
+    fn diagnostic(&self) -> lint::Diagnostic {
+        let (lint, location) = match self {
+            Self::CyclicReference { key, .. } | Self::MissingReference { key, .. } => {
+                ("workbench-references", lint::Location::WorkbenchRule(key.clone()))
+            }
+            Self::UndefinedVariable(name) => {
+                ("undefined-variables", lint::Location::Variable(name.clone()))
+            }
+            Self::NotABackgroundKey(key) | Self::UnusedStates(key) => {
+                ("interaction-states", lint::Location::WorkbenchRule(key.clone().into()))
+            }
+            Self::InvalidColor(_, location) => ("invalid-colors", location.clone()),
+        };
+
+        lint::Diagnostic { lint, severity: self.severity(), location, message: self.to_string() }
+    }
+
+    fn severity(&self) -> lint::Severity {
+        match self {
+            Self::UnusedStates(_) => lint::Severity::Warning,
+            _ => lint::Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CyclicReference { key, through } => {
+                write!(f, "workbench color `{key}` refers back to itself through `{through}`")
+            }
+            Self::MissingReference { key, target } => {
+                write!(f, "workbench color `{key}` refers to `{target}`, which is not defined")
+            }
+            Self::UndefinedVariable(name) => write!(f, "color variable `{name}` is not defined"),
+            Self::NotABackgroundKey(key) => {
+                write!(f, "interaction states need a background key, found `{key}`")
+            }
+            Self::UnusedStates(key) => {
+                write!(f, "`{key}` has no hover, active or border keys to derive states for")
+            }
+            Self::InvalidColor(error, _) => write!(f, "{error}"),
+        }
+    }
+}
+
+type Hook = dyn Fn(RuleMut<'_>) + Send + Sync;
+
+#[derive(Clone, Default)]
+struct Hooks(Vec<Arc<Hook>>);
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Hooks({})", self.0.len())
+    }
 }
 
 impl ThemeBuilder {
+    pub fn with_capacity(textmate: usize, semantic: usize, workbench: usize) -> Self {
+        Self::with_rule_capacity(textmate, semantic, workbench)
+    }
+
+    pub fn semantic_rules(&self) -> &IndexMap<proto::semantic::Selector, proto::semantic::Style> {
+        &self.semantic_rules
+    }
+
+    pub fn semantic_rules_mut(
+        &mut self,
+    ) -> &mut IndexMap<proto::semantic::Selector, proto::semantic::Style> {
+        &mut self.semantic_rules
+    }
+
     pub fn a(&mut self, selectors: impl IntoIterator<Item = Selector>, style: impl Into<Style>) {
-        let mut textmate_scopes = Vec::new();
-        let mut semantic_selectors = Vec::new();
-        let style = style.into();
+        let (textmate_scopes, semantic_selectors) = split_selectors(selectors);
+        self.add_rules(textmate_scopes, semantic_selectors, style.into(), None);
+    }
 
-        for selector in selectors {
-            match selector {
-                Selector::TextMate(scope) => textmate_scopes.push(scope),
-                Selector::Semantic(selector) => semantic_selectors.push(selector),
-            }
+    pub fn a_with_note(
+        &mut self,
+        selectors: impl IntoIterator<Item = Selector>,
+        style: impl Into<Style>,
+        note: impl Into<Cow<'static, str>>,
+    ) {
+        let (textmate_scopes, semantic_selectors) = split_selectors(selectors);
+        self.add_rules(textmate_scopes, semantic_selectors, style.into(), Some(note.into()));
+    }
+}
+
+fn split_selectors(
+    selectors: impl IntoIterator<Item = Selector>,
+) -> (Vec<Cow<'static, str>>, Vec<proto::semantic::Selector>) {
+    let selectors = selectors.into_iter();
+    let mut textmate_scopes = Vec::with_capacity(selectors.size_hint().0);
+    let mut semantic_selectors = Vec::new();
+
+    for selector in selectors {
+        match selector {
+            Selector::TextMate(scope) => textmate_scopes.push(scope),
+            Selector::Semantic(selector) => semantic_selectors.push(selector),
+        }
+    }
+
+    (textmate_scopes, semantic_selectors)
+}
+
+impl<S: SemanticHighlighting> ThemeBuilder<S> {
+    fn with_rule_capacity(textmate: usize, semantic: usize, workbench: usize) -> Self {
+        Self {
+            global_token_defaults: None,
+            textmate_rules: Vec::with_capacity(textmate),
+            semantic_rules: IndexMap::with_capacity(semantic),
+            workbench_rules: IndexMap::with_capacity(workbench),
+            workbench_references: IndexMap::new(),
+            variables: IndexMap::new(),
+            problems: Vec::new(),
+            hooks: Hooks::default(),
+            state: PhantomData,
+        }
+    }
+
+    pub fn on_rule(&mut self, hook: impl Fn(RuleMut<'_>) + Send + Sync + 'static) {
+        self.hooks.0.push(Arc::new(hook));
+    }
+
+    fn add_rules(
+        &mut self,
+        textmate_scopes: Vec<Cow<'static, str>>,
+        semantic_selectors: Vec<proto::semantic::Selector>,
+        mut style: Style,
+        note: Option<Cow<'static, str>>,
+    ) {
+        if let Some(name) = style.variable.take() {
+            style.foreground = self.resolve_variable(name);
+        }
+        if let Some(error) = style.invalid.take() {
+            self.record(Problem::InvalidColor(error, lint::Location::Theme));
         }
 
         if !textmate_scopes.is_empty() {
-            self.textmate_rules.push(proto::textmate::Rule {
+            let mut rule = proto::textmate::Rule {
                 scope: textmate_scopes,
-                settings: style_to_textmate_rule_settings(style),
-            });
-        }
-
-        let semantic_style = proto::semantic::Style {
-            foreground: style.foreground,
-            font_style: match style.font_style {
-                Some(font_style) => {
-                    let mut s = proto::semantic::FontStyle {
-                        bold: proto::semantic::FontStyleSetting::Inherit,
-                        italic: proto::semantic::FontStyleSetting::Inherit,
-                        underline: proto::semantic::FontStyleSetting::Inherit,
-                    };
-
-                    match font_style {
-                        FontStyle::Bold => s.bold = proto::semantic::FontStyleSetting::True,
-                        FontStyle::Italic => s.italic = proto::semantic::FontStyleSetting::True,
-                        FontStyle::Underline => {
-                            s.underline = proto::semantic::FontStyleSetting::True
-                        }
-                        FontStyle::Clear => {
-                            s = proto::semantic::FontStyle {
-                                bold: proto::semantic::FontStyleSetting::False,
-                                italic: proto::semantic::FontStyleSetting::False,
-                                underline: proto::semantic::FontStyleSetting::False,
-                            }
-                        }
-                    }
+                settings: style.textmate_settings(),
+                note,
+            };
+            for hook in &self.hooks.0 {
+                hook(RuleMut::TextMate(&mut rule));
+            }
+            if !rule.scope.is_empty() {
+                self.textmate_rules.push(rule);
+            }
+        }
+
+        for selector in semantic_selectors {
+            let mut style = style.semantic_style();
+            for hook in &self.hooks.0 {
+                hook(RuleMut::Semantic(&selector, &mut style));
+            }
+            self.semantic_rules.insert(selector, style);
+        }
+    }
 
-                    s
+    pub fn define(&mut self, name: impl Into<Cow<'static, str>>, color: impl Into<Color>) {
+        let name = name.into();
+        let Color(color) = color.into();
+        if let Err(error) = color {
+            self.record(Problem::InvalidColor(error, lint::Location::Variable(name.to_string())));
+        }
+        self.variables.insert(name, Variable { color, used: false });
+    }
+
+    fn resolve_variable(&mut self, name: &str) -> Option<proto::Color> {
+        match self.variables.get_mut(name) {
+            Some(variable) => {
+                variable.used = true;
+                variable.color.ok()
+            }
+            None => {
+                self.record(Problem::UndefinedVariable(name.to_string()));
+                None
+            }
+        }
+    }
+
+    fn record(&mut self, problem: Problem) {
+        if !self.problems.contains(&problem) {
+            self.problems.push(problem);
+        }
+    }
+
+    pub fn global_token_defaults(
+        &mut self,
+        foreground: impl Into<Color>,
+        background: impl Into<Color>,
+    ) {
+        let Color(foreground) = foreground.into();
+        let Color(background) = background.into();
+        for error in [foreground, background].into_iter().filter_map(Result::err) {
+            self.record(Problem::InvalidColor(error, lint::Location::Theme));
+        }
+
+        self.global_token_defaults = Some(proto::textmate::GlobalDefaults {
+            foreground: foreground.ok(),
+            background: background.ok(),
+        });
+    }
+
+    pub fn w<'a>(
+        &mut self,
+        selector: impl IntoIterator<Item = &'a str>,
+        color: impl Into<WorkbenchColor>,
+    ) {
+        let color = color.into();
+        let selector = selector.into_iter();
+        self.workbench_rules.reserve(selector.size_hint().0);
+
+        for selector in selector {
+            match &color {
+                WorkbenchColor::Color(color) => self.insert_workbench_rule(selector, Some(*color)),
+                WorkbenchColor::Invalid(error) => {
+                    let location = lint::Location::WorkbenchRule(Cow::Owned(selector.to_string()));
+                    self.record(Problem::InvalidColor(*error, location));
                 }
-                None => proto::semantic::FontStyle {
-                    bold: proto::semantic::FontStyleSetting::Inherit,
-                    italic: proto::semantic::FontStyleSetting::Inherit,
-                    underline: proto::semantic::FontStyleSetting::Inherit,
-                },
-            },
+                WorkbenchColor::Var(name) => {
+                    if let Some(color) = self.resolve_variable(name) {
+                        self.insert_workbench_rule(selector, Some(color));
+                    }
+                }
+                WorkbenchColor::States(states) => self.insert_states(selector, states),
+                WorkbenchColor::SameAs(target) => {
+                    self.workbench_rules.insert(Cow::Owned(selector.to_string()), None);
+                    self.workbench_references
+                        .insert(Cow::Owned(selector.to_string()), target.clone());
+                }
+            }
+        }
+    }
+
+    pub fn unset<'a>(&mut self, selector: impl IntoIterator<Item = &'a str>) {
+        for selector in selector {
+            self.insert_workbench_rule(selector, None);
+        }
+    }
+
+    fn insert_workbench_rule(&mut self, key: &str, mut color: Option<proto::Color>) {
+        for hook in &self.hooks.0 {
+            hook(RuleMut::Workbench(key, &mut color));
+        }
+        self.workbench_references.shift_remove(key);
+        self.workbench_rules.insert(Cow::Owned(key.to_string()), color);
+    }
+
+    fn insert_states(&mut self, key: &str, states: &derive::States) {
+        let (prefix, stem) = match key.rsplit_once('.') {
+            Some((prefix, "background")) => (prefix, ""),
+            Some((prefix, name)) if name.ends_with("Background") => {
+                (prefix, name.trim_end_matches("Background"))
+            }
+            _ => {
+                self.insert_workbench_rule(key, Some(states.base));
+                self.record(Problem::NotABackgroundKey(key.to_string()));
+                return;
+            }
         };
 
-        for selector in semantic_selectors {
-            self.semantic_rules.insert(selector, semantic_style);
+        self.insert_workbench_rule(key, Some(states.base));
+        let mut derived_any = false;
+
+        let derived = [
+            ("Hover", "Background", states.hover),
+            ("Active", "Background", states.active),
+            ("", "Border", states.border),
+        ];
+
+        for (state, suffix, color) in derived {
+            let name = format!("{stem}{state}{suffix}");
+            let mut chars = name.chars();
+            let name = match chars.next() {
+                Some(first) => first.to_lowercase().chain(chars).collect::<String>(),
+                None => continue,
+            };
+
+            let derived_key = format!("{prefix}.{name}");
+            if workbench::is_known_key(&derived_key) {
+                self.insert_workbench_rule(&derived_key, Some(color));
+                derived_any = true;
+            }
         }
 
-        fn style_to_textmate_rule_settings(style: Style) -> proto::textmate::RuleSettings {
-            let font_style = match style.font_style {
-                Some(font_style) => {
-                    let mut s = (false, false, false);
+        if !derived_any {
+            self.record(Problem::UnusedStates(key.to_string()));
+        }
+    }
 
-                    match font_style {
-                        FontStyle::Bold => s.0 = true,
-                        FontStyle::Italic => s.1 = true,
-                        FontStyle::Underline => s.2 = true,
-                        FontStyle::Clear => {}
-                    }
+    fn resolve_workbench_references(&mut self) -> Vec<Problem> {
+        let references = std::mem::take(&mut self.workbench_references);
+        let mut unresolved = Vec::new();
+
+        'keys: for key in references.keys() {
+            let mut target = key;
+            let mut seen = vec![key];
 
-                    proto::textmate::FontStyle::Set { bold: s.0, italic: s.1, underline: s.2 }
+            while let Some(next) = references.get(target) {
+                if seen.contains(&next) {
+                    let through = target.clone();
+                    let key = key.clone();
+                    unresolved.push(Problem::CyclicReference { key, through });
+                    continue 'keys;
                 }
-                None => proto::textmate::FontStyle::Inherit,
-            };
+                seen.push(next);
+                target = next;
+            }
 
-            proto::textmate::RuleSettings { foreground: style.foreground, font_style }
+            match self.workbench_rules.get(target) {
+                Some(color) => self.workbench_rules[&**key] = *color,
+                None => {
+                    let target = target.clone();
+                    let key = key.clone();
+                    unresolved.push(Problem::MissingReference { key, target });
+                }
+            }
         }
+
+        unresolved
     }
 
-    pub fn w<'a>(&mut self, selector: impl IntoIterator<Item = &'a str>, color: impl Into<Color>) {
-        let Color(color) = color.into();
+    pub fn coalesce_textmate_rules(&mut self) {
+        self.textmate_rules = proto::textmate::coalesce(std::mem::take(&mut self.textmate_rules));
+    }
 
-        for selector in selector {
-            self.workbench_rules.insert(Cow::Owned(selector.to_string()), color);
+    pub fn verify(&self) -> Vec<lint::Diagnostic> {
+        self.verify_with(&lint::ThemeLinter::default())
+    }
+
+    pub fn verify_with(&self, linter: &lint::ThemeLinter) -> Vec<lint::Diagnostic> {
+        let mut builder = self.clone();
+        let unresolved = builder.resolve_workbench_references();
+        let mut diagnostics = linter.lint(&builder.into_theme(String::new(), None));
+
+        diagnostics.extend(self.problems.iter().chain(&unresolved).map(Problem::diagnostic));
+
+        for (name, variable) in &self.variables {
+            if !variable.used {
+                diagnostics.push(lint::Diagnostic {
+                    lint: "unused-variables",
+                    severity: lint::Severity::Warning,
+                    location: lint::Location::Variable(name.to_string()),
+                    message: format!("color variable `{name}` is never used"),
+                });
+            }
         }
+
+        diagnostics
     }
 
     pub fn build(self, name: impl Into<String>) -> proto::Theme {
+        self.build_theme(name.into(), None)
+    }
+
+    pub fn build_with_type(
+        self,
+        name: impl Into<String>,
+        theme_type: proto::ThemeType,
+    ) -> proto::Theme {
+        self.build_theme(name.into(), Some(theme_type))
+    }
+
+    pub fn dump(&self) -> String {
+        self.to_string()
+    }
+
+    fn build_theme(mut self, name: String, theme_type: Option<proto::ThemeType>) -> proto::Theme {
+        let unresolved = self.resolve_workbench_references();
+        let mut problems = self.problems.iter().chain(&unresolved);
+        if let Some(error) = problems.find(|problem| problem.severity() == lint::Severity::Error) {
+            panic!("{error}");
+        }
+
+        self.into_theme(name, theme_type)
+    }
+
+    fn into_theme(self, name: String, theme_type: Option<proto::ThemeType>) -> proto::Theme {
         proto::Theme {
-            name: name.into(),
+            name,
+            theme_type,
+            include: None,
+            metadata: proto::Metadata::default(),
+            global_token_defaults: self.global_token_defaults,
             textmate_rules: self.textmate_rules,
-            semantic_highlighting: proto::semantic::Highlighting::On { rules: self.semantic_rules },
+            semantic_highlighting: S::highlighting(self.semantic_rules),
             workbench_rules: self.workbench_rules,
         }
     }
 }
 
-pub fn tm(scope: impl Into<String>) -> Selector {
+impl<S: SemanticHighlighting> fmt::Display for ThemeBuilder<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut groups: IndexMap<Option<proto::Color>, Vec<(&str, String, String)>> =
+            IndexMap::new();
+        let mut add = |color, kind, selector, font_style| {
+            groups.entry(color).or_default().push((kind, selector, font_style));
+        };
+
+        if let Some(defaults) = self.global_token_defaults {
+            add(defaults.foreground, "defaults", "foreground".to_string(), String::new());
+            add(defaults.background, "defaults", "background".to_string(), String::new());
+        }
+
+        for rule in &self.textmate_rules {
+            let font_style = match rule.settings.font_style {
+                proto::textmate::FontStyle::Inherit => String::new(),
+                proto::textmate::FontStyle::Set { bold, italic, underline } => {
+                    let set = [(bold, "bold"), (italic, "italic"), (underline, "underline")];
+                    let set: Vec<_> = set.iter().filter(|(on, _)| *on).map(|(_, s)| *s).collect();
+                    if set.is_empty() {
+                        "clear".to_string()
+                    } else {
+                        set.join(" ")
+                    }
+                }
+            };
+            add(rule.settings.foreground, "textmate", rule.scope.join(", "), font_style);
+        }
+
+        for (selector, style) in &self.semantic_rules {
+            let settings = [
+                (style.font_style.bold, "bold"),
+                (style.font_style.italic, "italic"),
+                (style.font_style.underline, "underline"),
+            ];
+            let font_style: Vec<_> = settings
+                .iter()
+                .filter_map(|(setting, name)| match setting {
+                    proto::semantic::FontStyleSetting::True => Some(name.to_string()),
+                    proto::semantic::FontStyleSetting::False => Some(format!("not {name}")),
+                    proto::semantic::FontStyleSetting::Inherit => None,
+                })
+                .collect();
+            add(style.foreground, "semantic", selector.to_string(), font_style.join(" "));
+        }
+
+        for (key, color) in &self.workbench_rules {
+            let note = match self.workbench_references.get(key) {
+                Some(target) => format!("same as {target}"),
+                None if color.is_none() => "unset".to_string(),
+                None => String::new(),
+            };
+            add(*color, "workbench", key.to_string(), note);
+        }
+
+        let width = groups.values().flatten().map(|(_, selector, _)| selector.len()).max();
+
+        for (color, rules) in &groups {
+            match color {
+                Some(color) => writeln!(f, "{color}")?,
+                None => writeln!(f, "(no color)")?,
+            }
+
+            for (kind, selector, font_style) in rules {
+                let line = format!(
+                    "  {kind:<9}  {selector:<width$}  {font_style}",
+                    width = width.unwrap_or(0)
+                );
+                writeln!(f, "{}", line.trim_end())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn tm(scope: impl Into<Cow<'static, str>>) -> Selector {
     Selector::TextMate(scope.into())
 }
 
 pub fn s(s: &str) -> Selector {
-    return match parse(s) {
-        Ok(s) => s,
+    match s.parse() {
+        Ok(selector) => Selector::Semantic(selector),
         Err(e) => panic!("Failed to parse semantic selector ‘{s}’: {e}"),
-    };
+    }
+}
 
-    fn parse(s: &str) -> Result<Selector, Cow<'static, str>> {
-        let (s, language) = match s.rfind(':') {
-            Some(idx) if idx == s.len() - 1 => {
-                return Err("expected language name after ‘:’".into())
-            }
-            Some(idx) => {
-                let language = s[idx + 1..].to_owned();
-                let language = proto::semantic::Identifier::new(language)?;
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Selector {
+    TextMate(Cow<'static, str>),
+    Semantic(proto::semantic::Selector),
+}
 
-                (&s[..idx], Some(language))
-            }
-            None => (s, None),
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Style {
+    foreground: Option<proto::Color>,
+    variable: Option<&'static str>,
+    invalid: Option<TooManyHexDigits>,
+    bold: Option<bool>,
+    italic: Option<bool>,
+    underline: Option<bool>,
+}
+
+impl Style {
+    pub const fn rgb(rgb: u32) -> Self {
+        let foreground = match opaque(rgb) {
+            Some(foreground) => foreground,
+            None => panic!(
+                "color has more than six hex digits; use `Color::rgba` for `0xRRGGBBAA` colors"
+            ),
         };
 
-        let mut components = s.split('.');
+        Self {
+            foreground: Some(foreground),
+            variable: None,
+            invalid: None,
+            bold: None,
+            italic: None,
+            underline: None,
+        }
+    }
+
+    pub const fn with(self, font_style: FontStyle) -> Self {
+        let (bold, italic, underline) = match font_style {
+            FontStyle::Bold => (Some(true), None, None),
+            FontStyle::Italic => (None, Some(true), None),
+            FontStyle::Underline => (None, None, Some(true)),
+            FontStyle::Clear => (Some(false), Some(false), Some(false)),
+        };
 
-        let kind = match components.next() {
-            Some("*") => proto::semantic::TokenKind::Wildcard,
-            Some(kind) => {
-                let kind = proto::semantic::Identifier::new(kind.to_owned())?;
-                proto::semantic::TokenKind::Specific(kind)
+        self.merge(Self {
+            foreground: None,
+            variable: None,
+            invalid: None,
+            bold,
+            italic,
+            underline,
+        })
+    }
+
+    pub const fn merge(self, other: Self) -> Self {
+        const fn or<T: Copy>(this: Option<T>, other: Option<T>) -> Option<T> {
+            match other {
+                Some(_) => other,
+                None => this,
             }
-            None => return Err("expected semantic token kind".into()),
+        }
+
+        let (foreground, variable, invalid) =
+            match (other.foreground, other.variable, other.invalid) {
+                (None, None, None) => (self.foreground, self.variable, self.invalid),
+                _ => (other.foreground, other.variable, other.invalid),
+            };
+
+        Self {
+            foreground,
+            variable,
+            invalid,
+            bold: or(self.bold, other.bold),
+            italic: or(self.italic, other.italic),
+            underline: or(self.underline, other.underline),
+        }
+    }
+
+    pub(crate) fn textmate_settings(self) -> proto::textmate::RuleSettings {
+        let font_style = match (self.bold, self.italic, self.underline) {
+            (None, None, None) => proto::textmate::FontStyle::Inherit,
+            (bold, italic, underline) => proto::textmate::FontStyle::Set {
+                bold: bold.unwrap_or(false),
+                italic: italic.unwrap_or(false),
+                underline: underline.unwrap_or(false),
+            },
         };
 
-        let modifiers = components
-            .map(|m| proto::semantic::Identifier::new(m.to_owned()))
-            .collect::<Result<_, _>>()?;
+        proto::textmate::RuleSettings { foreground: self.foreground, font_style }
+    }
+
+    pub(crate) fn semantic_style(self) -> proto::semantic::Style {
+        let setting = |setting: Option<bool>| match setting {
+            Some(true) => proto::semantic::FontStyleSetting::True,
+            Some(false) => proto::semantic::FontStyleSetting::False,
+            None => proto::semantic::FontStyleSetting::Inherit,
+        };
 
-        Ok(Selector::Semantic(proto::semantic::Selector { kind, modifiers, language }))
+        proto::semantic::Style {
+            foreground: self.foreground,
+            font_style: proto::semantic::FontStyle {
+                bold: setting(self.bold),
+                italic: setting(self.italic),
+                underline: setting(self.underline),
+            },
+        }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum Selector {
-    TextMate(String),
-    Semantic(proto::semantic::Selector),
+#[derive(Debug, Clone, Default)]
+pub struct StyleRegistry {
+    presets: IndexMap<String, Style>,
+    used: RefCell<HashSet<String>>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Style {
-    foreground: Option<proto::Color>,
-    font_style: Option<FontStyle>,
+impl PartialEq for StyleRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        self.presets == other.presets
+    }
+}
+
+impl Eq for StyleRegistry {}
+
+impl StyleRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn define(&mut self, name: impl Into<String>, style: impl Into<Style>) {
+        self.presets.insert(name.into(), style.into());
+    }
+
+    pub fn get(&self, name: &str) -> Style {
+        match self.presets.get(name) {
+            Some(style) => {
+                self.used.borrow_mut().insert(name.to_string());
+                *style
+            }
+            None => panic!("Style preset ‘{name}’ is not defined"),
+        }
+    }
+
+    pub fn unused(&self) -> impl Iterator<Item = &str> {
+        let used = self.used.borrow();
+        let unused: Vec<_> =
+            self.presets.keys().filter(|name| !used.contains(*name)).map(String::as_str).collect();
+        unused.into_iter()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Style)> {
+        self.presets.iter().map(|(name, style)| (name.as_str(), *style))
+    }
 }
 
 impl<C> From<C> for Style
@@ -167,14 +696,28 @@ where
     C: Into<Color>,
 {
     fn from(c: C) -> Self {
-        let Color(c) = c.into();
-        Self { foreground: Some(c), font_style: None }
+        match c.into() {
+            Color(Ok(c)) => Self { foreground: Some(c), ..Self::default() },
+            Color(Err(error)) => Self { invalid: Some(error), ..Self::default() },
+        }
+    }
+}
+
+impl From<Var> for Style {
+    fn from(Var(name): Var) -> Self {
+        Self { variable: Some(name), ..Self::default() }
+    }
+}
+
+impl From<(Var, FontStyle)> for Style {
+    fn from((var, font_style): (Var, FontStyle)) -> Self {
+        Self::from(var).with(font_style)
     }
 }
 
 impl From<FontStyle> for Style {
     fn from(font_style: FontStyle) -> Self {
-        Self { foreground: None, font_style: Some(font_style) }
+        Self::default().with(font_style)
     }
 }
 
@@ -183,44 +726,145 @@ where
     C: Into<Color>,
 {
     fn from((c, font_style): (C, FontStyle)) -> Self {
-        let Color(c) = c.into();
-        Self { foreground: Some(c), font_style: Some(font_style) }
+        Self::from(c).with(font_style)
+    }
+}
+
+impl<T> Add<T> for Style
+where
+    T: Into<Style>,
+{
+    type Output = Style;
+
+    fn add(self, other: T) -> Style {
+        self.merge(other.into())
+    }
+}
+
+impl<T> Add<T> for FontStyle
+where
+    T: Into<Style>,
+{
+    type Output = Style;
+
+    fn add(self, other: T) -> Style {
+        Style::from(self).merge(other.into())
     }
 }
 
-pub struct Color(proto::Color);
+pub struct Color(Result<proto::Color, TooManyHexDigits>);
+
+impl Color {
+    pub fn rgb(rgb: u32) -> Self {
+        Self(opaque(rgb).ok_or(TooManyHexDigits(rgb)))
+    }
+
+    pub fn try_rgb(rgb: u32) -> Result<Self, TooManyHexDigits> {
+        let color = Self::rgb(rgb);
+        color.0.map(|_| color)
+    }
+
+    pub fn rgba(rgba: u32) -> Self {
+        let [r, g, b, a] = rgba.to_be_bytes();
+        Self(Ok(proto::Color { r, g, b, a }))
+    }
+}
+
+const fn opaque(rgb: u32) -> Option<proto::Color> {
+    match rgb.to_be_bytes() {
+        [0, r, g, b] => Some(proto::Color { r, g, b, a: 0xFF }),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Error)]
+#[error(
+    "color `0x{0:08X}` has more than six hex digits; use `Color::rgba` for `0xRRGGBBAA` colors"
+)]
+pub struct TooManyHexDigits(pub u32);
 
 impl From<u32> for Color {
     fn from(rgb: u32) -> Self {
-        let (r, g, b) = rgb_from_u32(rgb);
-        Self(proto::Color { r, g, b, a: 0xFF })
+        Self::rgb(rgb)
     }
 }
 
 impl From<(u32, u8)> for Color {
     fn from((rgb, a): (u32, u8)) -> Self {
-        let (r, g, b) = rgb_from_u32(rgb);
-        Self(proto::Color { r, g, b, a })
+        let Self(c) = Self::rgb(rgb);
+        Self(c.map(|c| proto::Color { a, ..c }))
     }
 }
 
 impl From<(u8, u8, u8)> for Color {
     fn from((r, g, b): (u8, u8, u8)) -> Self {
-        Self(proto::Color { r, g, b, a: 0xFF })
+        Self(Ok(proto::Color { r, g, b, a: 0xFF }))
     }
 }
 
 impl From<((u8, u8, u8), u8)> for Color {
     fn from(((r, g, b), a): ((u8, u8, u8), u8)) -> Self {
-        Self(proto::Color { r, g, b, a })
+        Self(Ok(proto::Color { r, g, b, a }))
+    }
+}
+
+impl From<proto::Color> for Color {
+    fn from(c: proto::Color) -> Self {
+        Self(Ok(c))
+    }
+}
+
+impl From<Color> for proto::Color {
+    fn from(Color(c): Color) -> Self {
+        match c {
+            Ok(c) => c,
+            Err(e) => panic!("{e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WorkbenchColor {
+    Color(proto::Color),
+    SameAs(Cow<'static, str>),
+    Var(&'static str),
+    States(derive::States),
+    Invalid(TooManyHexDigits),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Var(&'static str);
+
+pub const fn var(name: &'static str) -> Var {
+    Var(name)
+}
+
+impl From<derive::States> for WorkbenchColor {
+    fn from(states: derive::States) -> Self {
+        Self::States(states)
     }
 }
 
-fn rgb_from_u32(rgb: u32) -> (u8, u8, u8) {
-    let [hi, r, g, b] = rgb.to_be_bytes();
-    assert_eq!(hi, 0);
+impl From<Var> for WorkbenchColor {
+    fn from(Var(name): Var) -> Self {
+        Self::Var(name)
+    }
+}
 
-    (r, g, b)
+pub fn same_as(key: impl Into<Cow<'static, str>>) -> WorkbenchColor {
+    WorkbenchColor::SameAs(key.into())
+}
+
+impl<C> From<C> for WorkbenchColor
+where
+    C: Into<Color>,
+{
+    fn from(color: C) -> Self {
+        match color.into() {
+            Color(Ok(color)) => Self::Color(color),
+            Color(Err(error)) => Self::Invalid(error),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -231,9 +875,149 @@ pub enum FontStyle {
     Clear,
 }
 
+#[macro_export]
+macro_rules! theme {
+    (@rules $t:ident []) => {};
+    (@rules $t:ident [] defaults($fg:expr, $bg:expr); $($rest:tt)*) => {
+        $t.global_token_defaults($fg, $bg);
+        $crate::theme!(@rules $t [] $($rest)*);
+    };
+    (@rules $t:ident [] w($($keys:tt)*) => $color:tt; $($rest:tt)*) => {
+        $t.w($crate::theme!(@keys [] [] $($keys)*), $color);
+        $crate::theme!(@rules $t [] $($rest)*);
+    };
+    (@rules $t:ident [$($sel:expr),*] => $color:tt $font_style:ident; $($rest:tt)*) => {
+        $t.a([$($sel),*], $crate::theme!(@style $color $font_style));
+        $crate::theme!(@rules $t [] $($rest)*);
+    };
+    (@rules $t:ident [$($sel:expr),*] => $style:tt; $($rest:tt)*) => {
+        $t.a([$($sel),*], $crate::theme!(@style $style));
+        $crate::theme!(@rules $t [] $($rest)*);
+    };
+    (@rules $t:ident [$($sel:expr),*] , $($rest:tt)*) => {
+        $crate::theme!(@rules $t [$($sel),*] $($rest)*);
+    };
+    (@rules $t:ident [$($sel:expr),*] s($($semantic:tt)*) $($rest:tt)*) => {
+        $crate::theme!(@rules $t [
+            $($sel,)* $crate::scopes!(@semantic $crate::theme!(@path [] $($semantic)*))
+        ] $($rest)*);
+    };
+    (@rules $t:ident [$($sel:expr),*] $scope:literal $($rest:tt)*) => {
+        $crate::theme!(@rules $t [$($sel,)* $crate::dsl::tm($scope)] $($rest)*);
+    };
+    (@rules $t:ident [$($sel:expr),*] $head:ident $($rest:tt)*) => {
+        $crate::theme!(@tm $t [$($sel),*] [stringify!($head)] $($rest)*);
+    };
+
+    (@tm $t:ident [$($sel:expr),*] [$($part:expr),*] . $next:ident $($rest:tt)*) => {
+        $crate::theme!(@tm $t [$($sel),*] [$($part,)* ".", stringify!($next)] $($rest)*);
+    };
+    (@tm $t:ident [$($sel:expr),*] [$($part:expr),*] - $next:ident $($rest:tt)*) => {
+        $crate::theme!(@tm $t [$($sel),*] [$($part,)* "-", stringify!($next)] $($rest)*);
+    };
+    (@tm $t:ident [$($sel:expr),*] [$($part:expr),*] $($rest:tt)*) => {
+        $crate::theme!(@rules $t [$($sel,)* $crate::dsl::tm(concat!($($part),*))] $($rest)*);
+    };
+
+    (@keys [$($key:expr),*] []) => { [$($key),*] };
+    (@keys [$($key:expr),*] [$($part:expr),*]) => { [$($key,)* concat!($($part),*)] };
+    (@keys [$($key:expr),*] [$($part:expr),*] , $($rest:tt)*) => {
+        $crate::theme!(@keys [$($key,)* concat!($($part),*)] [] $($rest)*)
+    };
+    (@keys [$($key:expr),*] [$($part:expr),*] . $($rest:tt)*) => {
+        $crate::theme!(@keys [$($key),*] [$($part,)* "."] $($rest)*)
+    };
+    (@keys [$($key:expr),*] [$($part:expr),*] $next:ident $($rest:tt)*) => {
+        $crate::theme!(@keys [$($key),*] [$($part,)* stringify!($next)] $($rest)*)
+    };
+
+    (@path [$($part:expr),*]) => { concat!($($part),*) };
+    (@path [$($part:expr),*] $next:ident $($rest:tt)*) => {
+        $crate::theme!(@path [$($part,)* stringify!($next)] $($rest)*)
+    };
+    (@path [$($part:expr),*] $next:tt $($rest:tt)*) => {
+        $crate::theme!(@path [$($part,)* stringify!($next)] $($rest)*)
+    };
+
+    (@style bold) => { $crate::dsl::FontStyle::Bold };
+    (@style italic) => { $crate::dsl::FontStyle::Italic };
+    (@style underline) => { $crate::dsl::FontStyle::Underline };
+    (@style clear) => { $crate::dsl::FontStyle::Clear };
+    (@style $color:tt) => { $color };
+    (@style $color:tt $font_style:ident) => { ($color, $crate::theme!(@style $font_style)) };
+
+    (@type dark) => { $crate::proto::ThemeType::Dark };
+    (@type light) => { $crate::proto::ThemeType::Light };
+    (@type high_contrast) => { $crate::proto::ThemeType::HighContrast };
+    (@type high_contrast_light) => { $crate::proto::ThemeType::HighContrastLight };
+
+    ($name:literal: $theme_type:ident; $($rules:tt)*) => {{
+        let mut t = $crate::dsl::ThemeBuilder::default();
+        $crate::theme!(@rules t [] $($rules)*);
+        t.build_with_type($name, $crate::theme!(@type $theme_type))
+    }};
+    ($name:literal; $($rules:tt)*) => {{
+        let mut t = $crate::dsl::ThemeBuilder::default();
+        $crate::theme!(@rules t [] $($rules)*);
+        t.build($name)
+    }};
+}
+
+#[macro_export]
+macro_rules! scopes {
+    (@sel [$($sel:expr),*]) => { [$($sel),*] };
+    (@sel [$($sel:expr),*] , $($rest:tt)*) => {
+        $crate::scopes!(@sel [$($sel),*] $($rest)*)
+    };
+    (@sel [$($sel:expr),*] s : * $($rest:tt)*) => {
+        $crate::scopes!(@s [$($sel),*] ["*"] $($rest)*)
+    };
+    (@sel [$($sel:expr),*] s : $head:ident $($rest:tt)*) => {
+        $crate::scopes!(@s [$($sel),*] [stringify!($head)] $($rest)*)
+    };
+    (@sel [$($sel:expr),*] $scope:literal $($rest:tt)*) => {
+        $crate::scopes!(@sel [$($sel,)* $crate::dsl::tm($scope)] $($rest)*)
+    };
+    (@sel [$($sel:expr),*] $head:ident $($rest:tt)*) => {
+        $crate::scopes!(@tm [$($sel),*] [stringify!($head)] $($rest)*)
+    };
+
+    (@tm [$($sel:expr),*] [$($part:expr),*] . $next:ident $($rest:tt)*) => {
+        $crate::scopes!(@tm [$($sel),*] [$($part,)* ".", stringify!($next)] $($rest)*)
+    };
+    (@tm [$($sel:expr),*] [$($part:expr),*] - $next:ident $($rest:tt)*) => {
+        $crate::scopes!(@tm [$($sel),*] [$($part,)* "-", stringify!($next)] $($rest)*)
+    };
+    (@tm [$($sel:expr),*] [$($part:expr),*] $($rest:tt)*) => {
+        $crate::scopes!(@sel [$($sel,)* $crate::dsl::tm(concat!($($part),*))] $($rest)*)
+    };
+
+    (@s [$($sel:expr),*] [$($part:expr),*] . $next:ident $($rest:tt)*) => {
+        $crate::scopes!(@s [$($sel),*] [$($part,)* ".", stringify!($next)] $($rest)*)
+    };
+    (@s [$($sel:expr),*] [$($part:expr),*] : $next:ident $($rest:tt)*) => {
+        $crate::scopes!(@s [$($sel),*] [$($part,)* ":", stringify!($next)] $($rest)*)
+    };
+    (@s [$($sel:expr),*] [$($part:expr),*] $($rest:tt)*) => {
+        $crate::scopes!(@sel [$($sel,)* $crate::scopes!(@semantic concat!($($part),*))] $($rest)*)
+    };
+
+    (@semantic $selector:expr) => {{
+        const SELECTOR: &str = $selector;
+        const _: () = assert!(
+            $crate::proto::semantic::is_valid_selector(SELECTOR),
+            concat!("invalid semantic selector ‘", $selector, "’")
+        );
+        $crate::dsl::s(SELECTOR)
+    }};
+
+    ($($selectors:tt)*) => { $crate::scopes!(@sel [] $($selectors)*) };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use expect_test::expect;
     use pretty_assertions::assert_eq;
     use std::borrow::Cow;
 
@@ -245,6 +1029,29 @@ mod tests {
             t.build("My cool theme"),
             proto::Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: proto::Metadata::default(),
+                global_token_defaults: None,
+                textmate_rules: Vec::new(),
+                semantic_highlighting: proto::semantic::Highlighting::On { rules: IndexMap::new() },
+                workbench_rules: IndexMap::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn build_with_type() {
+        let t = ThemeBuilder::default();
+
+        assert_eq!(
+            t.build_with_type("My cool theme", proto::ThemeType::Dark),
+            proto::Theme {
+                name: "My cool theme".to_string(),
+                theme_type: Some(proto::ThemeType::Dark),
+                include: None,
+                metadata: proto::Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: Vec::new(),
                 semantic_highlighting: proto::semantic::Highlighting::On { rules: IndexMap::new() },
                 workbench_rules: IndexMap::new(),
@@ -262,12 +1069,17 @@ mod tests {
             t.build("My cool theme"),
             proto::Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: proto::Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: vec![proto::textmate::Rule {
-                    scope: vec!["keyword.operator".to_string()],
+                    scope: vec![Cow::Borrowed("keyword.operator")],
                     settings: proto::textmate::RuleSettings {
                         foreground: Some(proto::Color { r: 0xF9, g: 0x26, b: 0x72, a: 0xFF }),
                         font_style: proto::textmate::FontStyle::Inherit
-                    }
+                    },
+                    note: None
                 }],
                 semantic_highlighting: proto::semantic::Highlighting::On { rules: IndexMap::new() },
                 workbench_rules: IndexMap::new(),
@@ -285,16 +1097,21 @@ mod tests {
             t.build("My cool theme"),
             proto::Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: proto::Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: vec![proto::textmate::Rule {
                     scope: vec![
-                        "keyword.operator".to_string(),
-                        "punctuation".to_string(),
-                        "keyword.other".to_string()
+                        Cow::Borrowed("keyword.operator"),
+                        Cow::Borrowed("punctuation"),
+                        Cow::Borrowed("keyword.other")
                     ],
                     settings: proto::textmate::RuleSettings {
                         foreground: Some(proto::Color { r: 0xF9, g: 0x26, b: 0x72, a: 0xFF }),
                         font_style: proto::textmate::FontStyle::Inherit
-                    }
+                    },
+                    note: None
                 }],
                 semantic_highlighting: proto::semantic::Highlighting::On { rules: IndexMap::new() },
                 workbench_rules: IndexMap::new(),
@@ -332,6 +1149,10 @@ mod tests {
             t.build("My cool theme"),
             proto::Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: proto::Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: Vec::new(),
                 semantic_highlighting: proto::semantic::Highlighting::On { rules },
                 workbench_rules: IndexMap::new(),
@@ -393,6 +1214,10 @@ mod tests {
             t.build("My cool theme"),
             proto::Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: proto::Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: Vec::new(),
                 semantic_highlighting: proto::semantic::Highlighting::On { rules },
                 workbench_rules: IndexMap::new(),
@@ -457,6 +1282,10 @@ mod tests {
             t.build("My cool theme"),
             proto::Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: proto::Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: Vec::new(),
                 semantic_highlighting: proto::semantic::Highlighting::On { rules },
                 workbench_rules: IndexMap::new(),
@@ -494,12 +1323,17 @@ mod tests {
             t.build("My cool theme"),
             proto::Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: proto::Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: vec![proto::textmate::Rule {
-                    scope: vec!["variable".to_string()],
+                    scope: vec![Cow::Borrowed("variable")],
                     settings: proto::textmate::RuleSettings {
                         foreground: Some(proto::Color { r: 0xFF, g: 0xFF, b: 0xFF, a: 0xFF }),
                         font_style: proto::textmate::FontStyle::Inherit
-                    }
+                    },
+                    note: None
                 }],
                 semantic_highlighting: proto::semantic::Highlighting::On { rules },
                 workbench_rules: IndexMap::new(),
@@ -537,8 +1371,12 @@ mod tests {
             t.build("My cool theme"),
             proto::Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: proto::Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: vec![proto::textmate::Rule {
-                    scope: vec!["keyword".to_string()],
+                    scope: vec![Cow::Borrowed("keyword")],
                     settings: proto::textmate::RuleSettings {
                         foreground: Some(proto::Color { r: 0xEA, g: 0xDF, b: 0xAF, a: 0xFF }),
                         font_style: proto::textmate::FontStyle::Set {
@@ -546,7 +1384,8 @@ mod tests {
                             italic: false,
                             underline: false
                         }
-                    }
+                    },
+                    note: None
                 }],
                 semantic_highlighting: proto::semantic::Highlighting::On { rules },
                 workbench_rules: IndexMap::new(),
@@ -582,8 +1421,12 @@ mod tests {
             t.build("My cool theme"),
             proto::Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: proto::Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: vec![proto::textmate::Rule {
-                    scope: vec!["markup.underline".to_string()],
+                    scope: vec![Cow::Borrowed("markup.underline")],
                     settings: proto::textmate::RuleSettings {
                         foreground: None,
                         font_style: proto::textmate::FontStyle::Set {
@@ -591,7 +1434,8 @@ mod tests {
                             italic: false,
                             underline: true
                         }
-                    }
+                    },
+                    note: None
                 }],
                 semantic_highlighting: proto::semantic::Highlighting::On { rules },
                 workbench_rules: IndexMap::new(),
@@ -629,6 +1473,10 @@ mod tests {
             t.build("My cool theme"),
             proto::Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: proto::Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: Vec::new(),
                 semantic_highlighting: proto::semantic::Highlighting::On { rules },
                 workbench_rules: IndexMap::new(),
@@ -636,35 +1484,552 @@ mod tests {
         );
     }
 
+    #[test]
+    fn global_token_defaults() {
+        let mut t = ThemeBuilder::default();
+
+        t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+
+        assert_eq!(
+            t.build("My cool theme"),
+            proto::Theme {
+                name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: proto::Metadata::default(),
+                global_token_defaults: Some(proto::textmate::GlobalDefaults {
+                    foreground: Some(proto::Color { r: 0xD4, g: 0xD4, b: 0xD4, a: 0xFF }),
+                    background: Some(proto::Color { r: 0x1E, g: 0x1E, b: 0x1E, a: 0xFF }),
+                }),
+                textmate_rules: Vec::new(),
+                semantic_highlighting: proto::semantic::Highlighting::On { rules: IndexMap::new() },
+                workbench_rules: IndexMap::new(),
+            }
+        );
+    }
+
     #[test]
     fn workbench_rules() {
         let mut t = ThemeBuilder::default();
 
         t.w(["editor.background"], 0x111111);
         t.w(["editor.foreground", "foreground"], 0xBCBCBC);
+        t.unset(["editor.lineHighlightBorder"]);
 
         let mut workbench_rules = IndexMap::new();
         workbench_rules.insert(
             Cow::Borrowed("editor.background"),
-            proto::Color { r: 0x11, g: 0x11, b: 0x11, a: 0xFF },
+            Some(proto::Color { r: 0x11, g: 0x11, b: 0x11, a: 0xFF }),
         );
         workbench_rules.insert(
             Cow::Borrowed("editor.foreground"),
-            proto::Color { r: 0xBC, g: 0xBC, b: 0xBC, a: 0xFF },
+            Some(proto::Color { r: 0xBC, g: 0xBC, b: 0xBC, a: 0xFF }),
         );
         workbench_rules.insert(
             Cow::Borrowed("foreground"),
-            proto::Color { r: 0xBC, g: 0xBC, b: 0xBC, a: 0xFF },
+            Some(proto::Color { r: 0xBC, g: 0xBC, b: 0xBC, a: 0xFF }),
         );
+        workbench_rules.insert(Cow::Borrowed("editor.lineHighlightBorder"), None);
 
         assert_eq!(
             t.build("My cool theme"),
             proto::Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: proto::Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: Vec::new(),
                 semantic_highlighting: proto::semantic::Highlighting::On { rules: IndexMap::new() },
                 workbench_rules,
             }
         );
     }
+
+    #[test]
+    fn rgba_colors() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], Color::rgba(0x11223380));
+        t.w(["editor.foreground"], Color::rgb(0x445566));
+
+        let theme = t.build("My cool theme");
+
+        assert_eq!(
+            theme.workbench_rules["editor.background"],
+            Some(proto::Color { r: 0x11, g: 0x22, b: 0x33, a: 0x80 })
+        );
+        assert_eq!(
+            theme.workbench_rules["editor.foreground"],
+            Some(proto::Color { r: 0x44, g: 0x55, b: 0x66, a: 0xFF })
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "color `0x11223380` has more than six hex digits; use `Color::rgba` for `0xRRGGBBAA` colors"
+    )]
+    fn eight_digit_rgb() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x11223380);
+        t.build("Theme");
+    }
+
+    #[test]
+    fn verify_eight_digit_colors() {
+        let mut t = ThemeBuilder::default();
+        t.define("accent", 0xFF007ACC);
+        t.global_token_defaults(0xD4D4D4, 0x1E1E1E80);
+        t.w(["editor.background"], 0x11223380);
+        t.a([tm("keyword"), s("keyword")], 0x569CD6FF);
+        t.a([tm("string")], (Color::rgb(0xCE917880), FontStyle::Italic));
+        t.a([tm("comment")], var("accent"));
+
+        assert_eq!(t.workbench_rules.len(), 0);
+        assert_eq!(t.textmate_rules[1].settings.foreground, None);
+
+        let diagnostics: String = t.verify().iter().map(|d| format!("{d}\n")).collect();
+        expect![[r#"
+            error[invalid-colors]: color `0xFF007ACC` has more than six hex digits; use `Color::rgba` for `0xRRGGBBAA` colors (color variable `accent`)
+            error[invalid-colors]: color `0x1E1E1E80` has more than six hex digits; use `Color::rgba` for `0xRRGGBBAA` colors (theme)
+            error[invalid-colors]: color `0x11223380` has more than six hex digits; use `Color::rgba` for `0xRRGGBBAA` colors (workbench color `editor.background`)
+            error[invalid-colors]: color `0x569CD6FF` has more than six hex digits; use `Color::rgba` for `0xRRGGBBAA` colors (theme)
+            error[invalid-colors]: color `0xCE917880` has more than six hex digits; use `Color::rgba` for `0xRRGGBBAA` colors (theme)
+        "#]]
+        .assert_eq(&diagnostics);
+    }
+
+    #[test]
+    fn try_rgb() {
+        assert!(matches!(
+            Color::try_rgb(0x445566),
+            Ok(Color(Ok(proto::Color { r: 0x44, g: 0x55, b: 0x66, a: 0xFF })))
+        ));
+
+        let error = Color::try_rgb(0x11223380).err().unwrap();
+        assert_eq!(error, TooManyHexDigits(0x11223380));
+        assert_eq!(
+            error.to_string(),
+            "color `0x11223380` has more than six hex digits; use `Color::rgba` for `0xRRGGBBAA` colors"
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "color has more than six hex digits; use `Color::rgba` for `0xRRGGBBAA` colors"
+    )]
+    fn eight_digit_style_rgb() {
+        let _ = Style::rgb(0x11223380);
+    }
+
+    #[test]
+    fn verify() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x1E1E1E);
+        t.w(["editor.foreground"], 0xD4D4D4);
+        t.a([tm("keyword"), s("keyword")], 0x569CD6);
+        assert_eq!(t.verify(), []);
+
+        t.w(["editor.foregroud"], 0xD4D4D4);
+        let diagnostics = t.verify();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].lint, "unknown-keys");
+        assert_eq!(diagnostics[0].severity, lint::Severity::Warning);
+        assert_eq!(
+            diagnostics[0].location,
+            lint::Location::WorkbenchRule("editor.foregroud".into())
+        );
+    }
+
+    #[test]
+    fn with_capacity() {
+        let mut t = ThemeBuilder::with_capacity(100, 50, 200);
+        assert!(t.textmate_rules.capacity() >= 100);
+        assert!(t.semantic_rules().capacity() >= 50);
+        assert!(t.workbench_rules.capacity() >= 200);
+
+        t.w(["editor.background"], 0x1E1E1E);
+        t.a([tm("keyword"), s("keyword")], 0x569CD6);
+
+        assert_eq!(t.build("My cool theme"), {
+            let mut t = ThemeBuilder::default();
+            t.w(["editor.background"], 0x1E1E1E);
+            t.a([tm("keyword"), s("keyword")], 0x569CD6);
+            t.build("My cool theme")
+        });
+    }
+
+    #[test]
+    fn scopes_macro() {
+        let selectors = crate::scopes![
+            keyword.operator, s:variable.mutable:rust
+            "meta.function-call" s:*.documentation entity.name.type-parameter
+        ];
+
+        assert_eq!(
+            selectors,
+            [
+                tm("keyword.operator"),
+                s("variable.mutable:rust"),
+                tm("meta.function-call"),
+                s("*.documentation"),
+                tm("entity.name.type-parameter"),
+            ]
+        );
+    }
+
+    #[test]
+    fn theme_macro() {
+        const ACCENT: u32 = 0x569CD6;
+
+        let theme = crate::theme! {
+            "My cool theme": dark;
+            defaults(0xD4D4D4, 0x1E1E1E);
+            w(editor.background, sideBar.background) => 0x1E1E1E;
+            keyword, storage.type, s(function.declaration) => ACCENT bold;
+            "meta.function-call", entity.name.function-call => (0xDCDCAA, 0x80);
+            comment, s(*.documentation:rust) => italic;
+            string => 0xCE9178;
+        };
+
+        let mut t = ThemeBuilder::default();
+        t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+        t.w(["editor.background", "sideBar.background"], 0x1E1E1E);
+        t.a(
+            [tm("keyword"), tm("storage.type"), s("function.declaration")],
+            (ACCENT, FontStyle::Bold),
+        );
+        t.a([tm("meta.function-call"), tm("entity.name.function-call")], (0xDCDCAA, 0x80));
+        t.a([tm("comment"), s("*.documentation:rust")], FontStyle::Italic);
+        t.a([tm("string")], 0xCE9178);
+
+        assert_eq!(theme, t.build_with_type("My cool theme", proto::ThemeType::Dark));
+    }
+
+    #[test]
+    fn style_composition() {
+        const EMPHASIS: Style = Style::rgb(0x569CD6).with(FontStyle::Italic);
+
+        let mut t = ThemeBuilder::default();
+        t.a([tm("emphasis"), s("variable")], EMPHASIS + FontStyle::Bold);
+        t.a([tm("override")], EMPHASIS + (0xCE9178, FontStyle::Clear) + FontStyle::Underline);
+        t.a([tm("font.only")], FontStyle::Bold + FontStyle::Underline);
+        let theme = t.build("My cool theme");
+
+        let settings: Vec<_> = theme.textmate_rules.iter().map(|rule| rule.settings).collect();
+        assert_eq!(
+            settings,
+            [
+                proto::textmate::RuleSettings {
+                    foreground: Some(proto::Color { r: 0x56, g: 0x9C, b: 0xD6, a: 0xFF }),
+                    font_style: proto::textmate::FontStyle::Set {
+                        bold: true,
+                        italic: true,
+                        underline: false
+                    },
+                },
+                proto::textmate::RuleSettings {
+                    foreground: Some(proto::Color { r: 0xCE, g: 0x91, b: 0x78, a: 0xFF }),
+                    font_style: proto::textmate::FontStyle::Set {
+                        bold: false,
+                        italic: false,
+                        underline: true
+                    },
+                },
+                proto::textmate::RuleSettings {
+                    foreground: None,
+                    font_style: proto::textmate::FontStyle::Set {
+                        bold: true,
+                        italic: false,
+                        underline: true
+                    },
+                },
+            ]
+        );
+
+        let proto::semantic::Highlighting::On { rules } = &theme.semantic_highlighting else {
+            unreachable!()
+        };
+        assert_eq!(
+            rules[0].font_style,
+            proto::semantic::FontStyle {
+                bold: proto::semantic::FontStyleSetting::True,
+                italic: proto::semantic::FontStyleSetting::True,
+                underline: proto::semantic::FontStyleSetting::Inherit,
+            }
+        );
+    }
+
+    #[test]
+    fn style_presets() {
+        let mut presets = StyleRegistry::new();
+        presets.define("accent", 0x569CD6);
+        presets.define("emphasis", presets.get("accent") + FontStyle::Italic);
+
+        let mut t = ThemeBuilder::default();
+        t.a([tm("markup.italic")], presets.get("emphasis"));
+
+        assert_eq!(
+            t.textmate_rules[0].settings,
+            Style::from((0x569CD6, FontStyle::Italic)).textmate_settings()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Style preset ‘missing’ is not defined")]
+    fn undefined_style_preset() {
+        StyleRegistry::new().get("missing");
+    }
+
+    #[test]
+    fn note_on_mixed_selectors() {
+        let mut t = ThemeBuilder::default();
+        t.a([tm("comment")], 0x6A9955);
+        t.a_with_note([s("keyword"), tm("keyword")], 0x569CD6, "matches semantic keywords");
+
+        assert_eq!(t.textmate_rules[0].note, None);
+        assert_eq!(t.textmate_rules[1].note.as_deref(), Some("matches semantic keywords"));
+    }
+
+    #[test]
+    fn note_without_textmate_scopes() {
+        let mut t = ThemeBuilder::default();
+        t.a_with_note([s("keyword")], 0x569CD6, "semantic only");
+        t.a([tm("keyword")], 0x569CD6);
+
+        assert_eq!(t.semantic_rules().len(), 1);
+        assert_eq!(t.textmate_rules[0].note, None);
+    }
+
+    #[test]
+    fn workbench_references() {
+        let mut t = ThemeBuilder::default();
+        t.w(["panel.background"], same_as("sideBar.background"));
+        t.w(["statusBar.background"], same_as("panel.background"));
+        t.w(["sideBar.background"], 0x252526);
+        t.w(["sideBar.border"], same_as("sideBar.background"));
+        t.w(["sideBar.border"], 0x2B2B2B);
+        let theme = t.build("My cool theme");
+
+        let sidebar = Some(proto::Color { r: 0x25, g: 0x25, b: 0x26, a: 0xFF });
+        assert_eq!(theme.workbench_rules["panel.background"], sidebar);
+        assert_eq!(theme.workbench_rules["statusBar.background"], sidebar);
+        assert_eq!(
+            theme.workbench_rules["sideBar.border"],
+            Some(proto::Color { r: 0x2B, g: 0x2B, b: 0x2B, a: 0xFF })
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "workbench color `panel.background` refers to `sideBar.background`, which is not defined"
+    )]
+    fn undefined_workbench_reference() {
+        let mut t = ThemeBuilder::default();
+        t.w(["panel.background"], same_as("sideBar.background"));
+        t.build("My cool theme");
+    }
+
+    #[test]
+    fn verify_workbench_references() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x1E1E1E);
+        t.w(["panel.background"], same_as("sideBar.background"));
+        t.w(["editor.lineHighlightBackground"], same_as("editor.selectionBackground"));
+        t.w(["editor.selectionBackground"], same_as("editor.lineHighlightBackground"));
+
+        let diagnostics: Vec<_> = t
+            .verify()
+            .into_iter()
+            .filter(|diagnostic| diagnostic.lint == "workbench-references")
+            .map(|diagnostic| {
+                assert_eq!(diagnostic.severity, lint::Severity::Error);
+                (diagnostic.location, diagnostic.message)
+            })
+            .collect();
+
+        assert_eq!(
+            diagnostics,
+            [
+                (
+                    lint::Location::WorkbenchRule("panel.background".into()),
+                    "workbench color `panel.background` refers to `sideBar.background`, which is not defined"
+                        .to_string()
+                ),
+                (
+                    lint::Location::WorkbenchRule("editor.lineHighlightBackground".into()),
+                    "workbench color `editor.lineHighlightBackground` refers back to itself through \
+                     `editor.selectionBackground`"
+                        .to_string()
+                ),
+                (
+                    lint::Location::WorkbenchRule("editor.selectionBackground".into()),
+                    "workbench color `editor.selectionBackground` refers back to itself through \
+                     `editor.lineHighlightBackground`"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn color_variables() {
+        let mut t = ThemeBuilder::default();
+        t.define("accent", 0xF92672);
+        t.define("muted", 0x75715E);
+        t.define("unused", 0x000000);
+        t.a([tm("keyword"), s("keyword")], var("accent"));
+        t.a([tm("comment")], (var("muted"), FontStyle::Italic));
+        t.a([tm("comment.line")], Style::from(var("muted")) + 0x888888);
+        t.w(["focusBorder"], var("accent"));
+
+        let diagnostics: String = t.verify().iter().map(|d| format!("{d}\n")).collect();
+        expect![[r#"
+            warning[unused-variables]: color variable `unused` is never used (color variable `unused`)
+        "#]]
+        .assert_eq(&diagnostics);
+
+        let theme = t.build("My cool theme");
+        let accent = Some(proto::Color { r: 0xF9, g: 0x26, b: 0x72, a: 0xFF });
+        assert_eq!(theme.textmate_rules[0].settings.foreground, accent);
+        assert_eq!(
+            theme.textmate_rules[1].settings.foreground,
+            Some(proto::Color { r: 0x75, g: 0x71, b: 0x5E, a: 0xFF })
+        );
+        assert_eq!(
+            theme.textmate_rules[2].settings.foreground,
+            Some(proto::Color { r: 0x88, g: 0x88, b: 0x88, a: 0xFF })
+        );
+        assert_eq!(theme.workbench_rules["focusBorder"], accent);
+    }
+
+    #[test]
+    #[should_panic(expected = "color variable `accent` is not defined")]
+    fn undefined_color_variable() {
+        let mut t = ThemeBuilder::default();
+        t.a([tm("keyword")], var("accent"));
+        t.build("My cool theme");
+    }
+
+    #[test]
+    fn verify_undefined_color_variables() {
+        let mut t = ThemeBuilder::default();
+        t.a([tm("keyword"), s("keyword")], var("accent"));
+        t.a([tm("comment")], (var("accent"), FontStyle::Italic));
+        t.w(["focusBorder"], var("focus"));
+
+        let diagnostics: String = t.verify().iter().map(|d| format!("{d}\n")).collect();
+        expect![[r#"
+            error[undefined-variables]: color variable `accent` is not defined (color variable `accent`)
+            error[undefined-variables]: color variable `focus` is not defined (color variable `focus`)
+        "#]]
+        .assert_eq(&diagnostics);
+    }
+
+    #[test]
+    fn rule_hooks() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let added = Arc::new(AtomicUsize::new(0));
+
+        let mut t = ThemeBuilder::default();
+        t.on_rule({
+            let added = Arc::clone(&added);
+            move |_| {
+                added.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        t.on_rule(|rule| match rule {
+            RuleMut::Workbench(key, Some(color)) if key.ends_with("Background") => color.a = 0x80,
+            RuleMut::TextMate(rule) => rule.scope.retain(|scope| !scope.ends_with(".rust")),
+            RuleMut::Semantic(_, style) => style.foreground = None,
+            RuleMut::Workbench(..) => {}
+        });
+
+        t.w(["editor.selectionBackground", "editor.foreground"], 0x264F78);
+        t.unset(["editor.lineHighlightBackground"]);
+        t.a([tm("keyword"), tm("keyword.rust"), s("keyword")], 0x569CD6);
+
+        assert_eq!(added.load(Ordering::Relaxed), 5);
+        assert_eq!(
+            t.workbench_rules.values().copied().collect::<Vec<_>>(),
+            [
+                Some(proto::Color { r: 0x26, g: 0x4F, b: 0x78, a: 0x80 }),
+                Some(proto::Color { r: 0x26, g: 0x4F, b: 0x78, a: 0xFF }),
+                None,
+            ]
+        );
+        assert_eq!(t.textmate_rules[0].scope, [Cow::Borrowed("keyword")]);
+        assert_eq!(t.semantic_rules()[0].foreground, None);
+    }
+
+    #[test]
+    fn rule_hooks_and_derived_colors() {
+        use std::sync::Mutex;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+
+        let mut t = ThemeBuilder::default();
+        t.on_rule({
+            let seen = Arc::clone(&seen);
+            move |rule| match rule {
+                RuleMut::Workbench(key, color) => {
+                    seen.lock().unwrap().push(key.to_string());
+                    if let Some(color) = color {
+                        color.a /= 2;
+                    }
+                }
+                RuleMut::TextMate(rule) => rule.scope.retain(|scope| !scope.ends_with(".rust")),
+                RuleMut::Semantic(..) => {}
+            }
+        });
+
+        t.w(["button.background"], derive::States::from(0x0E639C));
+        t.w(["panel.background"], same_as("button.background"));
+        t.a([tm("keyword.rust"), s("keyword")], 0x569CD6);
+        t.a_with_note([tm("comment.rust")], 0x6A9955, "dropped by the hook");
+        let theme = t.build("My cool theme");
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            ["button.background", "button.hoverBackground", "button.border"]
+        );
+        assert_eq!(theme.workbench_rules["button.background"].unwrap().a, 0x7F);
+        assert_eq!(theme.workbench_rules["panel.background"].unwrap().a, 0x7F);
+        assert_eq!(theme.textmate_rules, []);
+        assert!(matches!(
+            theme.semantic_highlighting,
+            proto::semantic::Highlighting::On { rules } if rules.len() == 1
+        ));
+    }
+
+    #[test]
+    fn dump() {
+        let mut t = ThemeBuilder::default();
+        t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+        t.w(["editor.background"], 0x1E1E1E);
+        t.w(["focusBorder"], 0x569CD6);
+        t.unset(["editor.lineHighlightBorder"]);
+        t.a([tm("keyword"), tm("storage"), s("keyword")], (0x569CD6, FontStyle::Bold));
+        t.a([tm("comment"), s("*.documentation")], FontStyle::Italic);
+        t.a([tm("markup.bold")], FontStyle::Clear);
+
+        expect![[r#"
+            #D4D4D4
+              defaults   foreground
+            #1E1E1E
+              defaults   background
+              workbench  editor.background
+            #569CD6
+              textmate   keyword, storage            bold
+              semantic   keyword                     bold
+              workbench  focusBorder
+            (no color)
+              textmate   comment                     italic
+              textmate   markup.bold                 clear
+              semantic   *.documentation             italic
+              workbench  editor.lineHighlightBorder  unset
+        "#]]
+        .assert_eq(&t.dump());
+    }
 }
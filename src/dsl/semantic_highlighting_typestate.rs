@@ -0,0 +1,85 @@
+use super::{Style, ThemeBuilder};
+use crate::proto;
+use indexmap::IndexMap;
+use std::borrow::Cow;
+use std::fmt;
+
+mod private {
+    pub trait Sealed {}
+}
+
+pub trait SemanticHighlighting: private::Sealed + Clone + fmt::Debug {
+    fn highlighting(
+        rules: IndexMap<proto::semantic::Selector, proto::semantic::Style>,
+    ) -> proto::semantic::Highlighting;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SemanticHighlightingEnabled;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SemanticHighlightingDisabled;
+
+impl private::Sealed for SemanticHighlightingEnabled {}
+impl private::Sealed for SemanticHighlightingDisabled {}
+
+impl SemanticHighlighting for SemanticHighlightingEnabled {
+    fn highlighting(
+        rules: IndexMap<proto::semantic::Selector, proto::semantic::Style>,
+    ) -> proto::semantic::Highlighting {
+        proto::semantic::Highlighting::On { rules }
+    }
+}
+
+impl SemanticHighlighting for SemanticHighlightingDisabled {
+    fn highlighting(
+        _rules: IndexMap<proto::semantic::Selector, proto::semantic::Style>,
+    ) -> proto::semantic::Highlighting {
+        proto::semantic::Highlighting::Off
+    }
+}
+
+impl ThemeBuilder<SemanticHighlightingDisabled> {
+    pub fn textmate_only() -> Self {
+        Self::with_rule_capacity(0, 0, 0)
+    }
+
+    pub fn a<T>(&mut self, scopes: impl IntoIterator<Item = T>, style: impl Into<Style>)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let scopes = scopes.into_iter().map(Into::into).collect();
+        self.add_rules(scopes, Vec::new(), style.into(), None);
+    }
+
+    pub fn a_with_note<T>(
+        &mut self,
+        scopes: impl IntoIterator<Item = T>,
+        style: impl Into<Style>,
+        note: impl Into<Cow<'static, str>>,
+    ) where
+        T: Into<Cow<'static, str>>,
+    {
+        let scopes = scopes.into_iter().map(Into::into).collect();
+        self.add_rules(scopes, Vec::new(), style.into(), Some(note.into()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::FontStyle;
+
+    #[test]
+    fn textmate_only() {
+        let mut t = ThemeBuilder::textmate_only();
+        t.a(["comment", "string.quoted"], (0x6A9955, FontStyle::Italic));
+        t.a([String::from("keyword")], 0x569CD6);
+        t.w(["editor.background"], 0x1E1E1E);
+
+        let theme = t.build("Plain");
+        assert_eq!(theme.semantic_highlighting, proto::semantic::Highlighting::Off);
+        assert_eq!(theme.textmate_rules.len(), 2);
+        assert_eq!(theme.textmate_rules[0].scope, ["comment", "string.quoted"]);
+    }
+}
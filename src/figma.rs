@@ -0,0 +1,112 @@
+use crate::palette::Palette;
+use crate::{ser, SerializeOptions, SerializeThemeError};
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+
+pub fn tokens(set: &str, palette: &Palette) -> Result<String, FigmaError> {
+    let mut tokens = Map::new();
+    let mut names: Vec<&str> = Vec::new();
+
+    for (name, color) in palette.iter() {
+        if let Some(other) =
+            names.iter().find(|other| is_nested(other, name) || is_nested(name, other))
+        {
+            return Err(FigmaError::PathCollision(other.to_string(), name.to_string()));
+        }
+        names.push(name);
+
+        let mut group = &mut tokens;
+        let mut path = name.split('.').peekable();
+
+        while let Some(segment) = path.next() {
+            if path.peek().is_none() {
+                group.insert(
+                    segment.to_string(),
+                    json!({ "value": color.to_string(), "type": "color" }),
+                );
+                break;
+            }
+
+            let entry = group.entry(segment).or_insert_with(|| Value::Object(Map::new()));
+            group = entry.as_object_mut().unwrap();
+        }
+    }
+
+    let mut sets = Map::new();
+    sets.insert(set.to_string(), Value::Object(tokens));
+
+    Ok(ser::to_string(&sets, &SerializeOptions::default())?)
+}
+
+fn is_nested(group: &str, token: &str) -> bool {
+    token.strip_prefix(group).is_some_and(|rest| rest.starts_with('.'))
+}
+
+#[derive(Debug, Error)]
+pub enum FigmaError {
+    #[error("palette entries `{0}` and `{1}` collide because one is nested inside the other")]
+    PathCollision(String, String),
+    #[error(transparent)]
+    Serialize(#[from] SerializeThemeError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::expect;
+
+    #[test]
+    fn token_set() {
+        let mut palette = Palette::new();
+        palette.insert("background", 0x1E1E1E);
+        palette.insert("accent.blue", 0x569CD6);
+        palette.insert("accent.orange", (0xCE9178, 0x80));
+
+        expect![[r##"
+            {
+                "My cool theme": {
+                    "background": {
+                        "value": "#1E1E1E",
+                        "type": "color"
+                    },
+                    "accent": {
+                        "blue": {
+                            "value": "#569CD6",
+                            "type": "color"
+                        },
+                        "orange": {
+                            "value": "#CE917880",
+                            "type": "color"
+                        }
+                    }
+                }
+            }
+        "##]]
+        .assert_eq(&tokens("My cool theme", &palette).unwrap());
+    }
+
+    #[test]
+    fn path_collision() {
+        let mut palette = Palette::new();
+        palette.insert("accent", 0x569CD6);
+        palette.insert("accentuated", 0x569CD6);
+        assert!(tokens("My cool theme", &palette).is_ok());
+
+        palette.insert("accent.blue", 0x569CD6);
+
+        let error = tokens("My cool theme", &palette).unwrap_err();
+        assert!(matches!(
+            &error,
+            FigmaError::PathCollision(group, token) if group == "accent" && token == "accent.blue"
+        ));
+
+        let mut palette = Palette::new();
+        palette.insert("accent.blue", 0x569CD6);
+        palette.insert("accent", 0x569CD6);
+
+        assert!(matches!(
+            tokens("My cool theme", &palette),
+            Err(FigmaError::PathCollision(token, group)) if token == "accent.blue" && group == "accent"
+        ));
+    }
+}
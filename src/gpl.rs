@@ -0,0 +1,94 @@
+use crate::palette::Palette;
+use crate::proto;
+use thiserror::Error;
+
+const HEADER: &str = "GIMP Palette";
+
+pub fn parse(src: &str) -> Result<Palette, GplError> {
+    let mut lines = src.lines().enumerate();
+
+    match lines.next() {
+        Some((_, line)) if line.trim() == HEADER => {}
+        _ => return Err(GplError::MissingHeader),
+    }
+
+    let mut palette = Palette::new();
+
+    for (idx, line) in lines {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || is_metadata(line) {
+            continue;
+        }
+
+        let invalid = || GplError::InvalidLine { line: idx + 1, content: line.to_string() };
+
+        let mut parts = line.split_whitespace();
+        let mut channel = || parts.next().and_then(|c| c.parse::<u8>().ok()).ok_or_else(invalid);
+        let color = proto::Color { r: channel()?, g: channel()?, b: channel()?, a: 0xFF };
+
+        let name = parts.collect::<Vec<_>>().join(" ");
+        let name = if name.is_empty() || name == "Untitled" {
+            format!("color{}", palette.len() + 1)
+        } else {
+            name
+        };
+
+        palette.insert(name, color);
+    }
+
+    Ok(palette)
+}
+
+fn is_metadata(line: &str) -> bool {
+    line.split_once(':').is_some_and(|(key, _)| matches!(key.trim(), "Name" | "Columns"))
+}
+
+#[derive(Debug, Error)]
+pub enum GplError {
+    #[error("not a GIMP palette (expected `{HEADER}` on the first line)")]
+    MissingHeader,
+    #[error("line {line}: expected `R G B [name]`, found `{content}`")]
+    InvalidLine { line: usize, content: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::expect;
+
+    #[test]
+    fn gimp_palette() {
+        let palette = parse(
+            "GIMP Palette\n\
+             Name: Mottle\n\
+             Columns: 4\n\
+             # exported from Inkscape\n\
+             \n\
+             30  30  30\tbackground\n\
+             86 156 214\tkeyword blue\n\
+             206 145 120\n",
+        )
+        .unwrap();
+
+        let actual: String =
+            palette.iter().map(|(name, color)| format!("{name} {color}\n")).collect();
+        expect![[r#"
+            background #1E1E1E
+            keyword blue #569CD6
+            color3 #CE9178
+        "#]]
+        .assert_eq(&actual);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse("JASC-PAL\n").unwrap_err().to_string(),
+            "not a GIMP palette (expected `GIMP Palette` on the first line)"
+        );
+        assert_eq!(
+            parse("GIMP Palette\n255 0\n").unwrap_err().to_string(),
+            "line 2: expected `R G B [name]`, found `255 0`"
+        );
+    }
+}
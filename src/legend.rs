@@ -0,0 +1,109 @@
+use crate::proto::{self, semantic};
+use serde::Deserialize;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Legend {
+    pub token_types: Vec<String>,
+    pub token_modifiers: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LegendCoverage {
+    pub styled_types: Vec<String>,
+    pub unstyled_types: Vec<String>,
+    pub styled_modifiers: Vec<String>,
+    pub unstyled_modifiers: Vec<String>,
+}
+
+impl Legend {
+    pub fn from_json(src: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(src)
+    }
+
+    pub fn coverage(&self, theme: &proto::Theme) -> LegendCoverage {
+        let selectors: Vec<_> = match &theme.semantic_highlighting {
+            semantic::Highlighting::On { rules } => rules.keys().collect(),
+            _ => Vec::new(),
+        };
+
+        let mut coverage = LegendCoverage::default();
+
+        for token_type in &self.token_types {
+            let is_styled = selectors.iter().any(|selector| match &selector.kind {
+                semantic::TokenKind::Specific(kind) => kind.as_str() == token_type,
+                semantic::TokenKind::Wildcard => false,
+            });
+
+            if is_styled {
+                coverage.styled_types.push(token_type.clone());
+            } else {
+                coverage.unstyled_types.push(token_type.clone());
+            }
+        }
+
+        for modifier in &self.token_modifiers {
+            let is_styled = selectors
+                .iter()
+                .any(|selector| selector.modifiers.iter().any(|m| m.as_str() == modifier));
+
+            if is_styled {
+                coverage.styled_modifiers.push(modifier.clone());
+            } else {
+                coverage.unstyled_modifiers.push(modifier.clone());
+            }
+        }
+
+        coverage
+    }
+}
+
+impl fmt::Display for LegendCoverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sections = [
+            ("token types", &self.styled_types, &self.unstyled_types),
+            ("token modifiers", &self.styled_modifiers, &self.unstyled_modifiers),
+        ];
+
+        for (name, styled, unstyled) in sections {
+            writeln!(f, "{} of {} {name} are styled", styled.len(), styled.len() + unstyled.len())?;
+
+            for token in unstyled {
+                writeln!(f, "  unstyled: {token}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{s, ThemeBuilder};
+    use expect_test::expect;
+
+    #[test]
+    fn coverage() {
+        let legend = Legend::from_json(
+            r#"{
+                "tokenTypes": ["keyword", "function", "lifetime", "selfKeyword"],
+                "tokenModifiers": ["mutable", "unsafe", "documentation"]
+            }"#,
+        )
+        .unwrap();
+
+        let mut t = ThemeBuilder::default();
+        t.a([s("keyword"), s("lifetime.declaration")], 0x569CD6);
+        t.a([s("*.mutable"), s("function.unsafe:rust")], 0xFF0000);
+
+        expect![[r#"
+            3 of 4 token types are styled
+              unstyled: selfKeyword
+            2 of 3 token modifiers are styled
+              unstyled: documentation
+        "#]]
+        .assert_eq(&legend.coverage(&t.build("My cool theme")).to_string());
+    }
+}
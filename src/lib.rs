@@ -1,50 +1,974 @@
+pub mod accessibility;
+#[cfg(any(feature = "export", feature = "import"))]
+pub mod ase;
+pub mod baseline;
+#[cfg(feature = "export")]
+pub mod bat;
+#[cfg(feature = "export")]
+pub mod captures;
+pub mod compare;
+#[cfg(feature = "coverage")]
+pub mod coverage;
+pub mod defaults;
+pub mod derive;
+#[cfg(feature = "std-fs")]
+mod diff;
 pub mod dsl;
+#[cfg(feature = "export")]
+pub mod figma;
+#[cfg(feature = "import")]
+pub mod gpl;
+pub mod legend;
+pub mod lint;
+pub mod manifest;
+#[cfg(feature = "export")]
+pub mod neovim;
+pub mod oklab;
+pub mod palette;
+#[cfg(feature = "import")]
+pub mod parse;
+#[cfg(feature = "preview")]
+pub mod preview;
 pub mod proto;
+#[cfg(feature = "export")]
+pub mod readme;
+#[cfg(feature = "std-fs")]
+pub mod regenerate;
+pub mod roles;
+#[cfg(any(feature = "preview", feature = "thumbnail"))]
+mod sample;
+mod ser;
+#[cfg(feature = "export")]
+pub mod settings;
+#[cfg(feature = "export")]
+pub mod shiki;
+#[cfg(feature = "import")]
+pub mod spec;
+#[cfg(feature = "export")]
+pub mod svg;
+#[cfg(feature = "export")]
+pub mod terminal;
+#[cfg(any(feature = "std-fs", feature = "testing"))]
+pub mod testing;
+#[cfg(feature = "thumbnail")]
+pub mod thumbnail;
+#[cfg(feature = "export")]
+pub mod tmtheme;
+#[cfg(feature = "tweak")]
+pub mod tweak;
+#[cfg(feature = "validate")]
+pub mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "watch")]
+mod watch;
+pub mod workbench;
 
-use serde::Serialize;
-use serde_json::ser::PrettyFormatter;
-use serde_json::Serializer;
-use std::path::{Path, PathBuf};
-use std::{fs, io};
+#[cfg(feature = "watch")]
+pub use watch::{watch, watch_to, WatchError};
+
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::string::FromUtf8Error;
+use std::{fmt, io};
 use thiserror::Error;
 
-pub fn save_theme(theme: &proto::Theme) -> Result<(), SaveThemeError> {
-    let themes_dir = prepare_themes_dir()?;
-    let theme_path = themes_dir.join(format!("{}-color-theme.json", theme.name));
+#[cfg(feature = "std-fs")]
+use std::fs;
+#[cfg(feature = "std-fs")]
+use std::path::Path;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg(feature = "std-fs")]
+pub fn save_theme(theme: &proto::Theme) -> Result<SaveStatus, SaveThemeError> {
+    save_theme_to("themes", theme, FileNames::ThemeName)
+}
+
+#[cfg(feature = "std-fs")]
+pub fn save_theme_to(
+    dir: impl AsRef<Path>,
+    theme: &proto::Theme,
+    file_names: FileNames,
+) -> Result<SaveStatus, SaveThemeError> {
+    let themes_dir = prepare_themes_dir(dir.as_ref())?;
+    write_theme(themes_dir.join(theme_file_name(theme, file_names)?), theme)
+}
+
+#[cfg(feature = "std-fs")]
+pub fn save_all(
+    themes: &[proto::Theme],
+    stale: StaleFiles,
+) -> Result<SaveAllReport, SaveThemeError> {
+    save_all_to("themes", themes, stale, FileNames::ThemeName)
+}
+
+#[cfg(feature = "std-fs")]
+pub fn save_all_to(
+    dir: impl AsRef<Path>,
+    themes: &[proto::Theme],
+    stale: StaleFiles,
+    file_names: FileNames,
+) -> Result<SaveAllReport, SaveThemeError> {
+    let themes_dir = prepare_themes_dir(dir.as_ref())?;
+    let paths = theme_paths(themes_dir, themes, file_names)?;
+    let mut report = SaveAllReport::default();
+
+    for (theme, path) in themes.iter().zip(paths) {
+        let status = write_theme(path.clone(), theme)?;
+        report.saved.push((path, status));
+    }
+
+    if stale == StaleFiles::Remove {
+        let saved: Vec<_> = report.saved.iter().map(|(path, _)| path.clone()).collect();
+
+        for path in stale_theme_files(themes_dir, &saved)? {
+            fs::remove_file(&path)
+                .map_err(|e| SaveThemeError::RemoveStaleTheme(e, path.clone()))?;
+            report.removed.push(path);
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(feature = "std-fs")]
+fn theme_paths(
+    themes_dir: &Path,
+    themes: &[proto::Theme],
+    file_names: FileNames,
+) -> Result<Vec<PathBuf>, SaveThemeError> {
+    let mut owners = std::collections::HashMap::new();
+    let mut paths = Vec::with_capacity(themes.len());
+
+    for theme in themes {
+        let path = themes_dir.join(theme_file_name(theme, file_names)?);
+
+        if let Some(first) = owners.insert(path.clone(), &theme.name) {
+            return Err(SaveThemeError::DuplicateThemeFile(
+                first.clone(),
+                theme.name.clone(),
+                path,
+            ));
+        }
+
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+#[cfg(all(feature = "rayon", feature = "std-fs"))]
+pub fn generate_variants<P, F>(palettes: &[P], build: F) -> Result<SaveAllReport, SaveThemeError>
+where
+    P: Sync,
+    F: Fn(&P) -> proto::Theme + Sync,
+{
+    generate_variants_to("themes", palettes, FileNames::ThemeName, build)
+}
+
+#[cfg(all(feature = "rayon", feature = "std-fs"))]
+pub fn generate_variants_to<P, F>(
+    dir: impl AsRef<Path>,
+    palettes: &[P],
+    file_names: FileNames,
+    build: F,
+) -> Result<SaveAllReport, SaveThemeError>
+where
+    P: Sync,
+    F: Fn(&P) -> proto::Theme + Sync,
+{
+    use rayon::prelude::*;
 
-    fs::write(&theme_path, serialize_theme(theme))
-        .map_err(|e| SaveThemeError::WriteTheme(e, theme_path))?;
+    let themes_dir = prepare_themes_dir(dir.as_ref())?;
+    let themes: Vec<_> = palettes.par_iter().map(&build).collect();
+    let paths = theme_paths(themes_dir, &themes, file_names)?;
 
-    Ok(())
+    let saved = themes
+        .par_iter()
+        .zip(paths)
+        .map(|(theme, path)| {
+            let status = write_theme(path.clone(), theme)?;
+            Ok((path, status))
+        })
+        .collect::<Result<_, SaveThemeError>>()?;
+
+    Ok(SaveAllReport { saved, removed: Vec::new() })
+}
+
+#[cfg(feature = "std-fs")]
+pub fn plan_save_all(
+    themes: &[proto::Theme],
+    stale: StaleFiles,
+) -> Result<SavePlan, SaveThemeError> {
+    plan_save_all_to("themes", themes, stale, FileNames::ThemeName)
+}
+
+#[cfg(feature = "std-fs")]
+pub fn plan_save_all_to(
+    dir: impl AsRef<Path>,
+    themes: &[proto::Theme],
+    stale: StaleFiles,
+    file_names: FileNames,
+) -> Result<SavePlan, SaveThemeError> {
+    let themes_dir = dir.as_ref();
+    if themes_dir.exists() && !themes_dir.is_dir() {
+        return Err(SaveThemeError::ThemesDirIsNotDir(themes_dir.to_path_buf()));
+    }
+
+    let mut plan = SavePlan::default();
+
+    for theme in themes {
+        let path = themes_dir.join(theme_file_name(theme, file_names)?);
+        let prepared = prepare_theme(path, theme)?;
+        let status = prepared.status();
+
+        let diff = match (status, &prepared.existing) {
+            (SaveStatus::Updated, Some(existing)) => {
+                let label = prepared.path.display().to_string();
+                Some(diff::unified(
+                    &String::from_utf8_lossy(existing),
+                    &String::from_utf8_lossy(&prepared.serialized),
+                    &label,
+                    &label,
+                ))
+            }
+            _ => None,
+        };
+
+        plan.writes.push(PlannedWrite { path: prepared.path, status, diff });
+    }
+
+    if stale == StaleFiles::Remove && themes_dir.is_dir() {
+        let written: Vec<_> = plan.writes.iter().map(|write| write.path.clone()).collect();
+        plan.removals = stale_theme_files(themes_dir, &written)?;
+    }
+
+    Ok(plan)
 }
 
-pub fn serialize_theme(theme: &proto::Theme) -> String {
-    let mut v = b"// Do not edit directly; this file is generated.\n".to_vec();
-    let mut serializer = Serializer::with_formatter(&mut v, PrettyFormatter::with_indent(b"    "));
-    theme.serialize(&mut serializer).unwrap();
-    v.push(b'\n');
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SavePlan {
+    pub writes: Vec<PlannedWrite>,
+    pub removals: Vec<PathBuf>,
+}
 
-    String::from_utf8(v).unwrap()
+impl SavePlan {
+    pub fn is_up_to_date(&self) -> bool {
+        self.removals.is_empty()
+            && self.writes.iter().all(|write| write.status == SaveStatus::Unchanged)
+    }
 }
 
-fn prepare_themes_dir() -> Result<&'static Path, SaveThemeError> {
-    let themes_dir = Path::new("themes");
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedWrite {
+    pub path: PathBuf,
+    pub status: SaveStatus,
+    pub diff: Option<String>,
+}
+
+impl fmt::Display for SavePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for write in &self.writes {
+            let verb = match write.status {
+                SaveStatus::Created => "create",
+                SaveStatus::Updated => "update",
+                SaveStatus::Unchanged => "unchanged",
+            };
+            writeln!(f, "{verb:>9} {}", write.path.display())?;
+        }
+
+        for path in &self.removals {
+            writeln!(f, "{:>9} {}", "remove", path.display())?;
+        }
+
+        for diff in self.writes.iter().filter_map(|write| write.diff.as_ref()) {
+            write!(f, "\n{diff}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std-fs")]
+fn stale_theme_files(themes_dir: &Path, keep: &[PathBuf]) -> Result<Vec<PathBuf>, SaveThemeError> {
+    let read_dir_error = |e| SaveThemeError::ReadThemesDir(e, themes_dir.to_path_buf());
+    let mut stale = Vec::new();
+
+    for entry in fs::read_dir(themes_dir).map_err(read_dir_error)? {
+        let path = entry.map_err(read_dir_error)?.path();
+
+        let is_theme_file = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.ends_with(THEME_FILE_SUFFIX));
 
+        if is_theme_file && !keep.contains(&path) {
+            stale.push(path);
+        }
+    }
+
+    stale.sort();
+
+    Ok(stale)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StaleFiles {
+    Keep,
+    Remove,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SaveAllReport {
+    pub saved: Vec<(PathBuf, SaveStatus)>,
+    pub removed: Vec<PathBuf>,
+}
+
+const THEME_FILE_SUFFIX: &str = "-color-theme.json";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FileNames {
+    #[default]
+    ThemeName,
+    Slug,
+}
+
+pub fn theme_file_name(
+    theme: &proto::Theme,
+    file_names: FileNames,
+) -> Result<String, SaveThemeError> {
+    let stem = match file_names {
+        FileNames::ThemeName if is_valid_file_stem(&theme.name) => Some(theme.name.clone()),
+        FileNames::ThemeName => None,
+        FileNames::Slug => slugify(&theme.name),
+    };
+
+    match stem {
+        Some(stem) => Ok(format!("{stem}{THEME_FILE_SUFFIX}")),
+        None => Err(SaveThemeError::InvalidThemeName(theme.name.clone())),
+    }
+}
+
+fn is_valid_file_stem(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['/', '\\', '\0'])
+}
+
+pub fn slugify(name: &str) -> Option<String> {
+    let mut slug = String::with_capacity(name.len());
+    let mut pending_dash = false;
+
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if pending_dash && !slug.is_empty() {
+                slug.push('-');
+            }
+            pending_dash = false;
+            slug.push(c.to_ascii_lowercase());
+        } else {
+            pending_dash = true;
+        }
+    }
+
+    if slug.is_empty() {
+        None
+    } else {
+        Some(slug)
+    }
+}
+
+#[cfg(feature = "std-fs")]
+struct PreparedTheme {
+    path: PathBuf,
+    serialized: Vec<u8>,
+    existing: Option<Vec<u8>>,
+}
+
+#[cfg(feature = "std-fs")]
+impl PreparedTheme {
+    fn status(&self) -> SaveStatus {
+        match &self.existing {
+            Some(existing) if *normalize_newlines(existing) == self.serialized => {
+                SaveStatus::Unchanged
+            }
+            Some(_) => SaveStatus::Updated,
+            None => SaveStatus::Created,
+        }
+    }
+}
+
+#[cfg(feature = "std-fs")]
+fn normalize_newlines(bytes: &[u8]) -> Cow<'_, [u8]> {
+    if !bytes.contains(&b'\r') {
+        return Cow::Borrowed(bytes);
+    }
+
+    let mut normalized = Vec::with_capacity(bytes.len());
+
+    for (idx, &byte) in bytes.iter().enumerate() {
+        if byte != b'\r' || bytes.get(idx + 1) != Some(&b'\n') {
+            normalized.push(byte);
+        }
+    }
+
+    Cow::Owned(normalized)
+}
+
+#[cfg(feature = "std-fs")]
+fn prepare_theme(path: PathBuf, theme: &proto::Theme) -> Result<PreparedTheme, SaveThemeError> {
+    let mut serialized = Vec::new();
+    serialize_theme_to(&mut serialized, theme).map_err(SaveThemeError::Serialize)?;
+
+    let existing = match fs::read(&path) {
+        Ok(existing) => Some(existing),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(SaveThemeError::ReadExistingTheme(e, path)),
+    };
+
+    Ok(PreparedTheme { path, serialized, existing })
+}
+
+#[cfg(feature = "std-fs")]
+fn write_theme(path: PathBuf, theme: &proto::Theme) -> Result<SaveStatus, SaveThemeError> {
+    let prepared = prepare_theme(path, theme)?;
+    let status = prepared.status();
+
+    if status != SaveStatus::Unchanged {
+        let PreparedTheme { path, serialized, .. } = prepared;
+        fs::write(&path, serialized).map_err(|e| SaveThemeError::WriteTheme(e, path))?;
+    }
+
+    Ok(status)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SaveStatus {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+pub fn serialize_theme(theme: &proto::Theme) -> Result<String, SerializeThemeError> {
+    serialize_theme_with_options(theme, &SerializeOptions::default())
+}
+
+pub fn serialize_theme_with_options(
+    theme: &proto::Theme,
+    options: &SerializeOptions,
+) -> Result<String, SerializeThemeError> {
+    let mut v = Vec::new();
+    serialize_theme_to_with_options(&mut v, theme, options)?;
+
+    String::from_utf8(v).map_err(SerializeThemeError::Utf8)
+}
+
+pub fn serialize_theme_to(
+    writer: impl io::Write,
+    theme: &proto::Theme,
+) -> Result<(), SerializeThemeError> {
+    serialize_theme_to_with_options(writer, theme, &SerializeOptions::default())
+}
+
+pub fn serialize_theme_to_with_options(
+    mut writer: impl io::Write,
+    theme: &proto::Theme,
+    options: &SerializeOptions,
+) -> Result<(), SerializeThemeError> {
+    let canonical;
+    let theme = if options.canonical_order {
+        canonical = {
+            let mut theme = theme.clone();
+            theme.canonicalize();
+            theme
+        };
+        &canonical
+    } else {
+        theme
+    };
+
+    write_header(&mut writer, &options.header).map_err(SerializeThemeError::Io)?;
+    ser::write_theme(writer, theme, options)
+}
+
+fn write_header(writer: &mut impl io::Write, header: &Header) -> io::Result<()> {
+    match header {
+        Header::Generated => {
+            writer.write_all(b"// Do not edit directly; this file is generated.\n")
+        }
+        Header::Custom(text) => {
+            for line in text.lines() {
+                if line.is_empty() {
+                    writeln!(writer, "//")?;
+                } else {
+                    writeln!(writer, "// {line}")?;
+                }
+            }
+
+            Ok(())
+        }
+        Header::Omit => Ok(()),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SerializeOptions {
+    pub header: Header,
+    pub indent: Indent,
+    pub schema: bool,
+    pub scope_format: ScopeFormat,
+    pub compact_arrays: bool,
+    pub semantic_style_shorthand: bool,
+    pub canonical_order: bool,
+    pub rule_notes: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Header {
+    #[default]
+    Generated,
+    Custom(Cow<'static, str>),
+    Omit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Indent {
+    Spaces(usize),
+    Tabs,
+}
+
+impl Default for Indent {
+    fn default() -> Self {
+        Self::Spaces(4)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ScopeFormat {
+    #[default]
+    Array,
+    SingleAsString,
+    CommaSeparated,
+}
+
+#[cfg(feature = "std-fs")]
+fn prepare_themes_dir(themes_dir: &Path) -> Result<&Path, SaveThemeError> {
     if !themes_dir.exists() {
-        fs::create_dir(themes_dir).map_err(SaveThemeError::CreateThemesDir)?;
+        fs::create_dir_all(themes_dir)
+            .map_err(|e| SaveThemeError::CreateThemesDir(e, themes_dir.to_path_buf()))?;
     } else if !themes_dir.is_dir() {
-        return Err(SaveThemeError::ThemesDirIsNotDir);
+        return Err(SaveThemeError::ThemesDirIsNotDir(themes_dir.to_path_buf()));
     }
 
     Ok(themes_dir)
 }
 
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Serialize(#[from] SerializeThemeError),
+    #[error(transparent)]
+    Save(#[from] SaveThemeError),
+    #[cfg(feature = "import")]
+    #[error(transparent)]
+    Parse(#[from] parse::ParseThemeError),
+    #[cfg(feature = "import")]
+    #[error(transparent)]
+    Spec(#[from] spec::SpecError),
+    #[error(transparent)]
+    Manifest(#[from] manifest::ManifestError),
+    #[cfg(all(feature = "import", feature = "std-fs"))]
+    #[error(transparent)]
+    LoadPalette(#[from] palette::LoadPaletteError),
+    #[cfg(feature = "validate")]
+    #[error("theme JSON does not match the color theme schema ({} violations)", .0.len())]
+    Validation(Vec<validate::Violation>),
+    #[cfg(feature = "thumbnail")]
+    #[error(transparent)]
+    Thumbnail(#[from] thumbnail::ThumbnailError),
+    #[cfg(feature = "watch")]
+    #[error(transparent)]
+    Watch(#[from] WatchError),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum SerializeThemeError {
+    #[error("failed serializing theme to JSON")]
+    Json(#[source] serde_json::Error),
+    #[error("failed writing serialized theme")]
+    Io(#[source] io::Error),
+    #[error("serialized theme is not valid UTF-8")]
+    Utf8(#[source] FromUtf8Error),
+}
+
 #[derive(Debug, Error)]
 pub enum SaveThemeError {
-    #[error("failed creating `themes/` directory")]
-    CreateThemesDir(#[source] io::Error),
-    #[error("`themes/` already exists and is not a directory")]
-    ThemesDirIsNotDir,
+    #[error("failed creating `{1}` directory")]
+    CreateThemesDir(#[source] io::Error, PathBuf),
+    #[error("`{0}` already exists and is not a directory")]
+    ThemesDirIsNotDir(PathBuf),
+    #[error("theme name `{0}` cannot be used as a file name")]
+    InvalidThemeName(String),
+    #[error("themes `{0}` and `{1}` would both be saved to `{2}`")]
+    DuplicateThemeFile(String, String, PathBuf),
+    #[error("failed serializing theme")]
+    Serialize(#[source] SerializeThemeError),
+    #[error("failed reading directory `{1}`")]
+    ReadThemesDir(#[source] io::Error, PathBuf),
+    #[error("failed removing stale theme `{1}`")]
+    RemoveStaleTheme(#[source] io::Error, PathBuf),
+    #[error("failed reading existing theme at `{1}`")]
+    ReadExistingTheme(#[source] io::Error, PathBuf),
     #[error("failed writing theme to `{1}`")]
     WriteTheme(#[source] io::Error, PathBuf),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::ThemeBuilder;
+    use expect_test::expect;
+
+    #[cfg(feature = "std-fs")]
+    pub(crate) struct TempDir(PathBuf);
+
+    #[cfg(feature = "std-fs")]
+    impl std::ops::Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    #[cfg(feature = "std-fs")]
+    impl AsRef<Path> for TempDir {
+        fn as_ref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    #[cfg(feature = "std-fs")]
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[cfg(feature = "std-fs")]
+    pub(crate) fn temp_dir(name: &str) -> TempDir {
+        let dir = std::env::temp_dir().join(format!("mottle-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        TempDir(dir)
+    }
+
+    #[cfg(feature = "validate")]
+    #[test]
+    fn unified_error() {
+        fn round_trip(src: &str) -> Result<String, Error> {
+            let theme = parse::parse_theme(src)?;
+            validate::validate_theme(&theme)?;
+            Ok(serialize_theme(&theme)?)
+        }
+
+        assert!(round_trip(r#"{ "name": "My cool theme" }"#).is_ok());
+
+        let error = round_trip(r#"{ "colors": 1 }"#).unwrap_err();
+        assert!(matches!(error, Error::Parse(_)));
+        assert_eq!(error.to_string(), "invalid theme at `colors`: expected an object");
+    }
+
+    #[cfg(feature = "std-fs")]
+    #[test]
+    fn save_to_custom_dir() {
+        let root = temp_dir("save-to-custom-dir");
+        let dir = root.join("extension").join("themes");
+        let theme = ThemeBuilder::default().build("My cool theme");
+
+        assert_eq!(save_theme_to(&dir, &theme, FileNames::ThemeName).unwrap(), SaveStatus::Created);
+
+        assert_eq!(
+            fs::read_to_string(dir.join("My cool theme-color-theme.json")).unwrap(),
+            serialize_theme(&theme).unwrap()
+        );
+    }
+
+    #[cfg(feature = "std-fs")]
+    #[test]
+    fn skip_unchanged() {
+        let dir = temp_dir("skip-unchanged");
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x111111);
+        let theme = t.build("My cool theme");
+
+        assert_eq!(save_theme_to(&dir, &theme, FileNames::ThemeName).unwrap(), SaveStatus::Created);
+        assert_eq!(
+            save_theme_to(&dir, &theme, FileNames::ThemeName).unwrap(),
+            SaveStatus::Unchanged
+        );
+
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x222222);
+        assert_eq!(
+            save_theme_to(&dir, &t.build("My cool theme"), FileNames::ThemeName).unwrap(),
+            SaveStatus::Updated
+        );
+    }
+
+    #[cfg(feature = "std-fs")]
+    #[test]
+    fn crlf_checkout_is_unchanged() {
+        let dir = temp_dir("crlf-checkout-is-unchanged");
+        let theme = ThemeBuilder::default().build("My cool theme");
+
+        assert_eq!(save_theme_to(&dir, &theme, FileNames::ThemeName).unwrap(), SaveStatus::Created);
+
+        let path = dir.join("My cool theme-color-theme.json");
+        let crlf = fs::read_to_string(&path).unwrap().replace('\n', "\r\n");
+        fs::write(&path, crlf).unwrap();
+
+        assert_eq!(
+            save_theme_to(&dir, &theme, FileNames::ThemeName).unwrap(),
+            SaveStatus::Unchanged
+        );
+        assert!(plan_save_all_to(&dir, &[theme], StaleFiles::Remove, FileNames::ThemeName)
+            .unwrap()
+            .is_up_to_date());
+    }
+
+    #[test]
+    fn platform_independent_output() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x1E1E1E);
+        t.w(["editor.foreground"], (0xD4D4D4, 0x80));
+        let theme = t.build("Théme\r\n");
+
+        let options = SerializeOptions {
+            header: Header::Custom("First line\r\nSecond line".into()),
+            ..SerializeOptions::default()
+        };
+        let serialized = serialize_theme_with_options(&theme, &options).unwrap();
+
+        assert!(!serialized.contains('\r'));
+        expect![[r##"
+            // First line
+            // Second line
+            {
+                "name": "Théme\r\n",
+                "tokenColors": [],
+                "semanticHighlighting": true,
+                "semanticTokenColors": {},
+                "colors": {
+                    "editor.background": "#1E1E1E",
+                    "editor.foreground": "#D4D4D480"
+                }
+            }
+        "##]]
+        .assert_eq(&serialized);
+    }
+
+    #[cfg(feature = "std-fs")]
+    #[test]
+    fn save_all_removes_stale_files() {
+        let dir = temp_dir("save-all-removes-stale-files");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Old name-color-theme.json"), "").unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+
+        let themes = [
+            ThemeBuilder::default().build("My cool theme"),
+            ThemeBuilder::default().build("My cool theme (light)"),
+        ];
+
+        let report = save_all_to(&dir, &themes, StaleFiles::Remove, FileNames::ThemeName).unwrap();
+
+        assert_eq!(
+            report,
+            SaveAllReport {
+                saved: vec![
+                    (dir.join("My cool theme-color-theme.json"), SaveStatus::Created),
+                    (dir.join("My cool theme (light)-color-theme.json"), SaveStatus::Created),
+                ],
+                removed: vec![dir.join("Old name-color-theme.json")],
+            }
+        );
+        assert!(dir.join("notes.txt").exists());
+
+        let report =
+            save_all_to(&dir, &themes[..1], StaleFiles::Keep, FileNames::ThemeName).unwrap();
+        assert!(report.removed.is_empty());
+        assert!(dir.join("My cool theme (light)-color-theme.json").exists());
+    }
+
+    #[cfg(all(feature = "rayon", feature = "std-fs"))]
+    #[test]
+    fn generate_variants_in_parallel() {
+        let dir = temp_dir("generate-variants");
+        let variants = [("Dark", 0x1E1E1E), ("Dim", 0x2E2E2E), ("Light", 0xFFFFFF)];
+
+        let build = |&(name, background): &(&str, u32)| {
+            let mut t = ThemeBuilder::default();
+            t.w(["editor.background"], background);
+            t.build(format!("My cool theme {name}"))
+        };
+
+        let report = generate_variants_to(&dir, &variants, FileNames::Slug, build).unwrap();
+
+        assert_eq!(
+            report.saved,
+            [
+                (dir.join("my-cool-theme-dark-color-theme.json"), SaveStatus::Created),
+                (dir.join("my-cool-theme-dim-color-theme.json"), SaveStatus::Created),
+                (dir.join("my-cool-theme-light-color-theme.json"), SaveStatus::Created),
+            ]
+        );
+
+        let report = generate_variants_to(&dir, &variants, FileNames::Slug, build).unwrap();
+        assert!(report.saved.iter().all(|(_, status)| *status == SaveStatus::Unchanged));
+    }
+
+    #[cfg(all(feature = "rayon", feature = "std-fs"))]
+    #[test]
+    fn generate_variants_rejects_duplicate_file_names() {
+        let dir = temp_dir("generate-variants-rejects-duplicate-file-names");
+        let names = ["My Theme", "my-theme"];
+
+        let result = generate_variants_to(&dir, &names, FileNames::Slug, |name| {
+            ThemeBuilder::default().build(*name)
+        });
+
+        assert!(matches!(
+            result,
+            Err(SaveThemeError::DuplicateThemeFile(first, second, _))
+                if first == "My Theme" && second == "my-theme"
+        ));
+        assert!(!dir.join("my-theme-color-theme.json").exists());
+    }
+
+    #[cfg(feature = "std-fs")]
+    #[test]
+    fn plan_without_writing() {
+        let dir = temp_dir("plan-without-writing");
+        let theme = |foreground| {
+            let mut t = ThemeBuilder::default();
+            t.w(["editor.background"], 0x111111);
+            t.w(["editor.foreground"], foreground);
+            t.build("My cool theme")
+        };
+        save_theme_to(&dir, &theme(0xEEEEEE), FileNames::Slug).unwrap();
+        fs::write(dir.join("old-name-color-theme.json"), "").unwrap();
+
+        let themes = [theme(0xDDDDDD), ThemeBuilder::default().build("Light")];
+
+        let plan = plan_save_all_to(&dir, &themes, StaleFiles::Remove, FileNames::Slug).unwrap();
+        let actual = plan.to_string().replace(&dir.display().to_string(), "themes");
+
+        expect_test::expect![[r##"
+               update themes/my-cool-theme-color-theme.json
+               create themes/light-color-theme.json
+               remove themes/old-name-color-theme.json
+
+            --- themes/my-cool-theme-color-theme.json
+            +++ themes/my-cool-theme-color-theme.json
+            @@ -6,6 +6,6 @@
+                 "semanticTokenColors": {},
+                 "colors": {
+                     "editor.background": "#111111",
+            -        "editor.foreground": "#EEEEEE"
+            +        "editor.foreground": "#DDDDDD"
+                 }
+             }
+        "##]]
+        .assert_eq(&actual);
+
+        assert!(!plan.is_up_to_date());
+        assert!(!dir.join("light-color-theme.json").exists());
+        assert!(dir.join("old-name-color-theme.json").exists());
+
+        save_all_to(&dir, &themes, StaleFiles::Remove, FileNames::Slug).unwrap();
+        assert!(plan_save_all_to(&dir, &themes, StaleFiles::Remove, FileNames::Slug)
+            .unwrap()
+            .is_up_to_date());
+    }
+
+    #[cfg(feature = "std-fs")]
+    #[test]
+    fn save_all_rejects_duplicate_file_names() {
+        let dir = temp_dir("save-all-rejects-duplicate-file-names");
+        let themes =
+            [ThemeBuilder::default().build("My Theme"), ThemeBuilder::default().build("my-theme")];
+
+        let result = save_all_to(&dir, &themes, StaleFiles::Keep, FileNames::Slug);
+
+        assert!(matches!(
+            result,
+            Err(SaveThemeError::DuplicateThemeFile(first, second, path))
+                if first == "My Theme"
+                    && second == "my-theme"
+                    && path == dir.join("my-theme-color-theme.json")
+        ));
+        assert!(!dir.join("my-theme-color-theme.json").exists());
+    }
+
+    #[test]
+    fn slugify_theme_names() {
+        assert_eq!(slugify("My cool theme").as_deref(), Some("my-cool-theme"));
+        assert_eq!(slugify("  Solarized / Dark  ").as_deref(), Some("solarized-dark"));
+        assert_eq!(slugify("Ünïcödé Théme").as_deref(), Some("n-c-d-th-me"));
+        assert_eq!(slugify("../.."), None);
+        assert_eq!(slugify("日本語"), None);
+    }
+
+    #[test]
+    fn theme_file_names() {
+        let theme = ThemeBuilder::default().build("My cool theme (light)");
+
+        assert_eq!(
+            theme_file_name(&theme, FileNames::ThemeName).unwrap(),
+            "My cool theme (light)-color-theme.json"
+        );
+        assert_eq!(
+            theme_file_name(&theme, FileNames::Slug).unwrap(),
+            "my-cool-theme-light-color-theme.json"
+        );
+
+        let theme = ThemeBuilder::default().build("Dark/Light");
+        assert!(matches!(
+            theme_file_name(&theme, FileNames::ThemeName),
+            Err(SaveThemeError::InvalidThemeName(name)) if name == "Dark/Light"
+        ));
+        assert_eq!(
+            theme_file_name(&theme, FileNames::Slug).unwrap(),
+            "dark-light-color-theme.json"
+        );
+    }
+
+    #[cfg(feature = "std-fs")]
+    #[test]
+    fn invalid_theme_name() {
+        let dir = temp_dir("invalid-theme-name");
+        let result =
+            save_theme_to(&dir, &ThemeBuilder::default().build("///"), FileNames::ThemeName);
+
+        assert!(matches!(result, Err(SaveThemeError::InvalidThemeName(name)) if name == "///"));
+    }
+
+    #[cfg(feature = "std-fs")]
+    #[test]
+    fn save_to_file_path() {
+        let dir = temp_dir("save-to-file-path");
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("themes");
+        fs::write(&file, "").unwrap();
+
+        let result = save_theme_to(
+            &file,
+            &ThemeBuilder::default().build("My cool theme"),
+            FileNames::ThemeName,
+        );
+
+        assert!(matches!(result, Err(SaveThemeError::ThemesDirIsNotDir(path)) if path == file));
+    }
+}
@@ -0,0 +1,913 @@
+use crate::dsl::StyleRegistry;
+use crate::palette::Palette;
+use crate::proto::semantic;
+use crate::{proto, workbench};
+use indexmap::IndexMap;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt;
+
+pub trait Lint {
+    fn name(&self) -> &'static str;
+
+    fn check(&self, cx: &Context<'_>, reporter: &mut Reporter<'_>);
+}
+
+pub struct Context<'a> {
+    pub theme: &'a proto::Theme,
+    pub palette: Option<&'a Palette>,
+    pub presets: Option<&'a StyleRegistry>,
+}
+
+pub struct Reporter<'a> {
+    lint: &'static str,
+    diagnostics: &'a mut Vec<Diagnostic>,
+}
+
+impl Reporter<'_> {
+    pub fn warning(&mut self, location: Location, message: impl Into<String>) {
+        self.report(Severity::Warning, location, message.into());
+    }
+
+    pub fn error(&mut self, location: Location, message: impl Into<String>) {
+        self.report(Severity::Error, location, message.into());
+    }
+
+    fn report(&mut self, severity: Severity, location: Location, message: String) {
+        self.diagnostics.push(Diagnostic { lint: self.lint, severity, location, message });
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub lint: &'static str,
+    pub severity: Severity,
+    pub location: Location,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Location {
+    Theme,
+    TextMateRule { index: usize, scope: Option<String> },
+    SemanticRule(proto::semantic::Selector),
+    WorkbenchRule(Cow<'static, str>),
+    PaletteEntry(String),
+    StylePreset(String),
+    Variable(String),
+}
+
+pub struct ThemeLinter {
+    lints: Vec<Box<dyn Lint>>,
+    palette: Option<Palette>,
+    presets: Option<StyleRegistry>,
+}
+
+impl Default for ThemeLinter {
+    fn default() -> Self {
+        let mut linter = Self::empty();
+        linter.add(DuplicateRules);
+        linter.add(ConflictingRules);
+        linter.add(SemanticShadowing);
+        linter.add(SemanticTextMateMismatch);
+        linter.add(UnknownKeys);
+        linter.add(UnknownTokenTypes::default());
+        linter.add(AlphaUsage);
+        linter.add(PartialTerminalColors);
+        linter.add(LowContrast::default());
+        linter.add(UnusedPaletteEntries);
+        linter.add(UnusedStylePresets);
+
+        linter
+    }
+}
+
+impl ThemeLinter {
+    pub fn empty() -> Self {
+        Self { lints: Vec::new(), palette: None, presets: None }
+    }
+
+    pub fn add(&mut self, lint: impl Lint + 'static) {
+        self.lints.push(Box::new(lint));
+    }
+
+    pub fn palette(&mut self, palette: Palette) {
+        self.palette = Some(palette);
+    }
+
+    pub fn presets(&mut self, presets: StyleRegistry) {
+        self.presets = Some(presets);
+    }
+
+    pub fn lint(&self, theme: &proto::Theme) -> Vec<Diagnostic> {
+        let cx = Context { theme, palette: self.palette.as_ref(), presets: self.presets.as_ref() };
+        let mut diagnostics = Vec::new();
+
+        for lint in &self.lints {
+            lint.check(&cx, &mut Reporter { lint: lint.name(), diagnostics: &mut diagnostics });
+        }
+
+        diagnostics
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DuplicateRules;
+
+impl Lint for DuplicateRules {
+    fn name(&self) -> &'static str {
+        "duplicate-rules"
+    }
+
+    fn check(&self, cx: &Context<'_>, reporter: &mut Reporter<'_>) {
+        let mut seen: IndexMap<(&str, proto::textmate::RuleSettings), usize> = IndexMap::new();
+
+        for (index, rule) in cx.theme.textmate_rules.iter().enumerate() {
+            for scope in &rule.scope {
+                match seen.get(&(&**scope, rule.settings)) {
+                    Some(&first) if first == index => reporter.warning(
+                        Location::TextMateRule { index, scope: Some(scope.to_string()) },
+                        format!("scope `{scope}` is listed more than once in the same rule"),
+                    ),
+                    Some(&first) => reporter.warning(
+                        Location::TextMateRule { index, scope: Some(scope.to_string()) },
+                        format!(
+                            "scope `{scope}` is already given the same settings by rule {first}"
+                        ),
+                    ),
+                    None => {
+                        seen.insert((scope, rule.settings), index);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ConflictingRules;
+
+impl Lint for ConflictingRules {
+    fn name(&self) -> &'static str {
+        "conflicting-rules"
+    }
+
+    fn check(&self, cx: &Context<'_>, reporter: &mut Reporter<'_>) {
+        let mut last: IndexMap<&str, (usize, proto::textmate::RuleSettings)> = IndexMap::new();
+
+        for (index, rule) in cx.theme.textmate_rules.iter().enumerate() {
+            for scope in &rule.scope {
+                if let Some(&(previous, settings)) = last.get(&**scope) {
+                    if previous != index && settings != rule.settings {
+                        reporter.warning(
+                            Location::TextMateRule { index, scope: Some(scope.to_string()) },
+                            format!(
+                                "scope `{scope}` is given different settings by rule {previous}, which this rule overrides"
+                            ),
+                        );
+                    }
+                }
+
+                last.insert(scope, (index, rule.settings));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SemanticShadowing;
+
+impl Lint for SemanticShadowing {
+    fn name(&self) -> &'static str {
+        "semantic-shadowing"
+    }
+
+    fn check(&self, cx: &Context<'_>, reporter: &mut Reporter<'_>) {
+        let rules = match &cx.theme.semantic_highlighting {
+            proto::semantic::Highlighting::On { rules } => rules,
+            _ => return,
+        };
+
+        for (selector, style) in rules {
+            let fallback = rules
+                .iter()
+                .filter(|(other, _)| *other != selector && selector.refines(other))
+                .max_by_key(|(other, _)| other.specificity());
+
+            if let Some((fallback, fallback_style)) = fallback {
+                if fallback_style == style {
+                    reporter.warning(
+                        Location::SemanticRule(selector.clone()),
+                        format!("`{selector}` sets the same style as the less specific `{fallback}`, so it has no effect"),
+                    );
+                    continue;
+                }
+            }
+
+            for (other, other_style) in rules {
+                if other_style != style
+                    && other.specificity() > selector.specificity()
+                    && other.overlaps(selector)
+                    && !other.refines(selector)
+                {
+                    reporter.warning(
+                        Location::SemanticRule(selector.clone()),
+                        format!("`{selector}` is overridden by the more specific `{other}` for tokens matching both"),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SemanticTextMateMismatch;
+
+impl Lint for SemanticTextMateMismatch {
+    fn name(&self) -> &'static str {
+        "semantic-textmate-mismatch"
+    }
+
+    fn check(&self, cx: &Context<'_>, reporter: &mut Reporter<'_>) {
+        let rules = match &cx.theme.semantic_highlighting {
+            proto::semantic::Highlighting::On { rules } => rules,
+            _ => return,
+        };
+
+        for (selector, style) in rules {
+            let foreground = match style.foreground {
+                Some(foreground) => foreground,
+                None => continue,
+            };
+
+            let scopes = selector.textmate_scopes();
+            let counterpart = scopes.iter().find_map(|scope| {
+                proto::textmate::resolve_foreground(&cx.theme.textmate_rules, &[scope])
+                    .map(|color| (scope, color))
+            });
+
+            if let Some((scope, textmate)) = counterpart.filter(|(_, c)| *c != foreground) {
+                reporter.warning(
+                    Location::SemanticRule(selector.clone()),
+                    format!("`{selector}` is {foreground} but its TextMate counterpart `{scope}` is {textmate}, so highlighting flickers when semantic tokens arrive"),
+                );
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UnknownKeys;
+
+impl Lint for UnknownKeys {
+    fn name(&self) -> &'static str {
+        "unknown-keys"
+    }
+
+    fn check(&self, cx: &Context<'_>, reporter: &mut Reporter<'_>) {
+        for key in cx.theme.workbench_rules.keys() {
+            if !workbench::is_known_key(key) {
+                reporter.warning(
+                    Location::WorkbenchRule(key.clone()),
+                    format!("`{key}` is not a known workbench color"),
+                );
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnknownTokenTypes {
+    pub token_types: Vec<Cow<'static, str>>,
+    pub token_modifiers: Vec<Cow<'static, str>>,
+}
+
+impl Default for UnknownTokenTypes {
+    fn default() -> Self {
+        Self {
+            token_types: semantic::STANDARD_TOKEN_TYPES.iter().map(|t| Cow::Borrowed(*t)).collect(),
+            token_modifiers: semantic::STANDARD_TOKEN_MODIFIERS
+                .iter()
+                .map(|m| Cow::Borrowed(*m))
+                .collect(),
+        }
+    }
+}
+
+impl Lint for UnknownTokenTypes {
+    fn name(&self) -> &'static str {
+        "unknown-token-types"
+    }
+
+    fn check(&self, cx: &Context<'_>, reporter: &mut Reporter<'_>) {
+        let semantic::Highlighting::On { rules } = &cx.theme.semantic_highlighting else {
+            return;
+        };
+
+        for selector in rules.keys() {
+            if let semantic::TokenKind::Specific(kind) = &selector.kind {
+                if !self.token_types.iter().any(|t| t == kind.as_str()) {
+                    reporter.warning(
+                        Location::SemanticRule(selector.clone()),
+                        format!("`{}` is not a known semantic token type", kind.as_str()),
+                    );
+                }
+            }
+
+            for modifier in &selector.modifiers {
+                if !self.token_modifiers.iter().any(|m| m == modifier.as_str()) {
+                    reporter.warning(
+                        Location::SemanticRule(selector.clone()),
+                        format!("`{}` is not a known semantic token modifier", modifier.as_str()),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AlphaUsage;
+
+impl Lint for AlphaUsage {
+    fn name(&self) -> &'static str {
+        "alpha-usage"
+    }
+
+    fn check(&self, cx: &Context<'_>, reporter: &mut Reporter<'_>) {
+        for (key, color) in &cx.theme.workbench_rules {
+            let Some(color) = color else { continue };
+
+            match workbench::alpha_support(key) {
+                workbench::Alpha::Ignored if color.a != 0xFF => reporter.warning(
+                    Location::WorkbenchRule(key.clone()),
+                    format!("`{key}` ignores transparency, so the alpha channel of `{color}` has no effect"),
+                ),
+                workbench::Alpha::Required if color.a == 0xFF => reporter.warning(
+                    Location::WorkbenchRule(key.clone()),
+                    format!("`{key}` is drawn over other content, so the opaque `{color}` will hide it"),
+                ),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PartialTerminalColors;
+
+const TERMINAL_KEYS: &[&str] = &[
+    "terminal.foreground",
+    "terminal.background",
+    "terminal.ansiBlack",
+    "terminal.ansiRed",
+    "terminal.ansiGreen",
+    "terminal.ansiYellow",
+    "terminal.ansiBlue",
+    "terminal.ansiMagenta",
+    "terminal.ansiCyan",
+    "terminal.ansiWhite",
+    "terminal.ansiBrightBlack",
+    "terminal.ansiBrightRed",
+    "terminal.ansiBrightGreen",
+    "terminal.ansiBrightYellow",
+    "terminal.ansiBrightBlue",
+    "terminal.ansiBrightMagenta",
+    "terminal.ansiBrightCyan",
+    "terminal.ansiBrightWhite",
+];
+
+impl Lint for PartialTerminalColors {
+    fn name(&self) -> &'static str {
+        "partial-terminal-colors"
+    }
+
+    fn check(&self, cx: &Context<'_>, reporter: &mut Reporter<'_>) {
+        let missing: Vec<_> = TERMINAL_KEYS
+            .iter()
+            .filter(|key| !matches!(cx.theme.workbench_rules.get(**key), Some(Some(_))))
+            .map(|key| format!("`{key}`"))
+            .collect();
+
+        if !missing.is_empty() && missing.len() < TERMINAL_KEYS.len() {
+            reporter.warning(
+                Location::Theme,
+                format!(
+                    "only some terminal colors are set, so the rest fall back to defaults; missing {}",
+                    missing.join(", ")
+                ),
+            );
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LowContrast {
+    pub min_ratio: f64,
+}
+
+impl Default for LowContrast {
+    fn default() -> Self {
+        Self { min_ratio: 4.5 }
+    }
+}
+
+impl Lint for LowContrast {
+    fn name(&self) -> &'static str {
+        "low-contrast"
+    }
+
+    fn check(&self, cx: &Context<'_>, reporter: &mut Reporter<'_>) {
+        let workbench = |key| cx.theme.workbench_rules.get(key).copied().flatten();
+        let defaults = cx.theme.global_token_defaults.as_ref();
+
+        let background = match defaults
+            .and_then(|defaults| defaults.background)
+            .or_else(|| workbench("editor.background"))
+        {
+            Some(background) => background,
+            None => return,
+        };
+        let default_foreground = defaults
+            .and_then(|defaults| defaults.foreground)
+            .or_else(|| workbench("editor.foreground"));
+
+        let mut check = |location: Location, foreground: Option<proto::Color>| {
+            let foreground = match foreground.or(default_foreground) {
+                Some(foreground) => foreground,
+                None => return,
+            };
+            let ratio = foreground.over(background).contrast_ratio(background);
+
+            if ratio < self.min_ratio {
+                reporter.warning(
+                    location,
+                    format!(
+                        "contrast ratio {ratio:.2}:1 against the editor background is below {}:1",
+                        self.min_ratio
+                    ),
+                );
+            }
+        };
+
+        if let Some(foreground) = workbench("editor.foreground") {
+            check(Location::WorkbenchRule(Cow::Borrowed("editor.foreground")), Some(foreground));
+        }
+
+        for (index, rule) in cx.theme.textmate_rules.iter().enumerate() {
+            check(Location::TextMateRule { index, scope: None }, rule.settings.foreground);
+        }
+
+        if let proto::semantic::Highlighting::On { rules } = &cx.theme.semantic_highlighting {
+            for (selector, style) in rules {
+                check(Location::SemanticRule(selector.clone()), style.foreground);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UnusedPaletteEntries;
+
+impl Lint for UnusedPaletteEntries {
+    fn name(&self) -> &'static str {
+        "unused-palette-entries"
+    }
+
+    fn check(&self, cx: &Context<'_>, reporter: &mut Reporter<'_>) {
+        let palette = match cx.palette {
+            Some(palette) => palette,
+            None => return,
+        };
+
+        let mut used = HashSet::new();
+
+        for color in cx.theme.workbench_rules.values().flatten() {
+            used.insert((color.r, color.g, color.b));
+        }
+
+        for color in cx.theme.textmate_rules.iter().filter_map(|r| r.settings.foreground) {
+            used.insert((color.r, color.g, color.b));
+        }
+
+        if let proto::semantic::Highlighting::On { rules } = &cx.theme.semantic_highlighting {
+            for color in rules.values().filter_map(|s| s.foreground) {
+                used.insert((color.r, color.g, color.b));
+            }
+        }
+
+        for (name, color) in palette.iter() {
+            if !used.contains(&(color.r, color.g, color.b)) {
+                reporter.warning(
+                    Location::PaletteEntry(name.to_string()),
+                    format!("palette entry `{name}` is not used by any rule"),
+                );
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UnusedStylePresets;
+
+impl Lint for UnusedStylePresets {
+    fn name(&self) -> &'static str {
+        "unused-style-presets"
+    }
+
+    fn check(&self, cx: &Context<'_>, reporter: &mut Reporter<'_>) {
+        let presets = match cx.presets {
+            Some(presets) => presets,
+            None => return,
+        };
+
+        for name in presets.unused() {
+            reporter.warning(
+                Location::StylePreset(name.to_string()),
+                format!("style preset `{name}` is never used"),
+            );
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+
+        write!(f, "{severity}[{}]: {} ({})", self.lint, self.message, self.location)
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Theme => write!(f, "theme"),
+            Self::TextMateRule { index, scope: Some(scope) } => {
+                write!(f, "textmate rule {index}, scope `{scope}`")
+            }
+            Self::TextMateRule { index, scope: None } => write!(f, "textmate rule {index}"),
+            Self::SemanticRule(selector) => write!(f, "semantic rule `{selector}`"),
+            Self::WorkbenchRule(key) => write!(f, "workbench color `{key}`"),
+            Self::PaletteEntry(name) => write!(f, "palette entry `{name}`"),
+            Self::StylePreset(name) => write!(f, "style preset `{name}`"),
+            Self::Variable(name) => write!(f, "color variable `{name}`"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{s, tm, FontStyle, ThemeBuilder};
+    use expect_test::{expect, Expect};
+
+    fn check(linter: &ThemeLinter, t: ThemeBuilder, expect: Expect) {
+        let diagnostics = linter.lint(&t.build("My cool theme"));
+        let actual: String = diagnostics.iter().map(|d| format!("{d}\n")).collect();
+        expect.assert_eq(&actual);
+    }
+
+    #[test]
+    fn clean_theme() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x000000);
+        t.a([tm("keyword"), s("keyword")], 0xFFFFFF);
+
+        check(&ThemeLinter::default(), t, expect![[""]]);
+    }
+
+    #[test]
+    fn duplicate_rules() {
+        let mut t = ThemeBuilder::default();
+        t.a([tm("keyword"), tm("storage"), tm("keyword")], 0xFF0000);
+        t.a([tm("storage")], 0xFF0000);
+        t.a([tm("keyword")], 0x00FF00);
+
+        check(
+            &ThemeLinter::default(),
+            t,
+            expect![[r#"
+                warning[duplicate-rules]: scope `keyword` is listed more than once in the same rule (textmate rule 0, scope `keyword`)
+                warning[duplicate-rules]: scope `storage` is already given the same settings by rule 0 (textmate rule 1, scope `storage`)
+                warning[conflicting-rules]: scope `keyword` is given different settings by rule 0, which this rule overrides (textmate rule 2, scope `keyword`)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn conflicting_rules() {
+        let mut linter = ThemeLinter::empty();
+        linter.add(ConflictingRules);
+
+        let mut t = ThemeBuilder::default();
+        t.a([tm("string.quoted"), tm("string")], 0xCE9178);
+        t.a([tm("keyword")], 0x569CD6);
+        t.a([tm("string.quoted")], 0xD69D85);
+        t.a([tm("keyword")], 0x569CD6);
+
+        check(
+            &linter,
+            t,
+            expect![[r#"
+                warning[conflicting-rules]: scope `string.quoted` is given different settings by rule 0, which this rule overrides (textmate rule 2, scope `string.quoted`)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn unknown_keys() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.foreground", "editor.foregroud"], 0xFFFFFF);
+
+        check(
+            &ThemeLinter::default(),
+            t,
+            expect![[r#"
+                warning[unknown-keys]: `editor.foregroud` is not a known workbench color (workbench color `editor.foregroud`)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn unknown_token_types() {
+        let mut t = ThemeBuilder::default();
+        t.a([s("function.defaultLibrary"), s("lifetime"), s("variable.mutable")], 0xFFFFFF);
+
+        check(
+            &ThemeLinter::default(),
+            t,
+            expect![[r#"
+                warning[unknown-token-types]: `lifetime` is not a known semantic token type (semantic rule `lifetime`)
+                warning[unknown-token-types]: `mutable` is not a known semantic token modifier (semantic rule `variable.mutable`)
+            "#]],
+        );
+
+        let mut linter = ThemeLinter::empty();
+        linter.add(UnknownTokenTypes {
+            token_types: vec!["lifetime".into()],
+            token_modifiers: vec!["mutable".into()],
+        });
+
+        let mut t = ThemeBuilder::default();
+        t.a([s("lifetime.mutable"), s("function")], 0xFFFFFF);
+
+        check(
+            &linter,
+            t,
+            expect![[r#"
+                warning[unknown-token-types]: `function` is not a known semantic token type (semantic rule `function`)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn alpha_usage() {
+        let mut linter = ThemeLinter::empty();
+        linter.add(AlphaUsage);
+
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], (0x1E1E1E, 0x80));
+        t.w(["editor.selectionHighlightBackground"], 0xADD6FF);
+        t.w(["terminal.selectionBackground"], (0xADD6FF, 0x40));
+        t.w(["editor.foreground"], (0xD4D4D4, 0x80));
+
+        check(
+            &linter,
+            t,
+            expect![[r#"
+                warning[alpha-usage]: `editor.background` ignores transparency, so the alpha channel of `#1E1E1E80` has no effect (workbench color `editor.background`)
+                warning[alpha-usage]: `editor.selectionHighlightBackground` is drawn over other content, so the opaque `#ADD6FF` will hide it (workbench color `editor.selectionHighlightBackground`)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn partial_terminal_colors() {
+        let mut linter = ThemeLinter::empty();
+        linter.add(PartialTerminalColors);
+
+        let mut t = ThemeBuilder::default();
+        t.w(["terminal.foreground"], 0xCCCCCC);
+        t.w(["terminal.background"], 0x1E1E1E);
+        t.w(["terminal.ansiBlack", "terminal.ansiBrightBlack"], 0x000000);
+        t.w(["terminal.ansiRed", "terminal.ansiBrightRed"], 0xCD3131);
+        t.w(["terminal.ansiGreen", "terminal.ansiBrightGreen"], 0x0DBC79);
+        t.w(["terminal.ansiYellow", "terminal.ansiBrightYellow"], 0xE5E510);
+        t.w(["terminal.ansiBlue", "terminal.ansiBrightBlue"], 0x2472C8);
+        t.w(["terminal.ansiMagenta", "terminal.ansiBrightMagenta"], 0xBC3FBC);
+        t.w(["terminal.ansiCyan", "terminal.ansiBrightCyan"], 0x11A8CD);
+        t.w(["terminal.ansiWhite"], 0xE5E5E5);
+        t.unset(["terminal.ansiBrightWhite"]);
+
+        check(
+            &linter,
+            t,
+            expect![[r#"
+                warning[partial-terminal-colors]: only some terminal colors are set, so the rest fall back to defaults; missing `terminal.ansiBrightWhite` (theme)
+            "#]],
+        );
+        check(&linter, ThemeBuilder::default(), expect![[""]]);
+    }
+
+    #[test]
+    fn low_contrast() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x1E1E1E);
+        t.w(["editor.foreground"], 0xD4D4D4);
+        t.a([tm("comment")], 0x3A3A3A);
+        t.a([s("*.deprecated")], (0xFFFFFF, 0x20));
+
+        check(
+            &ThemeLinter::default(),
+            t,
+            expect![[r#"
+                warning[low-contrast]: contrast ratio 1.47:1 against the editor background is below 4.5:1 (textmate rule 0)
+                warning[low-contrast]: contrast ratio 1.47:1 against the editor background is below 4.5:1 (semantic rule `*.deprecated`)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn low_contrast_default_foreground() {
+        let mut t = ThemeBuilder::default();
+        t.global_token_defaults(0x505050, 0x1E1E1E);
+        t.w(["editor.background"], 0x1E1E1E);
+        t.w(["editor.foreground"], 0xD4D4D4);
+        t.a([tm("markup.bold")], FontStyle::Bold);
+        t.a([tm("keyword"), s("keyword")], 0x569CD6);
+        t.a([s("*.mutable")], FontStyle::Underline);
+
+        let mut linter = ThemeLinter::empty();
+        linter.add(LowContrast::default());
+        check(
+            &linter,
+            t,
+            expect![[r#"
+                warning[low-contrast]: contrast ratio 2.07:1 against the editor background is below 4.5:1 (textmate rule 0)
+                warning[low-contrast]: contrast ratio 2.07:1 against the editor background is below 4.5:1 (semantic rule `*.mutable`)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn unused_palette_entries() {
+        let mut palette = Palette::new();
+        palette.insert("red", 0xFF0000);
+        palette.insert("green", 0x00FF00);
+        palette.insert("blue", 0x0000FF);
+
+        let mut linter = ThemeLinter::empty();
+        linter.add(UnusedPaletteEntries);
+        linter.palette(palette);
+
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.selectionBackground"], (0x0000FF, 0x40));
+        t.a([tm("keyword")], 0xFF0000);
+
+        check(
+            &linter,
+            t,
+            expect![[r#"
+                warning[unused-palette-entries]: palette entry `green` is not used by any rule (palette entry `green`)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn custom_lint() {
+        struct NoRed;
+
+        impl Lint for NoRed {
+            fn name(&self) -> &'static str {
+                "no-red"
+            }
+
+            fn check(&self, cx: &Context<'_>, reporter: &mut Reporter<'_>) {
+                for (key, color) in &cx.theme.workbench_rules {
+                    if *color == Some(proto::Color { r: 0xFF, g: 0, b: 0, a: 0xFF }) {
+                        reporter.error(Location::WorkbenchRule(key.clone()), "red is banned");
+                    }
+                }
+            }
+        }
+
+        let mut linter = ThemeLinter::empty();
+        linter.add(NoRed);
+
+        let mut t = ThemeBuilder::default();
+        t.w(["errorForeground"], 0xFF0000);
+
+        check(
+            &linter,
+            t,
+            expect![[r#"
+                error[no-red]: red is banned (workbench color `errorForeground`)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn unused_style_presets() {
+        let mut presets = StyleRegistry::new();
+        presets.define("accent", 0x569CD6);
+        presets.define("emphasis", presets.get("accent") + FontStyle::Italic);
+        presets.define("strong", presets.get("accent") + FontStyle::Bold);
+
+        let mut t = ThemeBuilder::default();
+        t.a([tm("keyword")], presets.get("accent"));
+        t.a([s("*.documentation")], presets.get("emphasis"));
+
+        let mut linter = ThemeLinter::empty();
+        linter.add(UnusedStylePresets);
+        linter.presets(presets);
+
+        check(
+            &linter,
+            t,
+            expect![[r#"
+                warning[unused-style-presets]: style preset `strong` is never used (style preset `strong`)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn unused_style_presets_by_lookup() {
+        let mut presets = StyleRegistry::new();
+        presets.define("base", 0x569CD6);
+        presets.define("keyword", 0x569CD6);
+        presets.define("control", 0x569CD6);
+        presets.define("variable", crate::dsl::var("accent"));
+
+        let mut t = ThemeBuilder::default();
+        t.define("accent", 0xC586C0);
+        t.a([tm("markup.italic")], presets.get("base") + FontStyle::Italic);
+        t.a([tm("keyword")], presets.get("keyword"));
+        t.a([tm("variable")], presets.get("variable"));
+
+        let mut linter = ThemeLinter::empty();
+        linter.add(UnusedStylePresets);
+        linter.presets(presets);
+
+        check(
+            &linter,
+            t,
+            expect![[r#"
+                warning[unused-style-presets]: style preset `control` is never used (style preset `control`)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn semantic_shadowing() {
+        let mut linter = ThemeLinter::empty();
+        linter.add(SemanticShadowing);
+
+        let mut t = ThemeBuilder::default();
+        t.a([s("variable")], 0x9CDCFE);
+        t.a([s("variable.readonly")], 0x4FC1FF);
+        t.a([s("variable.defaultLibrary")], 0x9CDCFE);
+        t.a([s("*:rust")], 0xD4D4D4);
+        t.a([s("function.readonly:rust")], 0xDCDCAA);
+
+        check(
+            &linter,
+            t,
+            expect![[r#"
+                warning[semantic-shadowing]: `variable` is overridden by the more specific `*:rust` for tokens matching both (semantic rule `variable`)
+                warning[semantic-shadowing]: `variable.readonly` is overridden by the more specific `*:rust` for tokens matching both (semantic rule `variable.readonly`)
+                warning[semantic-shadowing]: `variable.defaultLibrary` sets the same style as the less specific `variable`, so it has no effect (semantic rule `variable.defaultLibrary`)
+            "#]],
+        );
+    }
+
+    #[test]
+    fn semantic_textmate_mismatch() {
+        let mut linter = ThemeLinter::empty();
+        linter.add(SemanticTextMateMismatch);
+
+        let mut t = ThemeBuilder::default();
+        t.a([tm("entity.name.function"), s("function")], 0xDCDCAA);
+        t.a([tm("support.function")], 0xC8C8C8);
+        t.a([tm("variable"), tm("variable.other.constant")], 0x9CDCFE);
+        t.a([s("variable.readonly")], 0x4FC1FF);
+        t.a([s("method:rust")], 0xDCDCAA);
+        t.a([s("namespace")], 0x4EC9B0);
+
+        check(
+            &linter,
+            t,
+            expect![[r#"
+                warning[semantic-textmate-mismatch]: `variable.readonly` is #4FC1FF but its TextMate counterpart `variable.other.constant` is #9CDCFE, so highlighting flickers when semantic tokens arrive (semantic rule `variable.readonly`)
+            "#]],
+        );
+    }
+}
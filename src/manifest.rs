@@ -0,0 +1,259 @@
+use crate::proto::{self, ThemeType};
+use crate::{
+    ser, theme_file_name, FileNames, Indent, SaveThemeError, SerializeOptions, SerializeThemeError,
+};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+
+#[derive(Debug, Clone)]
+pub struct Manifest {
+    pub name: String,
+    pub display_name: String,
+    pub publisher: String,
+    pub version: String,
+    pub vscode_engine: String,
+    pub themes_dir: String,
+    pub file_names: FileNames,
+}
+
+impl Manifest {
+    pub fn new(name: impl Into<String>, publisher: impl Into<String>) -> Self {
+        let name = name.into();
+
+        Self {
+            display_name: name.clone(),
+            name,
+            publisher: publisher.into(),
+            version: "0.1.0".to_string(),
+            vscode_engine: "^1.60.0".to_string(),
+            themes_dir: "./themes".to_string(),
+            file_names: FileNames::ThemeName,
+        }
+    }
+
+    pub fn generate(&self, themes: &[proto::Theme]) -> Result<String, ManifestError> {
+        let package = json!({
+            "name": self.name,
+            "displayName": self.display_name,
+            "version": self.version,
+            "publisher": self.publisher,
+            "engines": { "vscode": self.vscode_engine },
+            "categories": ["Themes"],
+            "contributes": { "themes": contributions(themes, &self.themes_dir, self.file_names)? },
+        });
+
+        to_string(&package, Indent::Spaces(2))
+    }
+
+    pub fn patch(
+        &self,
+        package_json: &str,
+        themes: &[proto::Theme],
+    ) -> Result<String, ManifestError> {
+        let mut package: Value =
+            serde_json::from_str(package_json).map_err(ManifestError::Parse)?;
+        let package = package.as_object_mut().ok_or(ManifestError::NotAnObject("package.json"))?;
+
+        let engines = object_entry(package, "engines")?;
+        engines.entry("vscode").or_insert_with(|| self.vscode_engine.clone().into());
+
+        let categories = package.entry("categories").or_insert_with(|| Value::Array(Vec::new()));
+        match categories {
+            Value::Array(categories) => {
+                if !categories.iter().any(|c| c == "Themes") {
+                    categories.push("Themes".into());
+                }
+            }
+            _ => return Err(ManifestError::NotAnArray("categories")),
+        }
+
+        let contributes = object_entry(package, "contributes")?;
+        let contributions = contributions(themes, &self.themes_dir, self.file_names)?;
+        contributes.insert(
+            "themes".to_string(),
+            serde_json::to_value(contributions).map_err(ManifestError::Parse)?,
+        );
+
+        to_string(&package, detect_indent(package_json))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeContribution {
+    pub label: String,
+    pub ui_theme: &'static str,
+    pub path: String,
+}
+
+pub fn contributions(
+    themes: &[proto::Theme],
+    themes_dir: &str,
+    file_names: FileNames,
+) -> Result<Vec<ThemeContribution>, ManifestError> {
+    themes
+        .iter()
+        .map(|theme| {
+            let ui_theme = match theme.theme_type {
+                Some(ThemeType::Light) => "vs",
+                Some(ThemeType::Dark) => "vs-dark",
+                Some(ThemeType::HighContrast) => "hc-black",
+                Some(ThemeType::HighContrastLight) => "hc-light",
+                None => return Err(ManifestError::MissingThemeType(theme.name.clone())),
+            };
+
+            let file_name = theme_file_name(theme, file_names).map_err(ManifestError::FileName)?;
+
+            Ok(ThemeContribution {
+                label: theme.name.clone(),
+                ui_theme,
+                path: format!("{}/{file_name}", themes_dir.trim_end_matches('/')),
+            })
+        })
+        .collect()
+}
+
+fn object_entry<'a>(
+    object: &'a mut Map<String, Value>,
+    key: &'static str,
+) -> Result<&'a mut Map<String, Value>, ManifestError> {
+    object
+        .entry(key)
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .ok_or(ManifestError::NotAnObject(key))
+}
+
+fn detect_indent(json: &str) -> Indent {
+    let indent = json
+        .lines()
+        .skip(1)
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .find(|indent| !indent.is_empty());
+
+    match indent {
+        Some(indent) if indent.starts_with('\t') => Indent::Tabs,
+        Some(indent) => Indent::Spaces(indent.len()),
+        None => Indent::Spaces(2),
+    }
+}
+
+fn to_string(value: &impl Serialize, indent: Indent) -> Result<String, ManifestError> {
+    let options = SerializeOptions { indent, compact_arrays: true, ..SerializeOptions::default() };
+    ser::to_string(value, &options).map_err(ManifestError::Serialize)
+}
+
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    #[error("failed parsing `package.json`")]
+    Parse(#[source] serde_json::Error),
+    #[error("`{0}` in `package.json` is not an object")]
+    NotAnObject(&'static str),
+    #[error("`{0}` in `package.json` is not an array")]
+    NotAnArray(&'static str),
+    #[error("theme `{0}` has no type, so its `uiTheme` cannot be determined")]
+    MissingThemeType(String),
+    #[error("failed determining theme file name")]
+    FileName(#[source] SaveThemeError),
+    #[error("failed serializing `package.json`")]
+    Serialize(#[source] SerializeThemeError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::ThemeBuilder;
+    use expect_test::expect;
+
+    fn themes() -> Vec<proto::Theme> {
+        vec![
+            ThemeBuilder::default().build_with_type("My cool theme", ThemeType::Dark),
+            ThemeBuilder::default().build_with_type("My cool theme (light)", ThemeType::Light),
+        ]
+    }
+
+    #[test]
+    fn generate() {
+        let manifest = Manifest::new("my-cool-theme", "me");
+
+        expect![[r#"
+            {
+              "name": "my-cool-theme",
+              "displayName": "my-cool-theme",
+              "version": "0.1.0",
+              "publisher": "me",
+              "engines": {
+                "vscode": "^1.60.0"
+              },
+              "categories": ["Themes"],
+              "contributes": {
+                "themes": [
+                  {
+                    "label": "My cool theme",
+                    "uiTheme": "vs-dark",
+                    "path": "./themes/My cool theme-color-theme.json"
+                  },
+                  {
+                    "label": "My cool theme (light)",
+                    "uiTheme": "vs",
+                    "path": "./themes/My cool theme (light)-color-theme.json"
+                  }
+                ]
+              }
+            }
+        "#]]
+        .assert_eq(&manifest.generate(&themes()).unwrap());
+    }
+
+    #[test]
+    fn patch() {
+        let manifest = Manifest::new("my-cool-theme", "me");
+        let existing = r#"{
+	"name": "my-cool-theme",
+	"scripts": { "zzz": "run", "aaa": "build" },
+	"categories": ["Other"],
+	"engines": { "vscode": "^1.80.0" },
+	"contributes": { "themes": [] }
+}"#;
+
+        expect![[r#"
+            {
+            	"name": "my-cool-theme",
+            	"scripts": {
+            		"zzz": "run",
+            		"aaa": "build"
+            	},
+            	"categories": ["Other", "Themes"],
+            	"engines": {
+            		"vscode": "^1.80.0"
+            	},
+            	"contributes": {
+            		"themes": [
+            			{
+            				"label": "My cool theme",
+            				"uiTheme": "vs-dark",
+            				"path": "./themes/My cool theme-color-theme.json"
+            			},
+            			{
+            				"label": "My cool theme (light)",
+            				"uiTheme": "vs",
+            				"path": "./themes/My cool theme (light)-color-theme.json"
+            			}
+            		]
+            	}
+            }
+        "#]]
+        .assert_eq(&manifest.patch(existing, &themes()).unwrap());
+    }
+
+    #[test]
+    fn missing_theme_type() {
+        let manifest = Manifest::new("my-cool-theme", "me");
+        let result = manifest.generate(&[ThemeBuilder::default().build("My cool theme")]);
+
+        assert!(
+            matches!(result, Err(ManifestError::MissingThemeType(name)) if name == "My cool theme")
+        );
+    }
+}
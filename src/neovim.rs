@@ -0,0 +1,138 @@
+use crate::captures::{CaptureMap, CaptureStyle};
+use crate::proto::{self, semantic};
+use std::fmt::Write;
+
+pub fn colorscheme(theme: &proto::Theme) -> String {
+    colorscheme_with_captures(theme, &CaptureMap::default())
+}
+
+pub fn colorscheme_with_captures(theme: &proto::Theme, captures: &CaptureMap) -> String {
+    let mut out = String::new();
+    writeln!(out, "-- Do not edit directly; this file is generated.").unwrap();
+    writeln!(out, "vim.cmd(\"highlight clear\")").unwrap();
+    if let Some(name) = crate::slugify(&theme.name) {
+        writeln!(out, "vim.g.colors_name = {name:?}").unwrap();
+    }
+    writeln!(out, "local set = vim.api.nvim_set_hl").unwrap();
+
+    let defaults = theme.global_token_defaults.unwrap_or_default();
+    let color = |key: &str| theme.workbench_rules.get(key).copied().flatten();
+    let foreground = color("editor.foreground").or(defaults.foreground);
+    let background = color("editor.background").or(defaults.background);
+
+    let mut normal = Vec::new();
+    normal.extend(foreground.map(|c| format!("fg = \"{c}\"")));
+    normal.extend(background.map(|c| format!("bg = \"{c}\"")));
+    if !normal.is_empty() {
+        set(&mut out, "Normal", &normal);
+    }
+
+    for (capture, style) in captures.resolve(theme) {
+        set(&mut out, &format!("@{capture}"), &capture_attributes(style));
+    }
+
+    if let semantic::Highlighting::On { rules } = &theme.semantic_highlighting {
+        for (selector, style) in rules {
+            if let Some(group) = lsp_group(selector) {
+                set(&mut out, &group, &semantic_attributes(style));
+            }
+        }
+    }
+
+    out
+}
+
+fn lsp_group(selector: &semantic::Selector) -> Option<String> {
+    let mut group = match (&selector.kind, selector.modifiers.as_slice()) {
+        (semantic::TokenKind::Specific(kind), []) => format!("@lsp.type.{}", kind.as_str()),
+        (semantic::TokenKind::Wildcard, [modifier]) => format!("@lsp.mod.{}", modifier.as_str()),
+        (semantic::TokenKind::Specific(kind), [modifier]) => {
+            format!("@lsp.typemod.{}.{}", kind.as_str(), modifier.as_str())
+        }
+        _ => return None,
+    };
+
+    if let Some(language) = &selector.language {
+        group.push('.');
+        group.push_str(language.as_str());
+    }
+
+    Some(group)
+}
+
+fn capture_attributes(style: CaptureStyle) -> Vec<String> {
+    let mut attributes = Vec::new();
+    attributes.extend(style.foreground.map(|c| format!("fg = \"{c}\"")));
+
+    for (set, name) in
+        [(style.bold, "bold"), (style.italic, "italic"), (style.underline, "underline")]
+    {
+        if set {
+            attributes.push(format!("{name} = true"));
+        }
+    }
+
+    attributes
+}
+
+fn semantic_attributes(style: &semantic::Style) -> Vec<String> {
+    let mut attributes = Vec::new();
+    attributes.extend(style.foreground.map(|c| format!("fg = \"{c}\"")));
+
+    let font_style = style.font_style;
+    for (setting, name) in [
+        (font_style.bold, "bold"),
+        (font_style.italic, "italic"),
+        (font_style.underline, "underline"),
+    ] {
+        match setting {
+            semantic::FontStyleSetting::True => attributes.push(format!("{name} = true")),
+            semantic::FontStyleSetting::False => attributes.push(format!("{name} = false")),
+            semantic::FontStyleSetting::Inherit => {}
+        }
+    }
+
+    attributes
+}
+
+fn set(out: &mut String, group: &str, attributes: &[String]) {
+    writeln!(out, "set(0, {group:?}, {{ {} }})", attributes.join(", ")).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{s, tm, FontStyle, ThemeBuilder};
+    use expect_test::expect;
+
+    #[test]
+    fn lsp_groups() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x1E1E1E);
+        t.w(["editor.foreground"], 0xD4D4D4);
+        t.a([tm("keyword"), s("keyword")], 0x569CD6);
+        t.a([s("*.deprecated")], FontStyle::Underline);
+        t.a([s("variable.readonly")], 0x4FC1FF);
+        t.a([s("variable.constant:rust")], 0x4FC1FF);
+        t.a([s("*.documentation:rust")], FontStyle::Clear);
+        t.a([s("variable.declaration.static")], 0x9CDCFE);
+        t.a([s("*")], 0xD4D4D4);
+
+        expect![[r##"
+            -- Do not edit directly; this file is generated.
+            vim.cmd("highlight clear")
+            vim.g.colors_name = "my-cool-theme"
+            local set = vim.api.nvim_set_hl
+            set(0, "Normal", { fg = "#D4D4D4", bg = "#1E1E1E" })
+            set(0, "@constant", { fg = "#4FC1FF" })
+            set(0, "@keyword", { fg = "#569CD6" })
+            set(0, "@keyword.storage", { fg = "#569CD6" })
+            set(0, "@operator", { fg = "#569CD6" })
+            set(0, "@lsp.type.keyword", { fg = "#569CD6" })
+            set(0, "@lsp.mod.deprecated", { underline = true })
+            set(0, "@lsp.typemod.variable.readonly", { fg = "#4FC1FF" })
+            set(0, "@lsp.typemod.variable.constant.rust", { fg = "#4FC1FF" })
+            set(0, "@lsp.mod.documentation.rust", { bold = false, italic = false, underline = false })
+        "##]].assert_eq(&colorscheme(&t.build("My cool theme")));
+    }
+}
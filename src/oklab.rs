@@ -0,0 +1,175 @@
+use crate::proto;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    pub l: f64,
+    pub c: f64,
+    pub h: f64,
+}
+
+impl Oklab {
+    pub fn to_color(self, alpha: u8) -> proto::Color {
+        let (r, g, b) = self.to_linear_srgb();
+        let encode = |c: f64| (gamma(c.clamp(0.0, 1.0)) * 255.0).round() as u8;
+
+        proto::Color { r: encode(r), g: encode(g), b: encode(b), a: alpha }
+    }
+
+    pub fn is_in_gamut(self) -> bool {
+        const EPSILON: f64 = 1e-6;
+        let (r, g, b) = self.to_linear_srgb();
+
+        [r, g, b].iter().all(|c| (-EPSILON..=1.0 + EPSILON).contains(c))
+    }
+
+    fn to_linear_srgb(self) -> (f64, f64, f64) {
+        let l = (self.l + 0.3963377774 * self.a + 0.2158037573 * self.b).powi(3);
+        let m = (self.l - 0.1055613458 * self.a - 0.0638541728 * self.b).powi(3);
+        let s = (self.l - 0.0894841775 * self.a - 1.2914855480 * self.b).powi(3);
+
+        (
+            4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+            -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+            -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+        )
+    }
+}
+
+impl Oklch {
+    pub fn to_color(self, alpha: u8) -> proto::Color {
+        Oklab::from(self.clamp_chroma()).to_color(alpha)
+    }
+
+    pub fn clamp_chroma(self) -> Self {
+        let l = self.l.clamp(0.0, 1.0);
+        if Oklab::from(Self { l, ..self }).is_in_gamut() {
+            return Self { l, ..self };
+        }
+
+        let (mut low, mut high) = (0.0, self.c);
+        for _ in 0..32 {
+            let mid = (low + high) / 2.0;
+            if Oklab::from(Self { l, c: mid, h: self.h }).is_in_gamut() {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        Self { l, c: low, h: self.h }
+    }
+}
+
+impl From<proto::Color> for Oklab {
+    fn from(color: proto::Color) -> Self {
+        let r = linearize(color.r);
+        let g = linearize(color.g);
+        let b = linearize(color.b);
+
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        Self {
+            l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        }
+    }
+}
+
+impl From<proto::Color> for Oklch {
+    fn from(color: proto::Color) -> Self {
+        Oklab::from(color).into()
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(Oklab { l, a, b }: Oklab) -> Self {
+        let h = b.atan2(a).to_degrees().rem_euclid(360.0);
+        Self { l, c: a.hypot(b), h }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(Oklch { l, c, h }: Oklch) -> Self {
+        let (sin, cos) = h.to_radians().sin_cos();
+        Self { l, a: c * cos, b: c * sin }
+    }
+}
+
+pub fn mix(from: proto::Color, to: proto::Color, t: f64) -> proto::Color {
+    let (a, b) = (Oklab::from(from), Oklab::from(to));
+    let lerp = |a: f64, b: f64| a + (b - a) * t;
+    let alpha = lerp(f64::from(from.a), f64::from(to.a)).round() as u8;
+
+    Oklab { l: lerp(a.l, b.l), a: lerp(a.a, b.a), b: lerp(a.b, b.b) }.to_color(alpha)
+}
+
+fn linearize(c: u8) -> f64 {
+    let c = f64::from(c) / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn gamma(c: f64) -> f64 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn color(rgb: u32) -> proto::Color {
+        let [_, r, g, b] = rgb.to_be_bytes();
+        proto::Color { r, g, b, a: 0xFF }
+    }
+
+    #[test]
+    fn round_trip() {
+        for rgb in [0x000000, 0xFFFFFF, 0xFF0000, 0x00FF00, 0x0000FF, 0x1E1E1E, 0xC586C0] {
+            assert_eq!(Oklch::from(color(rgb)).to_color(0xFF), color(rgb));
+        }
+    }
+
+    #[test]
+    fn white_and_black() {
+        let white = Oklab::from(color(0xFFFFFF));
+        assert!((white.l - 1.0).abs() < 1e-4 && white.a.abs() < 1e-4 && white.b.abs() < 1e-4);
+
+        let black = Oklab::from(color(0x000000));
+        assert!(black.l.abs() < 1e-9);
+    }
+
+    #[test]
+    fn clamps_chroma_into_gamut() {
+        let vivid = Oklch { l: 0.7, c: 0.4, h: 150.0 };
+        let clamped = vivid.clamp_chroma();
+
+        assert!(clamped.c < vivid.c);
+        assert!(Oklab::from(clamped).is_in_gamut());
+        assert!(!Oklab::from(Oklch { c: clamped.c + 0.01, ..clamped }).is_in_gamut());
+    }
+
+    #[test]
+    fn mix_endpoints() {
+        let (black, white) = (color(0x000000), color(0xFFFFFF));
+        assert_eq!(mix(black, white, 0.0), black);
+        assert_eq!(mix(black, white, 1.0), white);
+        assert_eq!(mix(black, white, 0.5), color(0x636363));
+    }
+}
@@ -0,0 +1,469 @@
+use crate::dsl;
+use crate::oklab::{Oklab, Oklch};
+use crate::proto;
+use indexmap::IndexMap;
+
+#[cfg(any(feature = "image", all(feature = "import", feature = "std-fs")))]
+use std::{fs, io, path::Path, path::PathBuf};
+#[cfg(any(feature = "image", all(feature = "import", feature = "std-fs")))]
+use thiserror::Error;
+
+const THEME_CLUSTER_DISTANCE: f64 = 0.02;
+const IMAGE_CLUSTER_DISTANCE: f64 = 0.08;
+const GREY_CHROMA: f64 = 0.03;
+#[cfg(feature = "image")]
+const MAX_KMEANS_ITERATIONS: usize = 32;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Palette {
+    colors: IndexMap<String, proto::Color>,
+}
+
+impl Palette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, color: impl Into<dsl::Color>) {
+        let color: dsl::Color = color.into();
+        self.colors.insert(name.into(), color.into());
+    }
+
+    pub fn get(&self, name: &str) -> Option<proto::Color> {
+        self.colors.get(name).copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, proto::Color)> {
+        self.colors.iter().map(|(name, color)| (name.as_str(), *color))
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+}
+
+#[cfg(all(feature = "import", feature = "std-fs"))]
+impl Palette {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadPaletteError> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|e| LoadPaletteError::Read(e, path.to_path_buf()))?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("ase") => Ok(crate::ase::decode(&bytes)?),
+            Some(ext) if ext.eq_ignore_ascii_case("gpl") => {
+                let src = String::from_utf8_lossy(&bytes);
+                Ok(crate::gpl::parse(&src)?)
+            }
+            _ => Err(LoadPaletteError::UnknownFormat(path.to_path_buf())),
+        }
+    }
+}
+
+#[cfg(all(feature = "import", feature = "std-fs"))]
+#[derive(Debug, Error)]
+pub enum LoadPaletteError {
+    #[error("failed reading palette `{1}`")]
+    Read(#[source] io::Error, PathBuf),
+    #[error("`{0}` is not an `.ase` or `.gpl` palette")]
+    UnknownFormat(PathBuf),
+    #[error(transparent)]
+    Ase(#[from] crate::ase::AseError),
+    #[error(transparent)]
+    Gpl(#[from] crate::gpl::GplError),
+}
+
+#[cfg(feature = "image")]
+impl Palette {
+    pub fn from_image(path: impl AsRef<Path>, n_colors: usize) -> Result<Self, ImageError> {
+        let pixels = read_png(path)?;
+        Ok(suggest_roles(&kmeans(pixels, n_colors)))
+    }
+}
+
+#[cfg(feature = "image")]
+pub fn read_png(path: impl AsRef<Path>) -> Result<Vec<proto::Color>, ImageError> {
+    let path = path.as_ref();
+    let file = fs::File::open(path).map_err(|e| ImageError::Read(e, path.to_path_buf()))?;
+    let decode_error = |e| ImageError::Decode(e, path.to_path_buf());
+
+    let mut decoder = png::Decoder::new(io::BufReader::new(file));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(decode_error)?;
+
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(decode_error)?;
+    let buf = &buf[..info.buffer_size()];
+
+    let pixel = |r, g, b| proto::Color { r, g, b, a: 0xFF };
+    let pixels = match info.color_type {
+        png::ColorType::Rgb => buf.chunks_exact(3).map(|p| pixel(p[0], p[1], p[2])).collect(),
+        png::ColorType::Rgba => buf.chunks_exact(4).map(|p| pixel(p[0], p[1], p[2])).collect(),
+        png::ColorType::Grayscale => buf.iter().map(|&v| pixel(v, v, v)).collect(),
+        png::ColorType::GrayscaleAlpha => {
+            buf.chunks_exact(2).map(|p| pixel(p[0], p[0], p[0])).collect()
+        }
+        png::ColorType::Indexed => {
+            return Err(ImageError::UnsupportedColorType(path.to_path_buf()))
+        }
+    };
+
+    Ok(pixels)
+}
+
+#[cfg(feature = "image")]
+#[derive(Debug, Error)]
+pub enum ImageError {
+    #[error("failed reading image `{1}`")]
+    Read(#[source] io::Error, PathBuf),
+    #[error("failed decoding PNG `{1}`")]
+    Decode(#[source] png::DecodingError, PathBuf),
+    #[error("unsupported PNG color type in `{0}`")]
+    UnsupportedColorType(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Swatch {
+    pub color: proto::Color,
+    pub count: usize,
+    pub usages: Vec<String>,
+}
+
+pub fn extract(theme: &proto::Theme) -> Vec<Swatch> {
+    let mut usages: IndexMap<proto::Color, Vec<String>> = IndexMap::new();
+    theme.visit_colors(&mut |site, color| {
+        usages.entry(proto::Color { a: 0xFF, ..*color }).or_default().push(site.to_string());
+    });
+
+    let swatches = usages
+        .into_iter()
+        .map(|(color, usages)| Swatch { color, count: usages.len(), usages })
+        .collect();
+
+    cluster(swatches, THEME_CLUSTER_DISTANCE, usize::MAX)
+}
+
+pub fn extract_from_pixels(
+    pixels: impl IntoIterator<Item = proto::Color>,
+    max_swatches: usize,
+) -> Vec<Swatch> {
+    let mut buckets: IndexMap<proto::Color, usize> = IndexMap::new();
+
+    for pixel in pixels {
+        let quantize = |c: u8| (c & 0xF8) | 0x04;
+        let bucket = proto::Color {
+            r: quantize(pixel.r),
+            g: quantize(pixel.g),
+            b: quantize(pixel.b),
+            a: 0xFF,
+        };
+        *buckets.entry(bucket).or_default() += 1;
+    }
+
+    let swatches = buckets
+        .into_iter()
+        .map(|(color, count)| Swatch { color, count, usages: Vec::new() })
+        .collect();
+
+    cluster(swatches, IMAGE_CLUSTER_DISTANCE, max_swatches)
+}
+
+pub fn suggest_roles(swatches: &[Swatch]) -> Palette {
+    let mut palette = Palette::new();
+    if swatches.is_empty() {
+        return palette;
+    }
+
+    let used_for = |key: &str| swatches.iter().position(|s| s.usages.iter().any(|u| u == key));
+
+    let background = used_for("editor.background")
+        .or_else(|| used_for("global background"))
+        .unwrap_or_else(|| max_index_by(swatches, |s| s.count as f64));
+    let foreground = used_for("editor.foreground")
+        .or_else(|| used_for("global foreground"))
+        .filter(|idx| *idx != background)
+        .or_else(|| {
+            let background = swatches[background].color;
+            let contrast = |idx: usize| swatches[idx].color.contrast_ratio(background);
+
+            (0..swatches.len())
+                .filter(|idx| swatches[*idx].color != background)
+                .reduce(|best, idx| if contrast(idx) > contrast(best) { idx } else { best })
+        });
+
+    palette.insert("background", swatches[background].color);
+    if let Some(foreground) = foreground {
+        palette.insert("foreground", swatches[foreground].color);
+    }
+
+    for (idx, swatch) in swatches.iter().enumerate() {
+        if idx == background || Some(idx) == foreground {
+            continue;
+        }
+
+        let name = hue_name(swatch.color);
+        let mut n = 1;
+        let mut unique = name.to_string();
+        while palette.get(&unique).is_some() {
+            n += 1;
+            unique = format!("{name}{n}");
+        }
+
+        palette.insert(unique, swatch.color);
+    }
+
+    palette
+}
+
+fn cluster(mut swatches: Vec<Swatch>, distance: f64, max_swatches: usize) -> Vec<Swatch> {
+    swatches.sort_by_key(|s| std::cmp::Reverse(s.count));
+
+    let mut clusters: Vec<(Oklab, Swatch)> = Vec::new();
+
+    for swatch in swatches {
+        let lab = Oklab::from(swatch.color);
+        let nearest = clusters.iter_mut().find(|(center, _)| {
+            let (dl, da, db) = (center.l - lab.l, center.a - lab.a, center.b - lab.b);
+            (dl * dl + da * da + db * db).sqrt() < distance
+        });
+
+        match nearest {
+            Some((_, cluster)) => {
+                cluster.count += swatch.count;
+                cluster.usages.extend(swatch.usages);
+            }
+            None => clusters.push((lab, swatch)),
+        }
+    }
+
+    clusters.sort_by_key(|(_, s)| std::cmp::Reverse(s.count));
+    clusters.into_iter().map(|(_, swatch)| swatch).take(max_swatches).collect()
+}
+
+#[cfg(feature = "image")]
+fn kmeans(pixels: Vec<proto::Color>, k: usize) -> Vec<Swatch> {
+    let mut buckets: IndexMap<proto::Color, usize> = IndexMap::new();
+    for pixel in pixels {
+        *buckets.entry(pixel).or_default() += 1;
+    }
+
+    let points: Vec<(Oklab, usize)> =
+        buckets.into_iter().map(|(color, count)| (Oklab::from(color), count)).collect();
+    if points.is_empty() || k == 0 {
+        return Vec::new();
+    }
+
+    let distance = |a: Oklab, b: Oklab| {
+        let (dl, da, db) = (a.l - b.l, a.a - b.a, a.b - b.b);
+        dl * dl + da * da + db * db
+    };
+    let nearest = |centers: &[Oklab], point: Oklab| {
+        (0..centers.len())
+            .min_by(|&a, &b| distance(centers[a], point).total_cmp(&distance(centers[b], point)))
+            .unwrap()
+    };
+
+    let mut centers = vec![points.iter().max_by_key(|(_, count)| *count).unwrap().0];
+    while centers.len() < k.min(points.len()) {
+        let farthest = points
+            .iter()
+            .map(|(lab, _)| *lab)
+            .max_by(|a, b| {
+                let d = |p: &Oklab| distance(centers[nearest(&centers, *p)], *p);
+                d(a).total_cmp(&d(b))
+            })
+            .unwrap();
+        centers.push(farthest);
+    }
+
+    let mut assignments = vec![usize::MAX; points.len()];
+    for _ in 0..MAX_KMEANS_ITERATIONS {
+        let mut changed = false;
+        for (assignment, (lab, _)) in assignments.iter_mut().zip(&points) {
+            let idx = nearest(&centers, *lab);
+            changed |= *assignment != idx;
+            *assignment = idx;
+        }
+
+        if !changed {
+            break;
+        }
+
+        let mut sums = vec![(0.0, 0.0, 0.0, 0); centers.len()];
+        for (&idx, (lab, count)) in assignments.iter().zip(&points) {
+            let w = *count as f64;
+            let sum = &mut sums[idx];
+            *sum = (sum.0 + lab.l * w, sum.1 + lab.a * w, sum.2 + lab.b * w, sum.3 + count);
+        }
+        for (center, (l, a, b, count)) in centers.iter_mut().zip(sums) {
+            if count > 0 {
+                let w = count as f64;
+                *center = Oklab { l: l / w, a: a / w, b: b / w };
+            }
+        }
+    }
+
+    let mut counts = vec![0; centers.len()];
+    for (&idx, (_, count)) in assignments.iter().zip(&points) {
+        counts[idx] += count;
+    }
+
+    let mut swatches: Vec<_> = centers
+        .into_iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .map(|(center, count)| Swatch { color: center.to_color(0xFF), count, usages: Vec::new() })
+        .collect();
+    swatches.sort_by_key(|s| std::cmp::Reverse(s.count));
+
+    swatches
+}
+
+fn max_index_by(swatches: &[Swatch], key: impl Fn(&Swatch) -> f64) -> usize {
+    let mut best = 0;
+
+    for (idx, swatch) in swatches.iter().enumerate() {
+        if key(swatch) > key(&swatches[best]) {
+            best = idx;
+        }
+    }
+
+    best
+}
+
+fn hue_name(color: proto::Color) -> &'static str {
+    let Oklch { c, h, .. } = Oklch::from(color);
+
+    if c < GREY_CHROMA {
+        return "grey";
+    }
+
+    match h {
+        h if h < 45.0 => "red",
+        h if h < 80.0 => "orange",
+        h if h < 120.0 => "yellow",
+        h if h < 170.0 => "green",
+        h if h < 230.0 => "cyan",
+        h if h < 285.0 => "blue",
+        h if h < 315.0 => "purple",
+        h if h < 345.0 => "magenta",
+        _ => "red",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{s, tm, ThemeBuilder};
+    use expect_test::expect;
+
+    fn describe(swatches: &[Swatch]) -> String {
+        let roles = suggest_roles(swatches);
+        let mut out = String::new();
+
+        for swatch in swatches {
+            let role = roles.iter().find(|(_, c)| *c == swatch.color).map_or("", |(name, _)| name);
+            out.push_str(&format!(
+                "{} {} {role} [{}]\n",
+                swatch.color,
+                swatch.count,
+                swatch.usages.join("; ")
+            ));
+        }
+
+        out
+    }
+
+    #[test]
+    fn extract_from_theme() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x1E1E1E);
+        t.w(["editor.foreground"], 0xD4D4D4);
+        t.w(["sideBar.background"], 0x1F1F1F);
+        t.w(["editor.selectionBackground"], (0x264F78, 0x80));
+        t.a([tm("keyword"), s("keyword")], 0x569CD6);
+        t.a([tm("string")], 0xCE9178);
+        t.a([tm("comment")], 0x6A9955);
+
+        expect![[r#"
+            #569CD6 2 blue [keyword; semantic keyword]
+            #1E1E1E 2 background [editor.background; sideBar.background]
+            #D4D4D4 1 foreground [editor.foreground]
+            #264F78 1 blue2 [editor.selectionBackground]
+            #CE9178 1 red [string]
+            #6A9955 1 green [comment]
+        "#]]
+        .assert_eq(&describe(&extract(&t.build("My cool theme"))));
+    }
+
+    #[cfg(all(feature = "import", feature = "std-fs"))]
+    #[test]
+    fn load_swatch_files() {
+        let dir = crate::tests::temp_dir("palette");
+        fs::create_dir_all(&dir).unwrap();
+
+        let gpl = dir.join("mottle.gpl");
+        fs::write(&gpl, "GIMP Palette\n30 30 30 background\n").unwrap();
+        let palette = Palette::load(&gpl).unwrap();
+        assert_eq!(palette.get("background"), Some(proto::Color { r: 30, g: 30, b: 30, a: 0xFF }));
+
+        let txt = dir.join("mottle.txt");
+        fs::write(&txt, "").unwrap();
+        assert!(matches!(Palette::load(&txt), Err(LoadPaletteError::UnknownFormat(_))));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn palette_from_image() {
+        let dir = crate::tests::temp_dir("image");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("wallpaper.png");
+
+        let mut pixels = Vec::new();
+        for rgb in [[0x10, 0x10, 0x10]; 60].iter().chain(&[[0x14, 0x12, 0x10]; 20]) {
+            pixels.extend_from_slice(rgb);
+        }
+        for rgb in [[0xF0, 0xF0, 0xF0]; 12].iter().chain(&[[0xE0, 0x20, 0x20]; 8]) {
+            pixels.extend_from_slice(rgb);
+        }
+
+        let file = fs::File::create(&path).unwrap();
+        let mut encoder = png::Encoder::new(io::BufWriter::new(file), 10, 10);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header().unwrap().write_image_data(&pixels).unwrap();
+
+        let palette = Palette::from_image(&path, 3).unwrap();
+        let actual: String =
+            palette.iter().map(|(name, color)| format!("{name} {color}\n")).collect();
+
+        expect![[r#"
+            background #111110
+            foreground #F0F0F0
+            red #E02020
+        "#]]
+        .assert_eq(&actual);
+    }
+
+    #[test]
+    fn extract_from_image_pixels() {
+        let color = |rgb: u32| {
+            let [_, r, g, b] = rgb.to_be_bytes();
+            proto::Color { r, g, b, a: 0xFF }
+        };
+        let pixels = std::iter::repeat_n(color(0x101010), 100)
+            .chain(std::iter::repeat_n(color(0x121212), 20))
+            .chain(std::iter::repeat_n(color(0xF0F0F0), 30))
+            .chain(std::iter::repeat_n(color(0xFF0000), 5));
+
+        expect![[r#"
+            #141414 120 background []
+            #F4F4F4 30 foreground []
+            #FC0404 5 red []
+        "#]]
+        .assert_eq(&describe(&extract_from_pixels(pixels, 8)));
+    }
+}
@@ -0,0 +1,383 @@
+use crate::proto::{self, semantic, textmate, ThemeType};
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::path::PathBuf;
+use thiserror::Error;
+
+pub fn parse_theme(src: &str) -> Result<proto::Theme, ParseThemeError> {
+    let value: Value = serde_json::from_str(&strip_jsonc(src)).map_err(ParseThemeError::Json)?;
+    let object = value.as_object().ok_or_else(|| invalid("", "expected an object"))?;
+
+    let name = optional_str(object, "name")?.unwrap_or_default().to_string();
+
+    let theme_type = match optional_str(object, "type")? {
+        None => None,
+        Some("light") => Some(ThemeType::Light),
+        Some("dark") => Some(ThemeType::Dark),
+        Some("hcDark") => Some(ThemeType::HighContrast),
+        Some("hcLight") => Some(ThemeType::HighContrastLight),
+        Some(other) => return Err(invalid("type", format!("unknown theme type `{other}`"))),
+    };
+
+    let include = optional_str(object, "include")?.map(PathBuf::from);
+
+    let metadata = proto::Metadata {
+        author: optional_str(object, "author")?.map(str::to_string),
+        maintainers: match object.get("maintainers") {
+            None => Vec::new(),
+            Some(Value::Array(maintainers)) => maintainers
+                .iter()
+                .enumerate()
+                .map(|(idx, m)| {
+                    m.as_str()
+                        .map(str::to_string)
+                        .ok_or_else(|| invalid(&format!("maintainers[{idx}]"), "expected a string"))
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err(invalid("maintainers", "expected an array")),
+        },
+        semantic_version: optional_str(object, "semanticVersion")?.map(str::to_string),
+    };
+
+    let mut global_token_defaults = None;
+    let mut textmate_rules = Vec::new();
+
+    match object.get("tokenColors") {
+        None => {}
+        Some(Value::Array(rules)) => {
+            textmate_rules.reserve(rules.len());
+
+            for (idx, rule) in rules.iter().enumerate() {
+                let path = format!("tokenColors[{idx}]");
+                let rule = rule.as_object().ok_or_else(|| invalid(&path, "expected an object"))?;
+                let settings = match rule.get("settings") {
+                    Some(Value::Object(settings)) => settings,
+                    _ => return Err(invalid(&path, "expected `settings` object")),
+                };
+                let settings_path = format!("{path}.settings");
+
+                match rule.get("scope") {
+                    None => {
+                        global_token_defaults = Some(textmate::GlobalDefaults {
+                            foreground: optional_color(settings, &settings_path, "foreground")?,
+                            background: optional_color(settings, &settings_path, "background")?,
+                        });
+                    }
+                    Some(scope) => textmate_rules.push(textmate::Rule {
+                        scope: parse_scope(scope, &format!("{path}.scope"))?,
+                        settings: textmate::RuleSettings {
+                            foreground: optional_color(settings, &settings_path, "foreground")?,
+                            font_style: parse_textmate_font_style(settings, &settings_path)?,
+                        },
+                        note: None,
+                    }),
+                }
+            }
+        }
+        Some(Value::String(_)) => {
+            return Err(invalid("tokenColors", "token colors in a separate file are not supported"))
+        }
+        Some(_) => return Err(invalid("tokenColors", "expected an array")),
+    }
+
+    let semantic_rules = match object.get("semanticTokenColors") {
+        None => None,
+        Some(Value::Object(rules)) => {
+            let mut parsed = IndexMap::with_capacity(rules.len());
+
+            for (selector, style) in rules {
+                let path = format!("semanticTokenColors.{selector}");
+                let parsed_selector = selector
+                    .parse::<semantic::Selector>()
+                    .map_err(|e| invalid(&path, e.message))?;
+                parsed.insert(parsed_selector, parse_semantic_style(style, &path)?);
+            }
+
+            Some(parsed)
+        }
+        Some(_) => return Err(invalid("semanticTokenColors", "expected an object")),
+    };
+
+    let semantic_highlighting = match (object.get("semanticHighlighting"), semantic_rules) {
+        (Some(Value::Bool(false)), _) => semantic::Highlighting::Off,
+        (Some(Value::Bool(true)), rules) => {
+            semantic::Highlighting::On { rules: rules.unwrap_or_default() }
+        }
+        (None, Some(rules)) => semantic::Highlighting::On { rules },
+        (None, None) => semantic::Highlighting::Inherit,
+        (Some(_), _) => return Err(invalid("semanticHighlighting", "expected a boolean")),
+    };
+
+    let mut workbench_rules = IndexMap::new();
+
+    match object.get("colors") {
+        None => {}
+        Some(Value::Object(colors)) => {
+            workbench_rules.reserve(colors.len());
+
+            for (key, color) in colors {
+                let color = match color {
+                    Value::Null => None,
+                    Value::String(s) => {
+                        Some(s.parse().map_err(|e| invalid(&format!("colors.{key}"), e))?)
+                    }
+                    _ => return Err(invalid(&format!("colors.{key}"), "expected a color string")),
+                };
+                workbench_rules.insert(Cow::Owned(key.clone()), color);
+            }
+        }
+        Some(_) => return Err(invalid("colors", "expected an object")),
+    }
+
+    Ok(proto::Theme {
+        name,
+        theme_type,
+        include,
+        metadata,
+        global_token_defaults,
+        textmate_rules,
+        semantic_highlighting,
+        workbench_rules,
+    })
+}
+
+fn parse_scope(scope: &Value, path: &str) -> Result<Vec<Cow<'static, str>>, ParseThemeError> {
+    fn split(s: &str) -> impl Iterator<Item = Cow<'static, str>> + '_ {
+        s.split(',').map(str::trim).filter(|s| !s.is_empty()).map(|s| Cow::Owned(s.to_string()))
+    }
+
+    match scope {
+        Value::String(s) => Ok(split(s).collect()),
+        Value::Array(scopes) => {
+            let mut parsed = Vec::with_capacity(scopes.len());
+
+            for (idx, scope) in scopes.iter().enumerate() {
+                match scope {
+                    Value::String(s) => parsed.extend(split(s)),
+                    _ => return Err(invalid(&format!("{path}[{idx}]"), "expected a string")),
+                }
+            }
+
+            Ok(parsed)
+        }
+        _ => Err(invalid(path, "expected a string or an array of strings")),
+    }
+}
+
+fn parse_textmate_font_style(
+    settings: &Map<String, Value>,
+    path: &str,
+) -> Result<textmate::FontStyle, ParseThemeError> {
+    let font_style = match optional_str(settings, "fontStyle") {
+        Ok(Some(font_style)) => font_style,
+        Ok(None) => return Ok(textmate::FontStyle::Inherit),
+        Err(_) => return Err(invalid(&format!("{path}.fontStyle"), "expected a string")),
+    };
+
+    let (mut bold, mut italic, mut underline) = (false, false, false);
+
+    for word in font_style.split_whitespace() {
+        match word {
+            "bold" => bold = true,
+            "italic" => italic = true,
+            "underline" => underline = true,
+            "strikethrough" => {}
+            _ => {
+                return Err(invalid(
+                    &format!("{path}.fontStyle"),
+                    format!("unknown font style `{word}`"),
+                ))
+            }
+        }
+    }
+
+    Ok(textmate::FontStyle::Set { bold, italic, underline })
+}
+
+fn parse_semantic_style(style: &Value, path: &str) -> Result<semantic::Style, ParseThemeError> {
+    let style = match style {
+        Value::String(s) => {
+            let foreground = s.parse().map_err(|e| invalid(path, e))?;
+            return Ok(semantic::Style {
+                foreground: Some(foreground),
+                font_style: semantic::FontStyle {
+                    bold: semantic::FontStyleSetting::Inherit,
+                    italic: semantic::FontStyleSetting::Inherit,
+                    underline: semantic::FontStyleSetting::Inherit,
+                },
+            });
+        }
+        Value::Object(style) => style,
+        _ => return Err(invalid(path, "expected a color string or an object")),
+    };
+
+    let setting = |key: &str| match style.get(key) {
+        None => Ok(semantic::FontStyleSetting::Inherit),
+        Some(Value::Bool(true)) => Ok(semantic::FontStyleSetting::True),
+        Some(Value::Bool(false)) => Ok(semantic::FontStyleSetting::False),
+        Some(_) => Err(invalid(&format!("{path}.{key}"), "expected a boolean")),
+    };
+
+    Ok(semantic::Style {
+        foreground: optional_color(style, path, "foreground")?,
+        font_style: semantic::FontStyle {
+            bold: setting("bold")?,
+            italic: setting("italic")?,
+            underline: setting("underline")?,
+        },
+    })
+}
+
+fn optional_str<'a>(
+    object: &'a Map<String, Value>,
+    key: &str,
+) -> Result<Option<&'a str>, ParseThemeError> {
+    match object.get(key) {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s)),
+        Some(_) => Err(invalid(key, "expected a string")),
+    }
+}
+
+fn optional_color(
+    object: &Map<String, Value>,
+    path: &str,
+    key: &str,
+) -> Result<Option<proto::Color>, ParseThemeError> {
+    let path = format!("{path}.{key}");
+
+    match object.get(key) {
+        None => Ok(None),
+        Some(Value::String(s)) => s.parse().map(Some).map_err(|e| invalid(&path, e)),
+        Some(_) => Err(invalid(&path, "expected a color string")),
+    }
+}
+
+fn invalid(path: &str, message: impl Into<String>) -> ParseThemeError {
+    ParseThemeError::Invalid { path: path.to_string(), message: message.into() }
+}
+
+pub(crate) fn strip_jsonc(src: &str) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut chars = src.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            (',', _) => {
+                let rest = chars.clone().find(|c| !c.is_whitespace());
+                if !matches!(rest, Some('}' | ']')) {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[derive(Debug, Error)]
+pub enum ParseThemeError {
+    #[error("failed parsing theme JSON")]
+    Json(#[source] serde_json::Error),
+    #[error("invalid theme at `{path}`: {message}")]
+    Invalid { path: String, message: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{s, tm, FontStyle, ThemeBuilder};
+
+    #[test]
+    fn round_trip() {
+        let mut t = ThemeBuilder::default();
+        t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+        t.w(["editor.background"], 0x1E1E1E);
+        t.w(["editor.foreground"], (0xD4D4D4, 0x80));
+        t.unset(["editor.lineHighlightBorder"]);
+        t.a([tm("keyword"), tm("storage"), s("keyword")], 0xFF0000);
+        t.a([tm("comment"), s("*.documentation:rust")], (0x6A9955, FontStyle::Italic));
+        let theme = t.build_with_type("My cool theme", ThemeType::Dark);
+
+        let serialized = crate::serialize_theme(&theme).unwrap();
+
+        assert_eq!(parse_theme(&serialized).unwrap(), theme);
+    }
+
+    #[test]
+    fn jsonc() {
+        let theme = parse_theme(
+            r##"// comment
+            {
+                /* block
+                   comment */
+                "name": "https://example.com // not a comment",
+                "colors": { "editor.background": "#123", },
+                "tokenColors": [
+                    { "scope": "keyword, storage", "settings": { "fontStyle": "" } },
+                ],
+            }"##,
+        )
+        .unwrap();
+
+        assert_eq!(theme.name, "https://example.com // not a comment");
+        assert_eq!(
+            theme.workbench_rules["editor.background"],
+            Some(proto::Color { r: 0x11, g: 0x22, b: 0x33, a: 0xFF })
+        );
+        assert_eq!(theme.textmate_rules[0].scope, ["keyword", "storage"]);
+        assert_eq!(
+            theme.textmate_rules[0].settings.font_style,
+            textmate::FontStyle::Set { bold: false, italic: false, underline: false }
+        );
+    }
+
+    #[test]
+    fn invalid_color() {
+        let error =
+            parse_theme(r##"{ "colors": { "editor.background": "#12345" } }"##).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "invalid theme at `colors.editor.background`: expected 3, 4, 6 or 8 hex digits in `#12345`"
+        );
+    }
+}
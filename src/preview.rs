@@ -0,0 +1,242 @@
+use crate::proto::{self, textmate};
+use crate::sample;
+use indexmap::IndexSet;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+const SWATCHES_PER_ROW: usize = 6;
+const CODE_WIDTH: usize = 64;
+
+pub fn print_to_terminal(theme: &proto::Theme) -> io::Result<()> {
+    write_preview(io::stdout().lock(), theme)
+}
+
+pub fn write_preview(mut w: impl Write, theme: &proto::Theme) -> io::Result<()> {
+    let workbench = |key: &str| match theme.workbench_rules.get(key) {
+        Some(Some(color)) => Some(*color),
+        _ => None,
+    };
+    let defaults = theme.global_token_defaults.unwrap_or_default();
+
+    let background = workbench("editor.background")
+        .or(defaults.background)
+        .unwrap_or(proto::Color { r: 0x1E, g: 0x1E, b: 0x1E, a: 0xFF });
+    let foreground = workbench("editor.foreground")
+        .or(defaults.foreground)
+        .unwrap_or(proto::Color { r: 0xD4, g: 0xD4, b: 0xD4, a: 0xFF })
+        .over(background);
+    let line_number = workbench("editorLineNumber.foreground")
+        .unwrap_or(proto::Color { a: 0x60, ..foreground })
+        .over(background);
+
+    writeln!(w, "{}", theme.name)?;
+    writeln!(w)?;
+
+    let mut colors = IndexSet::new();
+    colors.extend(theme.workbench_rules.values().flatten().copied());
+    colors.extend(crate::readme::syntax_groups(theme).into_keys());
+
+    for row in colors.into_iter().collect::<Vec<_>>().chunks(SWATCHES_PER_ROW) {
+        for color in row {
+            let opaque = color.over(background);
+            write!(w, "{}    {RESET} {:<10}", bg(opaque), color.to_string())?;
+        }
+        writeln!(w)?;
+    }
+    writeln!(w)?;
+
+    for (idx, line) in sample::LINES.iter().enumerate() {
+        let line_background = if idx == sample::HIGHLIGHTED_LINE {
+            workbench("editor.lineHighlightBackground").map_or(background, |c| c.over(background))
+        } else {
+            background
+        };
+        let selection = workbench("editor.selectionBackground")
+            .filter(|_| idx == sample::SELECTED_LINE)
+            .map(|c| c.over(line_background));
+
+        write!(w, "{}{} {:>3}  ", bg(background), fg(line_number), idx + 1)?;
+
+        let mut current = (background, line_number);
+        let mut column = 0;
+        for (text, scope) in *line {
+            let color = if scope.is_empty() {
+                foreground
+            } else {
+                textmate::resolve_foreground(&theme.textmate_rules, &[sample::ROOT_SCOPE, scope])
+                    .map_or(foreground, |c| c.over(line_background))
+            };
+
+            for c in text.chars() {
+                let background = match selection {
+                    Some(selection) if sample::SELECTION.contains(&column) => selection,
+                    _ => line_background,
+                };
+
+                if current.0 != background {
+                    write!(w, "{}", bg(background))?;
+                }
+                if current.1 != color {
+                    write!(w, "{}", fg(color))?;
+                }
+                current = (background, color);
+
+                write!(w, "{c}")?;
+                column += 1;
+            }
+        }
+
+        if current.0 != line_background {
+            write!(w, "{}", bg(line_background))?;
+        }
+
+        let padding = CODE_WIDTH.saturating_sub(column);
+        writeln!(w, "{:padding$}{RESET}", "")?;
+    }
+
+    Ok(())
+}
+
+pub fn html(theme: &proto::Theme) -> String {
+    let workbench = |key: &str| match theme.workbench_rules.get(key) {
+        Some(Some(color)) => Some(*color),
+        _ => None,
+    };
+    let defaults = theme.global_token_defaults.unwrap_or_default();
+
+    let background = workbench("editor.background")
+        .or(defaults.background)
+        .unwrap_or(proto::Color { r: 0x1E, g: 0x1E, b: 0x1E, a: 0xFF });
+    let foreground = workbench("editor.foreground")
+        .or(defaults.foreground)
+        .unwrap_or(proto::Color { r: 0xD4, g: 0xD4, b: 0xD4, a: 0xFF });
+    let line_number =
+        workbench("editorLineNumber.foreground").unwrap_or(proto::Color { a: 0x60, ..foreground });
+
+    let mut out = String::new();
+
+    writeln!(out, "<section>").unwrap();
+    writeln!(out, "<h2>{}</h2>", escape(&theme.name)).unwrap();
+    write!(out, "<pre style=\"background:{background};color:{foreground};padding:8px\">").unwrap();
+
+    for (idx, line) in sample::LINES.iter().enumerate() {
+        let line_background = workbench("editor.lineHighlightBackground")
+            .filter(|_| idx == sample::HIGHLIGHTED_LINE)
+            .map_or(String::new(), |c| format!(" style=\"background:{c}\""));
+        let selection =
+            workbench("editor.selectionBackground").filter(|_| idx == sample::SELECTED_LINE);
+
+        write!(
+            out,
+            "<div{line_background}><span style=\"color:{line_number}\">{:>3}</span>  ",
+            idx + 1
+        )
+        .unwrap();
+
+        let mut runs: Vec<(proto::Color, Option<proto::Color>, String)> = Vec::new();
+        let mut column = 0;
+        for (text, scope) in *line {
+            let color = if scope.is_empty() {
+                foreground
+            } else {
+                textmate::resolve_foreground(&theme.textmate_rules, &[sample::ROOT_SCOPE, scope])
+                    .unwrap_or(foreground)
+            };
+
+            for c in text.chars() {
+                let background = selection.filter(|_| sample::SELECTION.contains(&column));
+                match runs.last_mut() {
+                    Some((run_color, run_background, run))
+                        if *run_color == color && *run_background == background =>
+                    {
+                        run.push(c)
+                    }
+                    _ => runs.push((color, background, c.to_string())),
+                }
+                column += 1;
+            }
+        }
+
+        for (color, background, text) in runs {
+            let background = background.map_or(String::new(), |c| format!(";background:{c}"));
+            write!(out, "<span style=\"color:{color}{background}\">{}</span>", escape(&text))
+                .unwrap();
+        }
+
+        writeln!(out, "</div>").unwrap();
+    }
+
+    writeln!(out, "</pre>").unwrap();
+    writeln!(out, "</section>").unwrap();
+
+    out
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+const RESET: &str = "\x1b[0m";
+
+fn fg(color: proto::Color) -> String {
+    format!("\x1b[38;2;{};{};{}m", color.r, color.g, color.b)
+}
+
+fn bg(color: proto::Color) -> String {
+    format!("\x1b[48;2;{};{};{}m", color.r, color.g, color.b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{tm, ThemeBuilder};
+    use expect_test::expect;
+
+    #[test]
+    fn preview() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x000000);
+        t.w(["editor.foreground"], 0xFFFFFF);
+        t.a([tm("comment")], 0x808080);
+
+        let mut out = Vec::new();
+        write_preview(&mut out, &t.build("My cool theme")).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let first_lines: String = out.lines().take(6).map(|l| format!("{l:?}\n")).collect();
+
+        expect![[r#"
+            "My cool theme"
+            ""
+            "\u{1b}[48;2;0;0;0m    \u{1b}[0m #000000   \u{1b}[48;2;255;255;255m    \u{1b}[0m #FFFFFF   \u{1b}[48;2;128;128;128m    \u{1b}[0m #808080   "
+            ""
+            "\u{1b}[48;2;0;0;0m\u{1b}[38;2;96;96;96m   1  \u{1b}[38;2;128;128;128m// Greets everyone in the list.                                 \u{1b}[0m"
+            "\u{1b}[48;2;0;0;0m\u{1b}[38;2;96;96;96m   2  \u{1b}[38;2;255;255;255mpub fn greet(names: &[&str]) -> usize {                         \u{1b}[0m"
+        "#]]
+        .assert_eq(&first_lines);
+    }
+
+    #[test]
+    fn html_preview() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x000000);
+        t.w(["editor.foreground"], 0xFFFFFF);
+        t.w(["editor.selectionBackground"], 0x264F78);
+        t.a([tm("comment")], 0x808080);
+
+        let html = html(&t.build("My <cool> theme"));
+        let lines: String = html.lines().take(9).map(|l| format!("{l}\n")).collect();
+
+        expect![[r#"
+            <section>
+            <h2>My &lt;cool&gt; theme</h2>
+            <pre style="background:#000000;color:#FFFFFF;padding:8px"><div><span style="color:#FFFFFF60">  1</span>  <span style="color:#808080">// Greets everyone in the list.</span></div>
+            <div><span style="color:#FFFFFF60">  2</span>  <span style="color:#FFFFFF">pub fn greet(names: &amp;[&amp;str]) -&gt; usize {</span></div>
+            <div><span style="color:#FFFFFF60">  3</span>  <span style="color:#FFFFFF">    let mut count = 0;</span></div>
+            <div><span style="color:#FFFFFF60">  4</span>  <span style="color:#FFFFFF">    for name in names {</span></div>
+            <div><span style="color:#FFFFFF60">  5</span>  <span style="color:#FFFFFF">        println!(</span><span style="color:#FFFFFF;background:#264F78">&quot;Hello, {name}!</span><span style="color:#FFFFFF">&quot;);</span></div>
+            <div><span style="color:#FFFFFF60">  6</span>  <span style="color:#FFFFFF">        count += 1;</span></div>
+            <div><span style="color:#FFFFFF60">  7</span>  <span style="color:#FFFFFF">    }</span></div>
+        "#]].assert_eq(&lines);
+    }
+}
@@ -1,63 +1,1350 @@
 pub mod semantic;
 pub mod textmate;
 
+use crate::oklab::{Oklab, Oklch};
+use crate::palette::Palette;
 use indexmap::IndexMap;
 use serde::Serialize;
 use std::borrow::Cow;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
-#[serde(rename_all = "camelCase")]
+pub const COLOR_THEME_SCHEMA: &str = "vscode://schemas/color-theme";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Theme {
     pub name: String,
-    #[serde(rename = "tokenColors")]
+    pub theme_type: Option<ThemeType>,
+    pub include: Option<PathBuf>,
+    pub metadata: Metadata,
+    pub global_token_defaults: Option<textmate::GlobalDefaults>,
     pub textmate_rules: Vec<textmate::Rule>,
-    #[serde(flatten)]
     pub semantic_highlighting: semantic::Highlighting,
-    #[serde(rename = "colors")]
-    pub workbench_rules: IndexMap<Cow<'static, str>, Color>,
+    pub workbench_rules: IndexMap<Cow<'static, str>, Option<Color>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub maintainers: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub semantic_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum ThemeType {
+    #[serde(rename = "light")]
+    Light,
+    #[serde(rename = "dark")]
+    Dark,
+    #[serde(rename = "hcDark")]
+    HighContrast,
+    #[serde(rename = "hcLight")]
+    HighContrastLight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleRef<'a> {
+    TextMate { index: usize, rule: &'a textmate::Rule },
+    Semantic { selector: &'a semantic::Selector, style: &'a semantic::Style },
+    Workbench { key: &'a str, color: Option<Color> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSite<'a> {
+    GlobalForeground,
+    GlobalBackground,
+    TextMate { index: usize, scope: &'a [Cow<'static, str>] },
+    Semantic(&'a semantic::Selector),
+    Workbench(&'a str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Serialize for Theme {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::ser::ThemeFile::new(self, &crate::SerializeOptions::default()).serialize(serializer)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColorSubstitution {
+    pub from: Color,
+    pub to: Color,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Color {
-    pub r: u8,
-    pub g: u8,
-    pub b: u8,
-    pub a: u8,
-}
+const WARM_HUE: f64 = 70.0;
+
+impl Theme {
+    pub fn canonicalize(&mut self) {
+        self.workbench_rules.sort_keys();
+
+        if let semantic::Highlighting::On { rules } = &mut self.semantic_highlighting {
+            // Sorting by specificity first keeps more specific selectors after the ones they
+            // refine, so the rule VS Code picks for a token doesn't change.
+            rules.sort_by(|a, _, b, _| {
+                a.specificity()
+                    .cmp(&b.specificity())
+                    .then_with(|| a.to_string().cmp(&b.to_string()))
+            });
+        }
+
+        for rule in &mut self.textmate_rules {
+            rule.scope.sort();
+            rule.scope.dedup();
+        }
+    }
+
+    pub fn textmate_rules(&self) -> impl Iterator<Item = RuleRef<'_>> {
+        self.textmate_rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| RuleRef::TextMate { index, rule })
+    }
+
+    pub fn semantic_rules(&self) -> impl Iterator<Item = RuleRef<'_>> {
+        let rules = match &self.semantic_highlighting {
+            semantic::Highlighting::On { rules } => Some(rules),
+            _ => None,
+        };
+
+        rules.into_iter().flatten().map(|(selector, style)| RuleRef::Semantic { selector, style })
+    }
+
+    pub fn workbench_rules(&self) -> impl Iterator<Item = RuleRef<'_>> {
+        self.workbench_rules.iter().map(|(key, color)| RuleRef::Workbench { key, color: *color })
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = RuleRef<'_>> {
+        self.workbench_rules().chain(self.textmate_rules()).chain(self.semantic_rules())
+    }
+
+    pub fn visit_colors(&self, f: &mut impl FnMut(ColorSite<'_>, &Color)) {
+        if let Some(defaults) = &self.global_token_defaults {
+            if let Some(foreground) = &defaults.foreground {
+                f(ColorSite::GlobalForeground, foreground);
+            }
+            if let Some(background) = &defaults.background {
+                f(ColorSite::GlobalBackground, background);
+            }
+        }
+
+        for rule in self.rules() {
+            match rule {
+                RuleRef::TextMate { index, rule } => {
+                    if let Some(foreground) = &rule.settings.foreground {
+                        f(ColorSite::TextMate { index, scope: &rule.scope }, foreground);
+                    }
+                }
+                RuleRef::Semantic { selector, style } => {
+                    if let Some(foreground) = &style.foreground {
+                        f(ColorSite::Semantic(selector), foreground);
+                    }
+                }
+                RuleRef::Workbench { key, color: Some(color) } => {
+                    f(ColorSite::Workbench(key), &color)
+                }
+                RuleRef::Workbench { color: None, .. } => {}
+            }
+        }
+    }
+
+    pub fn map_colors(mut self, mut f: impl FnMut(Color) -> Color) -> Self {
+        self.for_each_color_mut(|color| *color = f(*color));
+        self
+    }
+
+    pub fn quantize_to(&mut self, palette: &Palette) -> Vec<ColorSubstitution> {
+        let entries: Vec<_> =
+            palette.iter().map(|(_, color)| (color, Oklab::from(color))).collect();
+        let mut substitutions = Vec::new();
+
+        self.for_each_color_mut(|color| {
+            let lab = Oklab::from(*color);
+            let distance = |other: &Oklab| {
+                (lab.l - other.l).powi(2) + (lab.a - other.a).powi(2) + (lab.b - other.b).powi(2)
+            };
+            let nearest =
+                entries.iter().min_by(|(_, x), (_, y)| distance(x).total_cmp(&distance(y)));
+
+            if let Some((nearest, _)) = nearest {
+                let to = Color { a: color.a, ..*nearest };
+                let substitution = ColorSubstitution { from: *color, to };
+                if to != *color && !substitutions.contains(&substitution) {
+                    substitutions.push(substitution);
+                }
+                *color = to;
+            }
+        });
+
+        substitutions
+    }
+
+    fn for_each_color_mut(&mut self, mut f: impl FnMut(&mut Color)) {
+        let mut map = |color: &mut Option<Color>| {
+            if let Some(c) = color {
+                f(c);
+            }
+        };
+
+        if let Some(defaults) = &mut self.global_token_defaults {
+            map(&mut defaults.foreground);
+            map(&mut defaults.background);
+        }
+
+        for rule in &mut self.textmate_rules {
+            map(&mut rule.settings.foreground);
+        }
+
+        if let semantic::Highlighting::On { rules } = &mut self.semantic_highlighting {
+            for style in rules.values_mut() {
+                map(&mut style.foreground);
+            }
+        }
+
+        for color in self.workbench_rules.values_mut() {
+            map(color);
+        }
+    }
+
+    pub fn shift_temperature(self, amount: f64) -> Self {
+        let (sin, cos) = WARM_HUE.to_radians().sin_cos();
+
+        self.map_colors(|color| {
+            let Oklab { l, a, b } = Oklab::from(color);
+            Oklab { l, a: a + amount * cos, b: b + amount * sin }.to_color(color.a)
+        })
+    }
+
+    pub fn ensure_min_contrast(&mut self, ratio: f64) -> Vec<ColorSubstitution> {
+        let background = match self.workbench_rules.get("editor.background") {
+            Some(Some(background)) => *background,
+            _ => return Vec::new(),
+        };
+
+        let mut substitutions = Vec::new();
+        let mut adjust = |color: &mut Option<Color>| {
+            let from = match color {
+                Some(color) => *color,
+                None => return,
+            };
+
+            if let Some(to) = boost_contrast(from, background, ratio) {
+                let substitution = ColorSubstitution { from, to };
+                if !substitutions.contains(&substitution) {
+                    substitutions.push(substitution);
+                }
+                *color = Some(to);
+            }
+        };
+
+        if let Some(foreground) = self.workbench_rules.get_mut("editor.foreground") {
+            adjust(foreground);
+        }
+
+        if let Some(defaults) = &mut self.global_token_defaults {
+            adjust(&mut defaults.foreground);
+        }
+
+        for rule in &mut self.textmate_rules {
+            adjust(&mut rule.settings.foreground);
+        }
+
+        if let semantic::Highlighting::On { rules } = &mut self.semantic_highlighting {
+            for style in rules.values_mut() {
+                adjust(&mut style.foreground);
+            }
+        }
+
+        substitutions
+    }
+
+    pub fn without_semantic_tokens(mut self) -> Self {
+        let rules =
+            match std::mem::replace(&mut self.semantic_highlighting, semantic::Highlighting::Off) {
+                semantic::Highlighting::On { rules } => rules,
+                _ => return self,
+            };
+
+        for (selector, style) in rules {
+            let scope = selector.textmate_scopes();
+            if scope.is_empty() {
+                continue;
+            }
+
+            self.textmate_rules.push(textmate::Rule {
+                scope,
+                settings: style.textmate_settings(),
+                note: None,
+            });
+        }
+
+        self
+    }
+
+    pub fn fingerprint(&self) -> Result<u64, crate::SerializeThemeError> {
+        let mut hasher = Fnv1a(0xCBF2_9CE4_8422_2325);
+        crate::serialize_theme_to(&mut hasher, self)?;
+        Ok(hasher.0)
+    }
+}
+
+struct Fnv1a(u64);
+
+impl io::Write for Fnv1a {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for &byte in buf {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01B3);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Color {
+    pub fn over(self, background: Color) -> Color {
+        let a = f64::from(self.a) / 255.0;
+        let blend = |fg: u8, bg: u8| (f64::from(fg) * a + f64::from(bg) * (1.0 - a)).round() as u8;
+
+        Color {
+            r: blend(self.r, background.r),
+            g: blend(self.g, background.g),
+            b: blend(self.b, background.b),
+            a: 0xFF,
+        }
+    }
+
+    pub fn relative_luminance(self) -> f64 {
+        let linearize = |c: u8| {
+            let c = f64::from(c) / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        0.2126 * linearize(self.r) + 0.7152 * linearize(self.g) + 0.0722 * linearize(self.b)
+    }
+
+    pub fn contrast_ratio(self, other: Color) -> f64 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+}
+
+fn boost_contrast(color: Color, background: Color, ratio: f64) -> Option<Color> {
+    let meets = |c: Color| c.over(background).contrast_ratio(background) >= ratio;
+    if meets(color) {
+        return None;
+    }
+
+    let oklch = Oklch::from(color);
+    let target = if Oklch::from(background).l < 0.5 { 1.0 } else { 0.0 };
+    let at = |t: f64| Oklch { l: oklch.l + (target - oklch.l) * t, ..oklch }.to_color(color.a);
+
+    let (mut low, mut high) = (0.0, 1.0);
+    if !meets(at(high)) {
+        return Some(at(high));
+    }
+
+    for _ in 0..24 {
+        let mid = (low + high) / 2.0;
+        if meets(at(mid)) {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+
+    Some(at(high))
+}
+
+impl fmt::Display for ColorSite<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GlobalForeground => write!(f, "global foreground"),
+            Self::GlobalBackground => write!(f, "global background"),
+            Self::TextMate { scope, .. } => write!(f, "{}", scope.join(", ")),
+            Self::Semantic(selector) => write!(f, "semantic {selector}"),
+            Self::Workbench(key) => write!(f, "{key}"),
+        }
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = match s.strip_prefix('#') {
+            Some(hex) if hex.is_ascii() => hex,
+            _ => return Err(format!("expected color in `#RRGGBB` form, found `{s}`")),
+        };
+
+        let digits: Result<Vec<_>, _> = match hex.len() {
+            3 | 4 => {
+                hex.chars().map(|c| u8::from_str_radix(&c.to_string().repeat(2), 16)).collect()
+            }
+            6 | 8 => {
+                (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16)).collect()
+            }
+            _ => return Err(format!("expected 3, 4, 6 or 8 hex digits in `{s}`")),
+        };
+        let digits = digits.map_err(|_| format!("invalid hex digit in `{s}`"))?;
+
+        match digits[..] {
+            [r, g, b] => Ok(Self { r, g, b, a: 0xFF }),
+            [r, g, b, a] => Ok(Self { r, g, b, a }),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.a == 0xFF {
+            write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+        } else {
+            write!(f, "#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::{expect, Expect};
+    use indexmap::IndexMap;
+
+    fn check(theme: Theme, expect: Expect) {
+        expect.assert_eq(&crate::serialize_theme(&theme).unwrap());
+    }
+
+    fn check_with_options(theme: Theme, options: crate::SerializeOptions, expect: Expect) {
+        expect.assert_eq(&crate::serialize_theme_with_options(&theme, &options).unwrap());
+    }
+
+    #[test]
+    fn completely_empty() {
+        check(
+            Theme {
+                name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
+                textmate_rules: Vec::new(),
+                semantic_highlighting: semantic::Highlighting::Off,
+                workbench_rules: IndexMap::new(),
+            },
+            expect![[r#"
+                // Do not edit directly; this file is generated.
+                {
+                    "name": "My cool theme",
+                    "tokenColors": [],
+                    "semanticHighlighting": false,
+                    "colors": {}
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn theme_type() {
+        check(
+            Theme {
+                name: "My cool theme".to_string(),
+                theme_type: Some(ThemeType::HighContrastLight),
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
+                textmate_rules: Vec::new(),
+                semantic_highlighting: semantic::Highlighting::Off,
+                workbench_rules: IndexMap::new(),
+            },
+            expect![[r#"
+                // Do not edit directly; this file is generated.
+                {
+                    "name": "My cool theme",
+                    "type": "hcLight",
+                    "tokenColors": [],
+                    "semanticHighlighting": false,
+                    "colors": {}
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn include() {
+        check(
+            Theme {
+                name: "My cool theme (muted)".to_string(),
+                theme_type: None,
+                include: Some(PathBuf::from("./my-cool-theme-color-theme.json")),
+                metadata: Metadata::default(),
+                global_token_defaults: None,
+                textmate_rules: Vec::new(),
+                semantic_highlighting: semantic::Highlighting::Off,
+                workbench_rules: IndexMap::new(),
+            },
+            expect![[r#"
+                // Do not edit directly; this file is generated.
+                {
+                    "name": "My cool theme (muted)",
+                    "include": "./my-cool-theme-color-theme.json",
+                    "tokenColors": [],
+                    "semanticHighlighting": false,
+                    "colors": {}
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn schema() {
+        check_with_options(
+            Theme {
+                name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
+                textmate_rules: Vec::new(),
+                semantic_highlighting: semantic::Highlighting::Off,
+                workbench_rules: IndexMap::new(),
+            },
+            crate::SerializeOptions { schema: true, ..Default::default() },
+            expect![[r#"
+                // Do not edit directly; this file is generated.
+                {
+                    "$schema": "vscode://schemas/color-theme",
+                    "name": "My cool theme",
+                    "tokenColors": [],
+                    "semanticHighlighting": false,
+                    "colors": {}
+                }
+            "#]],
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_include_path_is_an_error() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let theme = Theme {
+            name: "My cool theme".to_string(),
+            theme_type: None,
+            include: Some(PathBuf::from(OsStr::from_bytes(b"base-\xFF.json"))),
+            metadata: Metadata::default(),
+            global_token_defaults: None,
+            textmate_rules: Vec::new(),
+            semantic_highlighting: semantic::Highlighting::Off,
+            workbench_rules: IndexMap::new(),
+        };
+
+        assert!(matches!(crate::serialize_theme(&theme), Err(crate::SerializeThemeError::Json(_))));
+        assert!(matches!(theme.fingerprint(), Err(crate::SerializeThemeError::Json(_))));
+    }
+
+    #[test]
+    fn serialize_to_writer() {
+        let theme = Theme {
+            name: "My cool theme".to_string(),
+            theme_type: None,
+            include: None,
+            metadata: Metadata::default(),
+            global_token_defaults: None,
+            textmate_rules: Vec::new(),
+            semantic_highlighting: semantic::Highlighting::Off,
+            workbench_rules: IndexMap::new(),
+        };
+
+        let mut v = Vec::new();
+        crate::serialize_theme_to(&mut v, &theme).unwrap();
+        assert_eq!(String::from_utf8(v).unwrap(), crate::serialize_theme(&theme).unwrap());
+
+        let mut full = [0; 16];
+        let result = crate::serialize_theme_to(&mut full[..], &theme);
+        assert!(matches!(result, Err(crate::SerializeThemeError::Io(_))));
+    }
+
+    #[test]
+    fn metadata() {
+        check(
+            Theme {
+                name: "My cool theme".to_string(),
+                theme_type: Some(ThemeType::Dark),
+                include: None,
+                metadata: Metadata {
+                    author: Some("Jane Doe".to_string()),
+                    maintainers: vec!["Jane Doe <jane@example.com>".to_string()],
+                    semantic_version: Some("1.2.0".to_string()),
+                },
+                global_token_defaults: None,
+                textmate_rules: Vec::new(),
+                semantic_highlighting: semantic::Highlighting::Off,
+                workbench_rules: IndexMap::new(),
+            },
+            expect![[r#"
+                // Do not edit directly; this file is generated.
+                {
+                    "name": "My cool theme",
+                    "type": "dark",
+                    "author": "Jane Doe",
+                    "maintainers": [
+                        "Jane Doe <jane@example.com>"
+                    ],
+                    "semanticVersion": "1.2.0",
+                    "tokenColors": [],
+                    "semanticHighlighting": false,
+                    "colors": {}
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn custom_header() {
+        check_with_options(
+            Theme {
+                name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
+                textmate_rules: Vec::new(),
+                semantic_highlighting: semantic::Highlighting::Off,
+                workbench_rules: IndexMap::new(),
+            },
+            crate::SerializeOptions {
+                header: crate::Header::Custom(
+                    "SPDX-License-Identifier: MIT\n\nGenerated by mottle.".into(),
+                ),
+                ..Default::default()
+            },
+            expect![[r#"
+                // SPDX-License-Identifier: MIT
+                //
+                // Generated by mottle.
+                {
+                    "name": "My cool theme",
+                    "tokenColors": [],
+                    "semanticHighlighting": false,
+                    "colors": {}
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn omit_header() {
+        check_with_options(
+            Theme {
+                name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
+                textmate_rules: Vec::new(),
+                semantic_highlighting: semantic::Highlighting::Off,
+                workbench_rules: IndexMap::new(),
+            },
+            crate::SerializeOptions { header: crate::Header::Omit, ..Default::default() },
+            expect![[r#"
+                {
+                    "name": "My cool theme",
+                    "tokenColors": [],
+                    "semanticHighlighting": false,
+                    "colors": {}
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn tab_indent() {
+        check_with_options(
+            Theme {
+                name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
+                textmate_rules: vec![textmate::Rule {
+                    scope: vec![Cow::Borrowed("comment")],
+                    settings: textmate::RuleSettings {
+                        foreground: Some(Color { r: 0x80, g: 0x80, b: 0x80, a: 0xFF }),
+                        font_style: textmate::FontStyle::Inherit,
+                    },
+                    note: None,
+                }],
+                semantic_highlighting: semantic::Highlighting::Off,
+                workbench_rules: IndexMap::new(),
+            },
+            crate::SerializeOptions { indent: crate::Indent::Tabs, ..Default::default() },
+            expect![[r##"
+                // Do not edit directly; this file is generated.
+                {
+                	"name": "My cool theme",
+                	"tokenColors": [
+                		{
+                			"scope": [
+                				"comment"
+                			],
+                			"settings": {
+                				"foreground": "#808080"
+                			}
+                		}
+                	],
+                	"semanticHighlighting": false,
+                	"colors": {}
+                }
+            "##]],
+        );
+    }
+
+    #[test]
+    fn two_space_indent() {
+        check_with_options(
+            Theme {
+                name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
+                textmate_rules: Vec::new(),
+                semantic_highlighting: semantic::Highlighting::Off,
+                workbench_rules: IndexMap::new(),
+            },
+            crate::SerializeOptions { indent: crate::Indent::Spaces(2), ..Default::default() },
+            expect![[r#"
+                // Do not edit directly; this file is generated.
+                {
+                  "name": "My cool theme",
+                  "tokenColors": [],
+                  "semanticHighlighting": false,
+                  "colors": {}
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn single_scope_as_string() {
+        check_with_options(
+            Theme {
+                name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
+                textmate_rules: vec![
+                    textmate::Rule {
+                        scope: vec![Cow::Borrowed("comment")],
+                        settings: textmate::RuleSettings {
+                            foreground: Some(Color { r: 0x80, g: 0x80, b: 0x80, a: 0xFF }),
+                            font_style: textmate::FontStyle::Inherit,
+                        },
+                        note: None,
+                    },
+                    textmate::Rule {
+                        scope: vec![Cow::Borrowed("string"), Cow::Borrowed("constant.character")],
+                        settings: textmate::RuleSettings {
+                            foreground: Some(Color { r: 0xD4, g: 0x9E, b: 0x9E, a: 0xFF }),
+                            font_style: textmate::FontStyle::Inherit,
+                        },
+                        note: None,
+                    },
+                ],
+                semantic_highlighting: semantic::Highlighting::Off,
+                workbench_rules: IndexMap::new(),
+            },
+            crate::SerializeOptions {
+                scope_format: crate::ScopeFormat::SingleAsString,
+                ..Default::default()
+            },
+            expect![[r##"
+                // Do not edit directly; this file is generated.
+                {
+                    "name": "My cool theme",
+                    "tokenColors": [
+                        {
+                            "scope": "comment",
+                            "settings": {
+                                "foreground": "#808080"
+                            }
+                        },
+                        {
+                            "scope": [
+                                "string",
+                                "constant.character"
+                            ],
+                            "settings": {
+                                "foreground": "#D49E9E"
+                            }
+                        }
+                    ],
+                    "semanticHighlighting": false,
+                    "colors": {}
+                }
+            "##]],
+        );
+    }
+
+    #[test]
+    fn comma_separated_scopes() {
+        check_with_options(
+            Theme {
+                name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
+                textmate_rules: vec![textmate::Rule {
+                    scope: vec![
+                        Cow::Borrowed("string"),
+                        Cow::Borrowed("constant.character"),
+                        Cow::Borrowed("markup.inline.raw"),
+                    ],
+                    settings: textmate::RuleSettings {
+                        foreground: Some(Color { r: 0xD4, g: 0x9E, b: 0x9E, a: 0xFF }),
+                        font_style: textmate::FontStyle::Inherit,
+                    },
+                    note: None,
+                }],
+                semantic_highlighting: semantic::Highlighting::Off,
+                workbench_rules: IndexMap::new(),
+            },
+            crate::SerializeOptions {
+                scope_format: crate::ScopeFormat::CommaSeparated,
+                ..Default::default()
+            },
+            expect![[r##"
+                // Do not edit directly; this file is generated.
+                {
+                    "name": "My cool theme",
+                    "tokenColors": [
+                        {
+                            "scope": "string, constant.character, markup.inline.raw",
+                            "settings": {
+                                "foreground": "#D49E9E"
+                            }
+                        }
+                    ],
+                    "semanticHighlighting": false,
+                    "colors": {}
+                }
+            "##]],
+        );
+    }
+
+    #[test]
+    fn compact_arrays() {
+        check_with_options(
+            Theme {
+                name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata {
+                    author: None,
+                    maintainers: vec!["Jane Doe".to_string(), "John Doe".to_string()],
+                    semantic_version: None,
+                },
+                global_token_defaults: None,
+                textmate_rules: vec![
+                    textmate::Rule {
+                        scope: vec![Cow::Borrowed("string"), Cow::Borrowed("constant.character")],
+                        settings: textmate::RuleSettings {
+                            foreground: Some(Color { r: 0xD4, g: 0x9E, b: 0x9E, a: 0xFF }),
+                            font_style: textmate::FontStyle::Inherit,
+                        },
+                        note: None,
+                    },
+                    textmate::Rule {
+                        scope: vec![Cow::Borrowed("comment")],
+                        settings: textmate::RuleSettings {
+                            foreground: Some(Color { r: 0x80, g: 0x80, b: 0x80, a: 0xFF }),
+                            font_style: textmate::FontStyle::Inherit,
+                        },
+                        note: None,
+                    },
+                ],
+                semantic_highlighting: semantic::Highlighting::On { rules: IndexMap::new() },
+                workbench_rules: IndexMap::new(),
+            },
+            crate::SerializeOptions { compact_arrays: true, ..Default::default() },
+            expect![[r##"
+                // Do not edit directly; this file is generated.
+                {
+                    "name": "My cool theme",
+                    "maintainers": ["Jane Doe", "John Doe"],
+                    "tokenColors": [
+                        {
+                            "scope": ["string", "constant.character"],
+                            "settings": {
+                                "foreground": "#D49E9E"
+                            }
+                        },
+                        {
+                            "scope": ["comment"],
+                            "settings": {
+                                "foreground": "#808080"
+                            }
+                        }
+                    ],
+                    "semanticHighlighting": true,
+                    "semanticTokenColors": {},
+                    "colors": {}
+                }
+            "##]],
+        );
+    }
+
+    #[test]
+    fn fingerprint() {
+        use crate::dsl::{tm, ThemeBuilder};
 
-impl Serialize for Color {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        if self.a == 0xFF {
-            serializer.collect_str(&format_args!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b))
-        } else {
-            serializer.collect_str(&format_args!(
-                "#{:02X}{:02X}{:02X}{:02X}",
-                self.r, self.g, self.b, self.a
-            ))
+        let theme = |background| {
+            let mut t = ThemeBuilder::default();
+            t.w(["editor.background"], background);
+            t.a([tm("keyword")], 0x569CD6);
+            t.build("My cool theme")
+        };
+
+        let fingerprint = |background| theme(background).fingerprint().unwrap();
+
+        assert_eq!(fingerprint(0x1E1E1E), fingerprint(0x1E1E1E));
+        assert_ne!(fingerprint(0x1E1E1E), fingerprint(0x1E1E1F));
+        expect!["2b6e986e0cf3729d"].assert_eq(&format!("{:016x}", fingerprint(0x1E1E1E)));
+    }
+
+    #[test]
+    fn visit_colors() {
+        use crate::dsl::{s, tm, ThemeBuilder};
+
+        let mut t = ThemeBuilder::default();
+        t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+        t.w(["editor.background"], 0x1E1E1E);
+        t.unset(["editor.lineHighlightBorder"]);
+        t.a([tm("keyword"), tm("storage"), s("keyword")], 0x569CD6);
+        t.a([tm("comment")], crate::dsl::FontStyle::Italic);
+        let theme = t.build("My cool theme");
+
+        let mut sites = String::new();
+        theme.visit_colors(&mut |site, color| sites.push_str(&format!("{color} {site}\n")));
+
+        expect![[r#"
+            #D4D4D4 global foreground
+            #1E1E1E global background
+            #1E1E1E editor.background
+            #569CD6 keyword, storage
+            #569CD6 semantic keyword
+        "#]]
+        .assert_eq(&sites);
+        assert_eq!(theme.rules().count(), 5);
+        assert!(matches!(
+            theme.workbench_rules().last(),
+            Some(RuleRef::Workbench { key: "editor.lineHighlightBorder", color: None })
+        ));
+    }
+
+    #[test]
+    fn map_colors() {
+        use crate::dsl::{s, tm, ThemeBuilder};
+
+        let mut t = ThemeBuilder::default();
+        t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+        t.w(["editor.selectionBackground"], (0x264F78, 0x80));
+        t.unset(["editor.lineHighlightBorder"]);
+        t.a([tm("keyword"), s("keyword")], 0x569CD6);
+        t.a([tm("comment")], crate::dsl::FontStyle::Italic);
+
+        let theme = t.build("My cool theme").map_colors(|c| Color { r: c.b, b: c.r, ..c });
+
+        let mut sites = String::new();
+        theme.visit_colors(&mut |site, color| sites.push_str(&format!("{color} {site}\n")));
+
+        expect![[r#"
+            #D4D4D4 global foreground
+            #1E1E1E global background
+            #784F2680 editor.selectionBackground
+            #D69C56 keyword
+            #D69C56 semantic keyword
+        "#]]
+        .assert_eq(&sites);
+        assert_eq!(theme.workbench_rules["editor.lineHighlightBorder"], None);
+    }
+
+    #[test]
+    fn ensure_min_contrast() {
+        use crate::dsl::{s, tm, ThemeBuilder};
+
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x1E1E1E);
+        t.w(["editor.foreground"], 0xD4D4D4);
+        t.a([tm("comment"), s("comment")], 0x4A5A3A);
+        t.a([tm("punctuation")], (0x808080, 0x80));
+        t.a([tm("keyword")], 0x569CD6);
+        let mut theme = t.build("My cool theme");
+
+        let substitutions = theme.ensure_min_contrast(4.5);
+        let report: String =
+            substitutions.iter().map(|s| format!("{} -> {}\n", s.from, s.to)).collect();
+        expect![[r#"
+            #4A5A3A -> #798B68
+            #80808080 -> #EBEBEA80
+        "#]]
+        .assert_eq(&report);
+
+        let background = Color { r: 0x1E, g: 0x1E, b: 0x1E, a: 0xFF };
+        for rule in &theme.textmate_rules {
+            let foreground = rule.settings.foreground.unwrap().over(background);
+            assert!(foreground.contrast_ratio(background) >= 4.5, "{:?}", rule.scope);
         }
+        assert!(theme.ensure_min_contrast(4.5).is_empty());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use expect_test::{expect, Expect};
-    use indexmap::IndexMap;
+    #[test]
+    fn quantize_to() {
+        use crate::dsl::{s, tm, ThemeBuilder};
 
-    fn check(theme: Theme, expect: Expect) {
-        expect.assert_eq(&crate::serialize_theme(&theme));
+        let mut t = ThemeBuilder::default();
+        t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+        t.w(["editor.selectionBackground"], (0x264F78, 0x80));
+        t.a([tm("keyword"), s("keyword")], 0x569CD6);
+        t.a([tm("string")], 0xCE9178);
+        t.a([tm("comment")], 0x6A9955);
+        let mut theme = t.build("My cool theme");
+
+        let mut palette = Palette::new();
+        palette.insert("base", 0x1A1B26);
+        palette.insert("text", 0xC0CAF5);
+        palette.insert("blue", 0x7AA2F7);
+        palette.insert("orange", 0xFF9E64);
+        palette.insert("green", 0x9ECE6A);
+
+        let substitutions = theme.quantize_to(&palette);
+        let report: String =
+            substitutions.iter().map(|s| format!("{} -> {}\n", s.from, s.to)).collect();
+        expect![[r#"
+            #D4D4D4 -> #C0CAF5
+            #1E1E1E -> #1A1B26
+            #569CD6 -> #7AA2F7
+            #CE9178 -> #FF9E64
+            #6A9955 -> #9ECE6A
+            #264F7880 -> #1A1B2680
+        "#]]
+        .assert_eq(&report);
+
+        assert!(theme.quantize_to(&palette).is_empty());
+        assert!(theme.clone().quantize_to(&Palette::new()).is_empty());
     }
 
     #[test]
-    fn completely_empty() {
+    fn shift_temperature() {
+        use crate::dsl::{tm, ThemeBuilder};
+
+        let mut t = ThemeBuilder::default();
+        t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+        t.w(["editor.selectionBackground"], (0x264F78, 0x80));
+        t.a([tm("keyword")], 0x569CD6);
+        let theme = t.build("My cool theme");
+
+        let colors = |theme: Theme| {
+            let mut colors = Vec::new();
+            theme.visit_colors(&mut |_, color| colors.push(color.to_string()));
+            colors.join(" ")
+        };
+
+        let warm = colors(theme.clone().shift_temperature(0.03));
+        let cool = colors(theme.clone().shift_temperature(-0.03));
+        expect!["#E1D1C0 #271C0E #364F6980 #699BC5"].assert_eq(&warm);
+        expect!["#C6D6E8 #131F2C #0B4E8780 #3E9CE7"].assert_eq(&cool);
+        assert_eq!(theme.clone().shift_temperature(0.0), theme);
+    }
+
+    #[test]
+    fn without_semantic_tokens() {
+        use crate::dsl::{s, tm, FontStyle, ThemeBuilder};
+
+        let mut t = ThemeBuilder::default();
+        t.a([tm("comment")], 0x6A9955);
+        t.a([s("variable.readonly")], 0x4FC1FF);
+        t.a([s("function:rust")], (0xDCDCAA, FontStyle::Bold));
+        t.a([s("*.mutable")], FontStyle::Underline);
+        t.a([s("variable.mutable")], 0x9CDCFE);
+        let theme = t.build("My cool theme").without_semantic_tokens();
+
+        assert_eq!(theme.semantic_highlighting, semantic::Highlighting::Off);
+        check(
+            theme,
+            expect![[r##"
+            // Do not edit directly; this file is generated.
+            {
+                "name": "My cool theme",
+                "tokenColors": [
+                    {
+                        "scope": [
+                            "comment"
+                        ],
+                        "settings": {
+                            "foreground": "#6A9955"
+                        }
+                    },
+                    {
+                        "scope": [
+                            "variable.other.constant"
+                        ],
+                        "settings": {
+                            "foreground": "#4FC1FF"
+                        }
+                    },
+                    {
+                        "scope": [
+                            "entity.name.function.rust",
+                            "support.function.rust"
+                        ],
+                        "settings": {
+                            "foreground": "#DCDCAA",
+                            "fontStyle": "bold"
+                        }
+                    }
+                ],
+                "semanticHighlighting": false,
+                "colors": {}
+            }
+        "##]],
+        );
+    }
+
+    #[test]
+    fn rule_notes() {
+        use crate::dsl::{tm, ThemeBuilder};
+
+        let mut t = ThemeBuilder::default();
+        t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+        t.a_with_note([tm("variable.other.rust")], 0x9CDCFE, "matches rust-analyzer 2024 tokens");
+        t.a([tm("comment")], 0x6A9955);
+        t.a_with_note([tm("string")], 0xCE9178, "first line\nsecond line");
+        let theme = t.build("My cool theme");
+
+        check_with_options(
+            theme.clone(),
+            crate::SerializeOptions { rule_notes: true, ..Default::default() },
+            expect![[r##"
+                // Do not edit directly; this file is generated.
+                {
+                    "name": "My cool theme",
+                    "tokenColors": [
+                        {
+                            "settings": {
+                                "foreground": "#D4D4D4",
+                                "background": "#1E1E1E"
+                            }
+                        },
+                        // matches rust-analyzer 2024 tokens
+                        {
+                            "scope": [
+                                "variable.other.rust"
+                            ],
+                            "settings": {
+                                "foreground": "#9CDCFE"
+                            }
+                        },
+                        {
+                            "scope": [
+                                "comment"
+                            ],
+                            "settings": {
+                                "foreground": "#6A9955"
+                            }
+                        },
+                        // first line
+                        // second line
+                        {
+                            "scope": [
+                                "string"
+                            ],
+                            "settings": {
+                                "foreground": "#CE9178"
+                            }
+                        }
+                    ],
+                    "semanticHighlighting": true,
+                    "semanticTokenColors": {},
+                    "colors": {}
+                }
+            "##]],
+        );
+
+        let options = crate::SerializeOptions {
+            rule_notes: true,
+            compact_arrays: true,
+            ..Default::default()
+        };
+        let theme = Theme { global_token_defaults: None, ..theme };
+        let compact = crate::serialize_theme_with_options(&theme, &options).unwrap();
+        assert!(compact.contains("[\n        // matches rust-analyzer 2024 tokens\n        {"));
+        assert!(!crate::serialize_theme(&theme).unwrap().contains("rust-analyzer"));
+    }
+
+    #[test]
+    fn canonicalize_preserves_semantic_resolution() {
+        use crate::dsl::{s, ThemeBuilder};
+
+        let mut t = ThemeBuilder::default();
+        t.a([s("variable.readonly:rust")], 0x4FC1FF);
+        t.a([s("*.readonly")], 0x569CD6);
+        t.a([s("variable")], 0x9CDCFE);
+        t.a([s("*")], 0xD4D4D4);
+        t.a([s("function.declaration")], 0xDCDCAA);
+        let mut theme = t.build("My cool theme");
+
+        let resolve = |theme: &Theme, token: &str| {
+            let token: semantic::Selector = token.parse().unwrap();
+            let semantic::Highlighting::On { rules } = &theme.semantic_highlighting else {
+                unreachable!()
+            };
+            rules
+                .iter()
+                .filter(|(selector, _)| token.refines(selector))
+                .max_by_key(|(selector, _)| selector.specificity())
+                .map(|(_, style)| *style)
+        };
+
+        let tokens = [
+            "variable",
+            "variable.readonly",
+            "variable.readonly:rust",
+            "parameter.readonly",
+            "function.declaration",
+            "function",
+        ];
+        let before: Vec<_> = tokens.iter().map(|token| resolve(&theme, token)).collect();
+
+        theme.canonicalize();
+
+        let after: Vec<_> = tokens.iter().map(|token| resolve(&theme, token)).collect();
+        assert_eq!(before, after);
+
+        let semantic::Highlighting::On { rules } = &theme.semantic_highlighting else {
+            unreachable!()
+        };
+        let order: Vec<_> = rules.keys().map(ToString::to_string).collect();
+        expect![[r#"
+            [
+                "*",
+                "variable",
+                "*.readonly",
+                "function.declaration",
+                "variable.readonly:rust",
+            ]
+        "#]]
+        .assert_debug_eq(&order);
+    }
+
+    #[test]
+    fn canonical_order() {
+        use crate::dsl::{s, tm, ThemeBuilder};
+
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.foreground"], 0xD4D4D4);
+        t.w(["activityBar.background"], 0x333333);
+        t.w(["editor.background"], 0x1E1E1E);
+        t.a([tm("string"), tm("constant.character"), s("string")], 0xCE9178);
+        t.a([tm("comment"), s("*.documentation"), s("comment")], 0x6A9955);
+        let theme = t.build("My cool theme");
+
+        check_with_options(
+            theme,
+            crate::SerializeOptions { canonical_order: true, ..Default::default() },
+            expect![[r##"
+                // Do not edit directly; this file is generated.
+                {
+                    "name": "My cool theme",
+                    "tokenColors": [
+                        {
+                            "scope": [
+                                "constant.character",
+                                "string"
+                            ],
+                            "settings": {
+                                "foreground": "#CE9178"
+                            }
+                        },
+                        {
+                            "scope": [
+                                "comment"
+                            ],
+                            "settings": {
+                                "foreground": "#6A9955"
+                            }
+                        }
+                    ],
+                    "semanticHighlighting": true,
+                    "semanticTokenColors": {
+                        "comment": {
+                            "foreground": "#6A9955"
+                        },
+                        "string": {
+                            "foreground": "#CE9178"
+                        },
+                        "*.documentation": {
+                            "foreground": "#6A9955"
+                        }
+                    },
+                    "colors": {
+                        "activityBar.background": "#333333",
+                        "editor.background": "#1E1E1E",
+                        "editor.foreground": "#D4D4D4"
+                    }
+                }
+            "##]],
+        );
+    }
+
+    #[test]
+    fn inherit_semantic() {
         check(
             Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: Vec::new(),
-                semantic_highlighting: semantic::Highlighting::Off,
+                semantic_highlighting: semantic::Highlighting::Inherit,
                 workbench_rules: IndexMap::new(),
             },
             expect![[r#"
@@ -65,7 +1352,6 @@ mod tests {
                 {
                     "name": "My cool theme",
                     "tokenColors": [],
-                    "semanticHighlighting": false,
                     "colors": {}
                 }
             "#]],
@@ -77,6 +1363,10 @@ mod tests {
         check(
             Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: Vec::new(),
                 semantic_highlighting: semantic::Highlighting::On { rules: IndexMap::new() },
                 workbench_rules: IndexMap::new(),
@@ -99,12 +1389,17 @@ mod tests {
         check(
             Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: vec![textmate::Rule {
-                    scope: vec!["entity.function.name".to_string()],
+                    scope: vec![Cow::Borrowed("entity.function.name")],
                     settings: textmate::RuleSettings {
                         foreground: Some(Color { r: 156, g: 219, b: 222, a: 255 }),
                         font_style: textmate::FontStyle::Inherit,
                     },
+                    note: None,
                 }],
                 semantic_highlighting: semantic::Highlighting::Off,
                 workbench_rules: IndexMap::new(),
@@ -130,14 +1425,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn global_token_defaults() {
+        check(
+            Theme {
+                name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: Some(textmate::GlobalDefaults {
+                    foreground: Some(Color { r: 0xD4, g: 0xD4, b: 0xD4, a: 0xFF }),
+                    background: Some(Color { r: 0x1E, g: 0x1E, b: 0x1E, a: 0xFF }),
+                }),
+                textmate_rules: vec![textmate::Rule {
+                    scope: vec![Cow::Borrowed("comment")],
+                    settings: textmate::RuleSettings {
+                        foreground: Some(Color { r: 0x6A, g: 0x99, b: 0x55, a: 0xFF }),
+                        font_style: textmate::FontStyle::Inherit,
+                    },
+                    note: None,
+                }],
+                semantic_highlighting: semantic::Highlighting::Off,
+                workbench_rules: IndexMap::new(),
+            },
+            expect![[r##"
+                // Do not edit directly; this file is generated.
+                {
+                    "name": "My cool theme",
+                    "tokenColors": [
+                        {
+                            "settings": {
+                                "foreground": "#D4D4D4",
+                                "background": "#1E1E1E"
+                            }
+                        },
+                        {
+                            "scope": [
+                                "comment"
+                            ],
+                            "settings": {
+                                "foreground": "#6A9955"
+                            }
+                        }
+                    ],
+                    "semanticHighlighting": false,
+                    "colors": {}
+                }
+            "##]],
+        );
+    }
+
     #[test]
     fn textmate_with_font_styles() {
         check(
             Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: vec![
                     textmate::Rule {
-                        scope: vec!["storage".to_string()],
+                        scope: vec![Cow::Borrowed("storage")],
                         settings: textmate::RuleSettings {
                             foreground: Some(Color { r: 0, g: 0, b: 0, a: 255 }),
                             font_style: textmate::FontStyle::Set {
@@ -146,9 +1495,10 @@ mod tests {
                                 underline: false,
                             },
                         },
+                        note: None,
                     },
                     textmate::Rule {
-                        scope: vec!["entity".to_string()],
+                        scope: vec![Cow::Borrowed("entity")],
                         settings: textmate::RuleSettings {
                             foreground: None,
                             font_style: textmate::FontStyle::Set {
@@ -157,6 +1507,7 @@ mod tests {
                                 underline: false,
                             },
                         },
+                        note: None,
                     },
                 ],
                 semantic_highlighting: semantic::Highlighting::Off,
@@ -266,6 +1617,10 @@ mod tests {
         check(
             Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: Vec::new(),
                 semantic_highlighting: semantic::Highlighting::On { rules },
                 workbench_rules: IndexMap::new(),
@@ -297,15 +1652,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn semantic_style_shorthand() {
+        let mut rules = IndexMap::new();
+
+        rules.insert(
+            semantic::Selector {
+                kind: semantic::TokenKind::Specific(semantic::Identifier::new("string").unwrap()),
+                modifiers: Vec::new(),
+                language: None,
+            },
+            semantic::Style {
+                foreground: Some(Color { r: 0xD4, g: 0x9E, b: 0x9E, a: 0xFF }),
+                font_style: semantic::FontStyle {
+                    bold: semantic::FontStyleSetting::Inherit,
+                    italic: semantic::FontStyleSetting::Inherit,
+                    underline: semantic::FontStyleSetting::Inherit,
+                },
+            },
+        );
+
+        rules.insert(
+            semantic::Selector {
+                kind: semantic::TokenKind::Wildcard,
+                modifiers: vec![semantic::Identifier::new("mutable").unwrap()],
+                language: None,
+            },
+            semantic::Style {
+                foreground: Some(Color { r: 0xFF, g: 0xFF, b: 0xFF, a: 0xFF }),
+                font_style: semantic::FontStyle {
+                    bold: semantic::FontStyleSetting::Inherit,
+                    italic: semantic::FontStyleSetting::Inherit,
+                    underline: semantic::FontStyleSetting::True,
+                },
+            },
+        );
+
+        check_with_options(
+            Theme {
+                name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
+                textmate_rules: Vec::new(),
+                semantic_highlighting: semantic::Highlighting::On { rules },
+                workbench_rules: IndexMap::new(),
+            },
+            crate::SerializeOptions { semantic_style_shorthand: true, ..Default::default() },
+            expect![[r##"
+                // Do not edit directly; this file is generated.
+                {
+                    "name": "My cool theme",
+                    "tokenColors": [],
+                    "semanticHighlighting": true,
+                    "semanticTokenColors": {
+                        "string": "#D49E9E",
+                        "*.mutable": {
+                            "foreground": "#FFFFFF",
+                            "underline": true
+                        }
+                    },
+                    "colors": {}
+                }
+            "##]],
+        );
+    }
+
     #[test]
     fn workbench_rules() {
         let mut workbench_rules = IndexMap::new();
         workbench_rules
-            .insert(Cow::Borrowed("editor.foreground"), Color { r: 255, g: 0, b: 0, a: 255 });
+            .insert(Cow::Borrowed("editor.foreground"), Some(Color { r: 255, g: 0, b: 0, a: 255 }));
+        workbench_rules.insert(Cow::Borrowed("editor.lineHighlightBackground"), None);
 
         check(
             Theme {
                 name: "My cool theme".to_string(),
+                theme_type: None,
+                include: None,
+                metadata: Metadata::default(),
+                global_token_defaults: None,
                 textmate_rules: Vec::new(),
                 semantic_highlighting: semantic::Highlighting::Off,
                 workbench_rules,
@@ -317,7 +1744,8 @@ mod tests {
                     "tokenColors": [],
                     "semanticHighlighting": false,
                     "colors": {
-                        "editor.foreground": "#FF0000"
+                        "editor.foreground": "#FF0000",
+                        "editor.lineHighlightBackground": null
                     }
                 }
             "##]],
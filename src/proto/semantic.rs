@@ -1,11 +1,91 @@
-use super::Color;
+use super::{textmate, Color};
 use indexmap::IndexMap;
 use serde::ser::SerializeStruct;
 use serde::Serialize;
 use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+pub const STANDARD_TOKEN_TYPES: &[&str] = &[
+    "namespace",
+    "class",
+    "enum",
+    "interface",
+    "struct",
+    "typeParameter",
+    "type",
+    "parameter",
+    "variable",
+    "property",
+    "enumMember",
+    "decorator",
+    "event",
+    "function",
+    "method",
+    "macro",
+    "label",
+    "comment",
+    "string",
+    "keyword",
+    "number",
+    "regexp",
+    "operator",
+];
+
+pub const STANDARD_TOKEN_MODIFIERS: &[&str] = &[
+    "declaration",
+    "definition",
+    "readonly",
+    "static",
+    "deprecated",
+    "abstract",
+    "async",
+    "modification",
+    "documentation",
+    "defaultLibrary",
+];
+
+pub const TEXTMATE_SCOPES: &[(&str, &[&str])] = &[
+    ("comment", &["comment"]),
+    ("string", &["string"]),
+    ("keyword", &["keyword.control"]),
+    ("number", &["constant.numeric"]),
+    ("regexp", &["constant.regexp"]),
+    ("operator", &["keyword.operator"]),
+    ("namespace", &["entity.name.namespace"]),
+    ("type", &["entity.name.type", "support.type"]),
+    ("struct", &["entity.name.type.struct"]),
+    ("class", &["entity.name.type.class", "support.class"]),
+    ("interface", &["entity.name.type.interface"]),
+    ("enum", &["entity.name.type.enum"]),
+    ("typeParameter", &["entity.name.type.parameter"]),
+    ("function", &["entity.name.function", "support.function"]),
+    ("member", &["entity.name.function.member", "support.function"]),
+    ("method", &["entity.name.function.member", "support.function"]),
+    ("macro", &["entity.name.function.preprocessor"]),
+    ("variable", &["variable.other.readwrite", "entity.name.variable"]),
+    ("parameter", &["variable.parameter"]),
+    ("property", &["variable.other.property"]),
+    ("enumMember", &["variable.other.enummember"]),
+    ("event", &["variable.other.event"]),
+    ("decorator", &["entity.name.decorator", "entity.name.function"]),
+    ("variable.readonly", &["variable.other.constant"]),
+    ("property.readonly", &["variable.other.constant.property"]),
+    ("type.defaultLibrary", &["support.type"]),
+    ("class.defaultLibrary", &["support.class"]),
+    ("interface.defaultLibrary", &["support.class"]),
+    ("variable.defaultLibrary", &["support.variable", "support.other.variable"]),
+    ("variable.readonly.defaultLibrary", &["support.constant"]),
+    ("property.defaultLibrary", &["support.variable.property"]),
+    ("property.readonly.defaultLibrary", &["support.constant.property"]),
+    ("function.defaultLibrary", &["support.function"]),
+    ("member.defaultLibrary", &["support.function"]),
+];
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Highlighting {
+    Inherit,
     Off,
     On { rules: IndexMap<Selector, Style> },
 }
@@ -17,6 +97,13 @@ pub struct Selector {
     pub language: Option<Identifier>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Specificity {
+    pub language: bool,
+    pub modifiers: usize,
+    pub kind: bool,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Style {
     pub foreground: Option<Color>,
@@ -52,6 +139,8 @@ impl Serialize for Highlighting {
         S: serde::Serializer,
     {
         match self {
+            Self::Inherit => serializer.serialize_struct("SemanticHighlighting", 0)?.end(),
+
             Self::Off => {
                 let mut strukt = serializer.serialize_struct("SemanticHighlighting", 1)?;
                 strukt.serialize_field("semanticHighlighting", &false)?;
@@ -73,24 +162,246 @@ impl Serialize for Selector {
     where
         S: serde::Serializer,
     {
-        let mut s = String::new();
+        serializer.collect_str(self)
+    }
+}
 
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.kind {
-            TokenKind::Wildcard => s.push('*'),
-            TokenKind::Specific(kind) => s.push_str(&kind.0),
+            TokenKind::Wildcard => write!(f, "*")?,
+            TokenKind::Specific(kind) => write!(f, "{}", kind.0)?,
         }
 
         for modifier in &self.modifiers {
-            s.push('.');
-            s.push_str(&modifier.0);
+            write!(f, ".{}", modifier.0)?;
         }
 
         if let Some(language) = &self.language {
-            s.push(':');
-            s.push_str(&language.0);
+            write!(f, ":{}", language.0)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Selector {
+    pub fn specificity(&self) -> Specificity {
+        Specificity {
+            language: self.language.is_some(),
+            modifiers: self.modifiers.len(),
+            kind: matches!(self.kind, TokenKind::Specific(_)),
         }
+    }
+
+    pub fn refines(&self, other: &Selector) -> bool {
+        let kind = match (&self.kind, &other.kind) {
+            (_, TokenKind::Wildcard) => true,
+            (TokenKind::Specific(a), TokenKind::Specific(b)) => a == b,
+            (TokenKind::Wildcard, TokenKind::Specific(_)) => false,
+        };
+        let language = other.language.is_none() || self.language == other.language;
 
-        serializer.serialize_str(&s)
+        kind && language && other.modifiers.iter().all(|m| self.modifiers.contains(m))
+    }
+
+    pub fn textmate_scopes(&self) -> Vec<Cow<'static, str>> {
+        let kind = match &self.kind {
+            TokenKind::Specific(kind) => kind,
+            TokenKind::Wildcard => return Vec::new(),
+        };
+
+        let scopes = TEXTMATE_SCOPES.iter().find_map(|(selector, scopes)| {
+            let mut parts = selector.split('.');
+            let same_kind = parts.next() == Some(&*kind.0);
+            let modifiers: Vec<_> = parts.collect();
+            let same_modifiers = modifiers.len() == self.modifiers.len()
+                && self.modifiers.iter().all(|m| modifiers.contains(&&*m.0));
+
+            (same_kind && same_modifiers).then_some(*scopes)
+        });
+
+        let scopes = scopes.unwrap_or_default().iter();
+        match &self.language {
+            Some(language) => {
+                scopes.map(|scope| Cow::Owned(format!("{scope}.{}", language.0))).collect()
+            }
+            None => scopes.map(|scope| Cow::Borrowed(*scope)).collect(),
+        }
+    }
+
+    pub fn overlaps(&self, other: &Selector) -> bool {
+        let kind = match (&self.kind, &other.kind) {
+            (TokenKind::Specific(a), TokenKind::Specific(b)) => a == b,
+            _ => true,
+        };
+        let language = match (&self.language, &other.language) {
+            (Some(a), Some(b)) => a == b,
+            _ => true,
+        };
+
+        kind && language
+    }
+}
+
+impl FromStr for Selector {
+    type Err = ParseSelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |span: Range<usize>, message: String| ParseSelectorError {
+            selector: s.to_string(),
+            span,
+            message,
+        };
+
+        let (body, language) = match s.find(':') {
+            Some(idx) => (&s[..idx], Some((idx + 1, &s[idx + 1..]))),
+            None => (s, None),
+        };
+
+        let mut offset = 0;
+        let mut components = Vec::new();
+        for component in body.split('.') {
+            components.push((offset, component));
+            offset += component.len() + 1;
+        }
+
+        let kind = match components[0] {
+            (_, "*") => TokenKind::Wildcard,
+            (start, "") => {
+                return Err(error(start..start, "expected semantic token kind".to_string()))
+            }
+            (start, kind) => {
+                TokenKind::Specific(identifier(start, kind).map_err(|(span, m)| error(span, m))?)
+            }
+        };
+
+        let mut modifiers = Vec::new();
+        for &(start, modifier) in &components[1..] {
+            if modifier.is_empty() {
+                return Err(error(start - 1..start, "empty modifier".to_string()));
+            }
+            modifiers.push(identifier(start, modifier).map_err(|(span, m)| error(span, m))?);
+        }
+
+        let language = match language {
+            Some((start, "")) => {
+                return Err(error(start - 1..start, "expected language name after ‘:’".to_string()))
+            }
+            Some((start, language)) => {
+                Some(identifier(start, language).map_err(|(span, m)| error(span, m))?)
+            }
+            None => None,
+        };
+
+        Ok(Self { kind, modifiers, language })
+    }
+}
+
+fn identifier(start: usize, s: &str) -> Result<Identifier, (Range<usize>, String)> {
+    for (idx, c) in s.char_indices() {
+        let valid = if idx == 0 {
+            c.is_ascii_alphanumeric() || c == '_'
+        } else {
+            c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+')
+        };
+
+        if !valid {
+            let span = start + idx..start + idx + c.len_utf8();
+            return Err((span, format!("unexpected character ‘{c}’")));
+        }
+    }
+
+    Ok(Identifier(intern(s).map_or_else(|| Cow::Owned(s.to_string()), Cow::Borrowed)))
+}
+
+pub const fn is_valid_selector(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let mut idx = 0;
+
+    if !bytes.is_empty() && bytes[0] == b'*' {
+        idx = 1;
+    } else {
+        idx = identifier_end(bytes, idx);
+        if idx == 0 {
+            return false;
+        }
+    }
+
+    while idx < bytes.len() && bytes[idx] == b'.' {
+        let end = identifier_end(bytes, idx + 1);
+        if end == idx + 1 {
+            return false;
+        }
+        idx = end;
+    }
+
+    if idx < bytes.len() && bytes[idx] == b':' {
+        let end = identifier_end(bytes, idx + 1);
+        if end == idx + 1 {
+            return false;
+        }
+        idx = end;
+    }
+
+    idx == bytes.len()
+}
+
+const fn identifier_end(bytes: &[u8], start: usize) -> usize {
+    let mut idx = start;
+
+    while idx < bytes.len() {
+        let b = bytes[idx];
+        let valid =
+            b.is_ascii_alphanumeric() || b == b'_' || (idx > start && (b == b'-' || b == b'+'));
+        if !valid {
+            break;
+        }
+        idx += 1;
+    }
+
+    idx
+}
+
+fn intern(s: &str) -> Option<&'static str> {
+    STANDARD_TOKEN_TYPES.iter().chain(STANDARD_TOKEN_MODIFIERS).copied().find(|t| *t == s)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSelectorError {
+    pub selector: String,
+    pub span: Range<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ParseSelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let padding = self.selector[..self.span.start].chars().count();
+        let carets = self.selector[self.span.clone()].chars().count().max(1);
+
+        writeln!(f, "{}", self.message)?;
+        writeln!(f, "  {}", self.selector)?;
+        write!(f, "  {:padding$}{}", "", "^".repeat(carets))
+    }
+}
+
+impl std::error::Error for ParseSelectorError {}
+
+impl Style {
+    pub fn textmate_settings(&self) -> textmate::RuleSettings {
+        let settings = [self.font_style.bold, self.font_style.italic, self.font_style.underline];
+        let font_style = if settings.iter().all(|s| *s == FontStyleSetting::Inherit) {
+            textmate::FontStyle::Inherit
+        } else {
+            let on = |setting| setting == FontStyleSetting::True;
+            textmate::FontStyle::Set {
+                bold: on(self.font_style.bold),
+                italic: on(self.font_style.italic),
+                underline: on(self.font_style.underline),
+            }
+        };
+
+        textmate::RuleSettings { foreground: self.foreground, font_style }
     }
 }
 
@@ -130,13 +441,166 @@ impl Serialize for Style {
 impl Identifier {
     pub fn new(s: impl Into<Cow<'static, str>>) -> Result<Self, String> {
         let s = s.into();
+        let mut chars = s.chars();
+
+        match chars.next() {
+            Some(c) if c.is_ascii_alphanumeric() || c == '_' => {}
+            Some(_) => return Err(format!("‘{s}’ must start with a letter, digit or ‘_’")),
+            None => return Err("expected identifier".to_string()),
+        }
 
-        for c in s.chars() {
-            if !c.is_ascii_alphanumeric() && c != '-' {
+        for c in chars {
+            if !c.is_ascii_alphanumeric() && !matches!(c, '_' | '-' | '+') {
                 return Err(format!("invalid character in ‘{s}’"));
             }
         }
 
-        Ok(Self(s))
+        match s {
+            Cow::Owned(s) => Ok(Self(intern(&s).map_or(Cow::Owned(s), Cow::Borrowed))),
+            s => Ok(Self(s)),
+        }
+    }
+
+    pub const fn from_static(s: &'static str) -> Self {
+        let bytes = s.as_bytes();
+        assert!(!bytes.is_empty(), "expected identifier");
+
+        let mut idx = 0;
+        while idx < bytes.len() {
+            let b = bytes[idx];
+            let valid =
+                b.is_ascii_alphanumeric() || b == b'_' || (idx > 0 && (b == b'-' || b == b'+'));
+            assert!(valid, "invalid character in identifier");
+            idx += 1;
+        }
+
+        Self(Cow::Borrowed(s))
+    }
+
+    pub fn new_unchecked(s: impl Into<Cow<'static, str>>) -> Self {
+        Self(s.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::{expect, Expect};
+
+    #[test]
+    fn identifiers() {
+        assert!(Identifier::new("variable").is_ok());
+        assert!(Identifier::new("custom_token-type").is_ok());
+        assert!(Identifier::new("_private").is_ok());
+        assert!(Identifier::new("c++").is_ok());
+
+        assert_eq!(Identifier::new("").unwrap_err(), "expected identifier");
+        assert_eq!(
+            Identifier::new("-leading").unwrap_err(),
+            "‘-leading’ must start with a letter, digit or ‘_’"
+        );
+        assert_eq!(Identifier::new("with space").unwrap_err(), "invalid character in ‘with space’");
+
+        assert_eq!(Identifier::new_unchecked("with space").as_str(), "with space");
+    }
+
+    #[test]
+    fn interned_identifiers() {
+        const READONLY: Identifier = Identifier::from_static("readonly");
+        assert_eq!(Identifier::new("readonly"), Ok(READONLY));
+
+        let selector: Selector = "variable.readonly.mutable".parse().unwrap();
+        assert!(matches!(selector.kind, TokenKind::Specific(Identifier(Cow::Borrowed(_)))));
+        assert!(matches!(selector.modifiers[0], Identifier(Cow::Borrowed(_))));
+        assert!(matches!(selector.modifiers[1], Identifier(Cow::Owned(_))));
+        assert!(matches!(Identifier::new("static".to_string()), Ok(Identifier(Cow::Borrowed(_)))));
+    }
+
+    #[test]
+    fn const_validation() {
+        for selector in ["variable", "*.documentation", "variable.readonly:rust", "c++"] {
+            assert!(is_valid_selector(selector), "{selector}");
+            assert!(selector.parse::<Selector>().is_ok());
+        }
+
+        for selector in ["", "variable..static", ".static", "variable:", "*.*", "a:b:c", "-x"] {
+            assert!(!is_valid_selector(selector), "{selector}");
+            assert!(selector.parse::<Selector>().is_err());
+        }
+    }
+
+    #[test]
+    fn specificity() {
+        let selector = |s: &str| s.parse::<Selector>().unwrap();
+        let mut selectors: Vec<_> =
+            ["*.readonly.static", "variable:rust", "variable.readonly", "*", "variable"]
+                .map(selector)
+                .to_vec();
+        selectors.sort_by_key(Selector::specificity);
+
+        let sorted: Vec<_> = selectors.iter().map(Selector::to_string).collect();
+        assert_eq!(
+            sorted,
+            ["*", "variable", "variable.readonly", "*.readonly.static", "variable:rust"]
+        );
+
+        assert!(selector("variable.readonly:rust").refines(&selector("*.readonly")));
+        assert!(!selector("variable").refines(&selector("variable.readonly")));
+        assert!(selector("variable:rust").overlaps(&selector("*.readonly")));
+        assert!(!selector("variable:rust").overlaps(&selector("variable:go")));
+    }
+
+    fn check_error(selector: &str, expect: Expect) {
+        expect.assert_eq(&selector.parse::<Selector>().unwrap_err().to_string());
+    }
+
+    #[test]
+    fn parse_errors() {
+        check_error(
+            "variable..static",
+            expect![[r#"
+            empty modifier
+              variable..static
+                      ^"#]],
+        );
+        check_error(
+            ".static",
+            expect![[r#"
+            expected semantic token kind
+              .static
+              ^"#]],
+        );
+        check_error(
+            "variable.stat!c",
+            expect![[r#"
+            unexpected character ‘!’
+              variable.stat!c
+                           ^"#]],
+        );
+        check_error(
+            "variable:",
+            expect![[r#"
+            expected language name after ‘:’
+              variable:
+                      ^"#]],
+        );
+        check_error(
+            "variable:rust:rust",
+            expect![[r#"
+            unexpected character ‘:’
+              variable:rust:rust
+                           ^"#]],
+        );
+        check_error(
+            "*.*",
+            expect![[r#"
+            unexpected character ‘*’
+              *.*
+                ^"#]],
+        );
     }
 }
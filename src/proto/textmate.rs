@@ -1,12 +1,15 @@
 use super::Color;
 use serde::ser::SerializeStruct;
 use serde::Serialize;
+use std::borrow::Cow;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Rule {
-    pub scope: Vec<String>,
+    pub scope: Vec<Cow<'static, str>>,
     pub settings: RuleSettings,
+    #[serde(skip)]
+    pub note: Option<Cow<'static, str>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -15,6 +18,14 @@ pub struct RuleSettings {
     pub font_style: FontStyle,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct GlobalDefaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foreground: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<Color>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum FontStyle {
     Inherit,
@@ -74,3 +85,163 @@ impl Serialize for RuleSettings {
         }
     }
 }
+
+pub fn selector_matches(selector: &str, scope_stack: &[&str]) -> bool {
+    let mut parts = selector.split_whitespace().rev();
+
+    let innermost = match parts.next() {
+        Some(part) => part,
+        None => return false,
+    };
+
+    let mut remaining = match scope_stack.iter().rposition(|s| scope_matches(innermost, s)) {
+        Some(idx) => &scope_stack[..idx],
+        None => return false,
+    };
+
+    for part in parts {
+        match remaining.iter().rposition(|s| scope_matches(part, s)) {
+            Some(idx) => remaining = &remaining[..idx],
+            None => return false,
+        }
+    }
+
+    true
+}
+
+pub fn resolve_foreground(rules: &[Rule], scope_stack: &[&str]) -> Option<Color> {
+    resolve_by(rules, scope_stack, |settings| settings.foreground)
+}
+
+pub fn resolve(rules: &[Rule], scope_stack: &[&str]) -> RuleSettings {
+    RuleSettings {
+        foreground: resolve_foreground(rules, scope_stack),
+        font_style: resolve_by(rules, scope_stack, |settings| match settings.font_style {
+            FontStyle::Inherit => None,
+            font_style => Some(font_style),
+        })
+        .unwrap_or(FontStyle::Inherit),
+    }
+}
+
+fn resolve_by<T>(
+    rules: &[Rule],
+    scope_stack: &[&str],
+    get: impl Fn(&RuleSettings) -> Option<T>,
+) -> Option<T> {
+    let mut best: Option<(usize, T)> = None;
+
+    for rule in rules {
+        let value = match get(&rule.settings) {
+            Some(value) => value,
+            None => continue,
+        };
+
+        let selectors = rule.scope.iter().flat_map(|scope| scope.split(',')).map(str::trim);
+        let mut matched = None;
+
+        for selector in selectors {
+            let is_more_specific = best.as_ref().is_none_or(|(len, _)| selector.len() >= *len)
+                && matched.is_none_or(|len| selector.len() >= len);
+
+            if is_more_specific && selector_matches(selector, scope_stack) {
+                matched = Some(selector.len());
+            }
+        }
+
+        if let Some(len) = matched {
+            best = Some((len, value));
+        }
+    }
+
+    best.map(|(_, value)| value)
+}
+
+pub fn coalesce(rules: Vec<Rule>) -> Vec<Rule> {
+    let mut coalesced: Vec<Rule> = Vec::with_capacity(rules.len());
+
+    for rule in rules {
+        let target = (0..coalesced.len()).find(|&idx| {
+            coalesced[idx].settings == rule.settings
+                && coalesced[idx].note.is_none()
+                && rule.note.is_none()
+                && !coalesced[idx + 1..].iter().any(|between| {
+                    between.settings != rule.settings
+                        && between.scope.iter().any(|scope| rule.scope.contains(scope))
+                })
+        });
+
+        match target {
+            Some(idx) => {
+                let target = &mut coalesced[idx];
+                for scope in rule.scope {
+                    if !target.scope.contains(&scope) {
+                        target.scope.push(scope);
+                    }
+                }
+            }
+            None => coalesced.push(rule),
+        }
+    }
+
+    coalesced
+}
+
+fn scope_matches(selector: &str, scope: &str) -> bool {
+    match scope.strip_prefix(selector) {
+        Some(rest) => rest.is_empty() || rest.starts_with('.'),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{tm, FontStyle, ThemeBuilder};
+
+    #[test]
+    fn coalesce_identical_settings() {
+        let mut t = ThemeBuilder::default();
+        t.a([tm("keyword")], 0x569CD6);
+        t.a([tm("string")], 0xCE9178);
+        t.a([tm("storage"), tm("keyword")], 0x569CD6);
+        t.a([tm("comment")], (0x6A9955, FontStyle::Italic));
+        t.a([tm("string.quoted")], 0xCE9178);
+        t.a([tm("comment.block")], 0x6A9955);
+        t.coalesce_textmate_rules();
+
+        let rules: Vec<_> = t.textmate_rules.iter().map(|rule| rule.scope.join(", ")).collect();
+
+        assert_eq!(
+            rules,
+            ["keyword, storage", "string, string.quoted", "comment", "comment.block"]
+        );
+    }
+
+    #[test]
+    fn coalesce_keeps_overrides() {
+        let mut t = ThemeBuilder::default();
+        t.a([tm("string")], 0xCE9178);
+        t.a([tm("string.quoted")], 0xD69D85);
+        t.a([tm("string.quoted")], 0xCE9178);
+        t.coalesce_textmate_rules();
+
+        let rules: Vec<_> = t.textmate_rules.iter().map(|rule| rule.scope.join(", ")).collect();
+
+        assert_eq!(rules, ["string", "string.quoted", "string.quoted"]);
+        assert_eq!(
+            resolve_foreground(&t.textmate_rules, &["source", "string.quoted.double"]),
+            Some(Color { r: 0xCE, g: 0x91, b: 0x78, a: 0xFF })
+        );
+    }
+
+    #[test]
+    fn static_scopes_are_borrowed() {
+        const SCOPES: &[&str] = &["keyword", "storage.type"];
+
+        let mut t = ThemeBuilder::default();
+        t.a(SCOPES.iter().copied().map(tm), 0x569CD6);
+
+        assert!(t.textmate_rules[0].scope.iter().all(|scope| matches!(scope, Cow::Borrowed(_))));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::palette::Palette;
+use crate::proto::{self, semantic};
+use indexmap::IndexMap;
+use std::fmt::Write;
+
+const WORKBENCH_HIGHLIGHTS: &[&str] = &[
+    "editor.background",
+    "editor.foreground",
+    "editor.selectionBackground",
+    "editor.lineHighlightBackground",
+    "editorCursor.foreground",
+    "editorLineNumber.foreground",
+    "activityBar.background",
+    "sideBar.background",
+    "titleBar.activeBackground",
+    "tab.activeBackground",
+    "panel.background",
+    "statusBar.background",
+];
+
+pub fn markdown(theme: &proto::Theme, palette: Option<&Palette>) -> String {
+    let mut out = String::new();
+    writeln!(out, "## {}", theme.name).unwrap();
+
+    if let Some(palette) = palette.filter(|p| !p.is_empty()) {
+        writeln!(out, "\n### Palette\n").unwrap();
+        writeln!(out, "| | Name | Color |").unwrap();
+        writeln!(out, "|-|------|-------|").unwrap();
+
+        for (name, color) in palette.iter() {
+            writeln!(out, "| {} | {name} | `{color}` |", swatch(color)).unwrap();
+        }
+    }
+
+    let groups = syntax_groups(theme);
+    if !groups.is_empty() {
+        writeln!(out, "\n### Syntax\n").unwrap();
+        writeln!(out, "| | Color | Scopes |").unwrap();
+        writeln!(out, "|-|-------|--------|").unwrap();
+
+        for (color, selectors) in groups {
+            let selectors: Vec<_> = selectors.iter().map(|s| format!("`{s}`")).collect();
+            writeln!(out, "| {} | `{color}` | {} |", swatch(color), selectors.join(", ")).unwrap();
+        }
+    }
+
+    let highlights: Vec<_> = WORKBENCH_HIGHLIGHTS
+        .iter()
+        .filter_map(|key| match theme.workbench_rules.get(*key) {
+            Some(Some(color)) => Some((*key, *color)),
+            _ => None,
+        })
+        .collect();
+
+    if !highlights.is_empty() {
+        writeln!(out, "\n### Workbench\n").unwrap();
+        writeln!(out, "| | Key | Color |").unwrap();
+        writeln!(out, "|-|-----|-------|").unwrap();
+
+        for (key, color) in highlights {
+            writeln!(out, "| {} | `{key}` | `{color}` |", swatch(color)).unwrap();
+        }
+    }
+
+    out
+}
+
+pub(crate) fn syntax_groups(theme: &proto::Theme) -> IndexMap<proto::Color, Vec<String>> {
+    let mut groups: IndexMap<proto::Color, Vec<String>> = IndexMap::new();
+
+    for rule in &theme.textmate_rules {
+        if let Some(foreground) = rule.settings.foreground {
+            groups
+                .entry(foreground)
+                .or_default()
+                .extend(rule.scope.iter().map(|scope| scope.to_string()));
+        }
+    }
+
+    if let semantic::Highlighting::On { rules } = &theme.semantic_highlighting {
+        for (selector, style) in rules {
+            if let Some(foreground) = style.foreground {
+                groups.entry(foreground).or_default().push(selector.to_string());
+            }
+        }
+    }
+
+    groups
+}
+
+fn swatch(color: proto::Color) -> String {
+    format!(
+        "![](https://img.shields.io/badge/%20-%20-{:02X}{:02X}{:02X}?style=flat-square)",
+        color.r, color.g, color.b
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{s, tm, ThemeBuilder};
+    use expect_test::expect;
+
+    #[test]
+    fn palette_syntax_and_workbench() {
+        let mut palette = Palette::new();
+        palette.insert("red", 0xFF5555);
+        palette.insert("fg", 0xD4D4D4);
+
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x1E1E1E);
+        t.w(["editor.foreground", "unlisted.key"], 0xD4D4D4);
+        t.a([tm("keyword"), tm("storage"), s("keyword")], 0xFF5555);
+        t.a([tm("comment")], (0xD4D4D4, 0x80));
+
+        expect![[r#"
+            ## My cool theme
+
+            ### Palette
+
+            | | Name | Color |
+            |-|------|-------|
+            | ![](https://img.shields.io/badge/%20-%20-FF5555?style=flat-square) | red | `#FF5555` |
+            | ![](https://img.shields.io/badge/%20-%20-D4D4D4?style=flat-square) | fg | `#D4D4D4` |
+
+            ### Syntax
+
+            | | Color | Scopes |
+            |-|-------|--------|
+            | ![](https://img.shields.io/badge/%20-%20-FF5555?style=flat-square) | `#FF5555` | `keyword`, `storage`, `keyword` |
+            | ![](https://img.shields.io/badge/%20-%20-D4D4D4?style=flat-square) | `#D4D4D480` | `comment` |
+
+            ### Workbench
+
+            | | Key | Color |
+            |-|-----|-------|
+            | ![](https://img.shields.io/badge/%20-%20-1E1E1E?style=flat-square) | `editor.background` | `#1E1E1E` |
+            | ![](https://img.shields.io/badge/%20-%20-D4D4D4?style=flat-square) | `editor.foreground` | `#D4D4D4` |
+        "#]]
+        .assert_eq(&markdown(&t.build("My cool theme"), Some(&palette)));
+    }
+}
@@ -0,0 +1,166 @@
+//! Helpers for keeping generated themes in sync with the code that produces them.
+//!
+//! Call [`run`] from the `main` of your theme generator (or from a `build.rs`) and return its
+//! exit code:
+//!
+//! ```no_run
+//! use mottle::dsl::ThemeBuilder;
+//! use std::process::ExitCode;
+//!
+//! fn main() -> ExitCode {
+//!     let mut t = ThemeBuilder::default();
+//!     t.w(["editor.background"], 0x1E1E1E);
+//!
+//!     mottle::regenerate::run(&[t.build("My cool theme")])
+//! }
+//! ```
+//!
+//! Normally this writes every theme to `themes/` and removes theme files that are no longer
+//! generated. When the generator is passed `--check` or `MOTTLE_CHECK=1` is set, nothing is
+//! written; instead the generator exits with [`OUT_OF_DATE`] and prints a diff if the files on
+//! disk don't match, which makes it easy to enforce regeneration in CI.
+//!
+//! When running inside a build script, messages are emitted as `cargo:warning=` lines so they
+//! show up in the output of `cargo build` and `cargo check`.
+
+use crate::{plan_save_all_to, proto, save_all_to, FileNames, SaveStatus, StaleFiles};
+use std::env;
+use std::error::Error;
+use std::path::Path;
+use std::process::ExitCode;
+
+/// Exit code used in [`Mode::Check`] when the themes on disk are stale.
+pub const OUT_OF_DATE: u8 = 1;
+
+/// Exit code used when generating or saving the themes failed.
+pub const FAILED: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Write out-of-date themes and remove stale ones.
+    Write,
+    /// Only report whether the themes on disk are up to date.
+    Check,
+}
+
+impl Mode {
+    /// Selects [`Mode::Check`] if `--check` was passed or `MOTTLE_CHECK` is set to anything
+    /// other than an empty string or `0`.
+    pub fn from_env() -> Self {
+        let check_arg = env::args().skip(1).any(|arg| arg == "--check");
+        let check_var = env::var("MOTTLE_CHECK").is_ok_and(|v| !v.is_empty() && v != "0");
+
+        if check_arg || check_var {
+            Self::Check
+        } else {
+            Self::Write
+        }
+    }
+}
+
+/// Regenerates `themes` in the `themes` directory using the [`Mode`] selected by the
+/// environment. Files are named after the themes as with [`FileNames::ThemeName`].
+pub fn run(themes: &[proto::Theme]) -> ExitCode {
+    run_with("themes", themes, Mode::from_env(), FileNames::ThemeName)
+}
+
+/// Like [`run`], but with an explicit output directory, [`Mode`] and [`FileNames`].
+pub fn run_with(
+    dir: impl AsRef<Path>,
+    themes: &[proto::Theme],
+    mode: Mode,
+    file_names: FileNames,
+) -> ExitCode {
+    let dir = dir.as_ref();
+    let log = Log { in_build_script: env::var_os("OUT_DIR").is_some() };
+
+    match mode {
+        Mode::Write => match save_all_to(dir, themes, StaleFiles::Remove, file_names) {
+            Ok(report) => {
+                for (path, status) in &report.saved {
+                    match status {
+                        SaveStatus::Created => log.line(&format!("created {}", path.display())),
+                        SaveStatus::Updated => log.line(&format!("updated {}", path.display())),
+                        SaveStatus::Unchanged => {}
+                    }
+                }
+
+                for path in &report.removed {
+                    log.line(&format!("removed {}", path.display()));
+                }
+
+                ExitCode::SUCCESS
+            }
+            Err(e) => log.error(&e),
+        },
+
+        Mode::Check => match plan_save_all_to(dir, themes, StaleFiles::Remove, file_names) {
+            Ok(plan) if plan.is_up_to_date() => ExitCode::SUCCESS,
+            Ok(plan) => {
+                log.line("generated themes are out of date; rerun the generator without `--check`");
+                for line in plan.to_string().lines() {
+                    log.line(line);
+                }
+
+                ExitCode::from(OUT_OF_DATE)
+            }
+            Err(e) => log.error(&e),
+        },
+    }
+}
+
+struct Log {
+    in_build_script: bool,
+}
+
+impl Log {
+    fn line(&self, line: &str) {
+        if self.in_build_script {
+            println!("cargo:warning={line}");
+        } else {
+            eprintln!("{line}");
+        }
+    }
+
+    fn error(&self, error: &dyn Error) -> ExitCode {
+        self.line(&format!("error: {error}"));
+
+        let mut source = error.source();
+        while let Some(e) = source {
+            self.line(&format!("  caused by: {e}"));
+            source = e.source();
+        }
+
+        ExitCode::from(FAILED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::ThemeBuilder;
+    use crate::tests::temp_dir;
+
+    #[test]
+    fn check_then_write() {
+        let dir = temp_dir("check-then-write");
+        let themes = [ThemeBuilder::default().build("My cool theme")];
+
+        assert_eq!(
+            run_with(&dir, &themes, Mode::Check, FileNames::ThemeName),
+            ExitCode::from(OUT_OF_DATE)
+        );
+        assert!(!dir.exists());
+
+        assert_eq!(run_with(&dir, &themes, Mode::Write, FileNames::ThemeName), ExitCode::SUCCESS);
+        assert_eq!(run_with(&dir, &themes, Mode::Check, FileNames::ThemeName), ExitCode::SUCCESS);
+    }
+
+    #[test]
+    fn failure() {
+        let dir = temp_dir("regenerate-failure");
+        let themes = [ThemeBuilder::default().build("???")];
+
+        assert_eq!(run_with(&dir, &themes, Mode::Write, FileNames::Slug), ExitCode::from(FAILED));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::dsl::{Selector, Style, ThemeBuilder};
+use indexmap::IndexMap;
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoleMap {
+    roles: IndexMap<Cow<'static, str>, Vec<Selector>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoleStyles {
+    styles: IndexMap<Cow<'static, str>, Style>,
+}
+
+impl RoleMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn assign(
+        &mut self,
+        role: impl Into<Cow<'static, str>>,
+        selectors: impl IntoIterator<Item = Selector>,
+    ) {
+        self.roles.entry(role.into()).or_default().extend(selectors);
+    }
+
+    pub fn selectors(&self, role: &str) -> &[Selector] {
+        self.roles.get(role).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn roles(&self) -> impl Iterator<Item = &str> {
+        self.roles.keys().map(|role| &**role)
+    }
+
+    pub fn apply(&self, t: &mut ThemeBuilder, styles: &RoleStyles) {
+        for role in styles.styles.keys() {
+            if !self.roles.contains_key(role) {
+                panic!("Role ‘{role}’ is not mapped to any selectors");
+            }
+        }
+
+        for (role, selectors) in &self.roles {
+            if let Some(style) = styles.styles.get(role) {
+                t.a(selectors.iter().cloned(), *style);
+            }
+        }
+    }
+}
+
+impl RoleStyles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, role: impl Into<Cow<'static, str>>, style: impl Into<Style>) {
+        self.styles.insert(role.into(), style.into());
+    }
+
+    pub fn get(&self, role: &str) -> Option<Style> {
+        self.styles.get(role).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{s, tm, FontStyle};
+    use expect_test::expect;
+
+    #[test]
+    fn apply_variants() {
+        let mut roles = RoleMap::new();
+        roles.assign("literal", [tm("string"), tm("constant.numeric"), s("number")]);
+        roles.assign("callable", [tm("entity.name.function"), s("function"), s("method")]);
+        roles.assign("emphasis", [tm("markup.bold"), tm("markup.italic")]);
+
+        let mut dark = RoleStyles::new();
+        dark.set("literal", 0xCE9178);
+        dark.set("callable", 0xDCDCAA);
+        dark.set("emphasis", FontStyle::Bold);
+
+        let mut light = RoleStyles::new();
+        light.set("literal", 0xA31515);
+        light.set("callable", (0x795E26, FontStyle::Italic));
+
+        let dump = |styles| {
+            let mut t = ThemeBuilder::default();
+            roles.apply(&mut t, styles);
+            t.dump()
+        };
+
+        expect![[r#"
+            #CE9178
+              textmate   string, constant.numeric
+              semantic   number
+            #DCDCAA
+              textmate   entity.name.function
+              semantic   function
+              semantic   method
+            (no color)
+              textmate   markup.bold, markup.italic  bold
+        "#]]
+        .assert_eq(&dump(&dark));
+        expect![[r#"
+            #A31515
+              textmate   string, constant.numeric
+              semantic   number
+            #795E26
+              textmate   entity.name.function      italic
+              semantic   function                  italic
+              semantic   method                    italic
+        "#]]
+        .assert_eq(&dump(&light));
+    }
+
+    #[test]
+    #[should_panic(expected = "Role ‘keyword’ is not mapped to any selectors")]
+    fn unmapped_role() {
+        let mut styles = RoleStyles::new();
+        styles.set("keyword", 0x569CD6);
+        RoleMap::new().apply(&mut ThemeBuilder::default(), &styles);
+    }
+}
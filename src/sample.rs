@@ -0,0 +1,59 @@
+pub(crate) const ROOT_SCOPE: &str = "source.rust";
+
+pub(crate) const LINES: &[&[(&str, &str)]] = &[
+    &[("// Greets everyone in the list.", "comment.line.double-slash.rust")],
+    &[
+        ("pub", "storage.modifier.rust"),
+        (" ", ""),
+        ("fn", "storage.type.function.rust"),
+        (" ", ""),
+        ("greet", "entity.name.function.rust"),
+        ("(", "punctuation.brackets.round.rust"),
+        ("names", "variable.parameter.rust"),
+        (": ", ""),
+        ("&[&str]", "entity.name.type.rust"),
+        (")", "punctuation.brackets.round.rust"),
+        (" -> ", "keyword.operator.arrow.rust"),
+        ("usize", "entity.name.type.primitive.rust"),
+        (" {", ""),
+    ],
+    &[
+        ("    ", ""),
+        ("let", "storage.type.rust"),
+        (" ", ""),
+        ("mut", "storage.modifier.mut.rust"),
+        (" count = ", ""),
+        ("0", "constant.numeric.decimal.rust"),
+        (";", ""),
+    ],
+    &[
+        ("    ", ""),
+        ("for", "keyword.control.rust"),
+        (" name ", ""),
+        ("in", "keyword.control.rust"),
+        (" names {", ""),
+    ],
+    &[
+        ("        ", ""),
+        ("println!", "entity.name.function.macro.rust"),
+        ("(", ""),
+        ("\"Hello, {name}!\"", "string.quoted.double.rust"),
+        (");", ""),
+    ],
+    &[
+        ("        count ", ""),
+        ("+=", "keyword.operator.assignment.rust"),
+        (" ", ""),
+        ("1", "constant.numeric.decimal.rust"),
+        (";", ""),
+    ],
+    &[("    }", "")],
+    &[("    count", "")],
+    &[("}", "")],
+];
+
+pub(crate) const HIGHLIGHTED_LINE: usize = 3;
+
+pub(crate) const SELECTED_LINE: usize = 4;
+
+pub(crate) const SELECTION: std::ops::Range<usize> = 17..32;
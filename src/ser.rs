@@ -0,0 +1,491 @@
+use crate::proto::{self, semantic, textmate};
+use crate::{Indent, ScopeFormat, SerializeOptions, SerializeThemeError};
+use indexmap::IndexMap;
+use serde::ser::{SerializeSeq, SerializeStruct};
+use serde::Serialize;
+use serde_json::ser::{CharEscape, CompactFormatter};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::io;
+use std::path::Path;
+
+pub(crate) fn to_string(
+    value: &impl Serialize,
+    options: &SerializeOptions,
+) -> Result<String, SerializeThemeError> {
+    let mut v = Vec::new();
+    write(&mut v, value, options)?;
+
+    String::from_utf8(v).map_err(SerializeThemeError::Utf8)
+}
+
+pub(crate) fn write(
+    writer: impl io::Write,
+    value: &impl Serialize,
+    options: &SerializeOptions,
+) -> Result<(), SerializeThemeError> {
+    write_with_formatter(writer, value, Formatter::new(options))
+}
+
+pub(crate) fn write_theme(
+    writer: impl io::Write,
+    theme: &proto::Theme,
+    options: &SerializeOptions,
+) -> Result<(), SerializeThemeError> {
+    let mut formatter = Formatter::new(options);
+
+    if options.rule_notes {
+        if theme.global_token_defaults.is_some() {
+            formatter.notes.push_back(None);
+        }
+        formatter.notes.extend(theme.textmate_rules.iter().map(|rule| rule.note.clone()));
+    }
+
+    write_with_formatter(writer, &ThemeFile::new(theme, options), formatter)
+}
+
+fn write_with_formatter(
+    mut writer: impl io::Write,
+    value: &impl Serialize,
+    formatter: Formatter,
+) -> Result<(), SerializeThemeError> {
+    let mut serializer = serde_json::Serializer::with_formatter(&mut writer, formatter);
+
+    value.serialize(&mut serializer).map_err(|e| {
+        if e.is_io() {
+            SerializeThemeError::Io(e.into())
+        } else {
+            SerializeThemeError::Json(e)
+        }
+    })?;
+
+    writer.write_all(b"\n").map_err(SerializeThemeError::Io)
+}
+
+#[derive(Serialize)]
+pub(crate) struct ThemeFile<'a> {
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    schema: Option<&'static str>,
+    name: &'a str,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    theme_type: Option<proto::ThemeType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    include: Option<&'a Path>,
+    #[serde(flatten)]
+    metadata: &'a proto::Metadata,
+    #[serde(rename = "tokenColors")]
+    textmate_rules: TokenColors<'a>,
+    #[serde(flatten)]
+    semantic_highlighting: SemanticHighlighting<'a>,
+    #[serde(rename = "colors")]
+    workbench_rules: &'a IndexMap<Cow<'static, str>, Option<proto::Color>>,
+}
+
+impl<'a> ThemeFile<'a> {
+    pub(crate) fn new(theme: &'a proto::Theme, options: &'a SerializeOptions) -> Self {
+        Self {
+            schema: options.schema.then_some(proto::COLOR_THEME_SCHEMA),
+            name: &theme.name,
+            theme_type: theme.theme_type,
+            include: theme.include.as_deref(),
+            metadata: &theme.metadata,
+            textmate_rules: TokenColors {
+                global_defaults: theme.global_token_defaults.as_ref(),
+                rules: &theme.textmate_rules,
+                scope_format: options.scope_format,
+            },
+            semantic_highlighting: SemanticHighlighting {
+                highlighting: &theme.semantic_highlighting,
+                style_shorthand: options.semantic_style_shorthand,
+            },
+            workbench_rules: &theme.workbench_rules,
+        }
+    }
+}
+
+struct TokenColors<'a> {
+    global_defaults: Option<&'a textmate::GlobalDefaults>,
+    rules: &'a [textmate::Rule],
+    scope_format: ScopeFormat,
+}
+
+impl Serialize for TokenColors<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let len = self.rules.len() + usize::from(self.global_defaults.is_some());
+        let mut seq = serializer.serialize_seq(Some(len))?;
+
+        if let Some(settings) = self.global_defaults {
+            seq.serialize_element(&GlobalDefaultsRule { settings })?;
+        }
+
+        for rule in self.rules {
+            seq.serialize_element(&Rule { rule, scope_format: self.scope_format })?;
+        }
+
+        seq.end()
+    }
+}
+
+#[derive(Serialize)]
+struct GlobalDefaultsRule<'a> {
+    settings: &'a textmate::GlobalDefaults,
+}
+
+struct Rule<'a> {
+    rule: &'a textmate::Rule,
+    scope_format: ScopeFormat,
+}
+
+impl Serialize for Rule<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut strukt = serializer.serialize_struct("Rule", 2)?;
+
+        match (self.scope_format, self.rule.scope.as_slice()) {
+            (ScopeFormat::SingleAsString, [scope]) => strukt.serialize_field("scope", scope)?,
+            (ScopeFormat::CommaSeparated, scopes) => {
+                strukt.serialize_field("scope", &scopes.join(", "))?
+            }
+            _ => strukt.serialize_field("scope", &self.rule.scope)?,
+        }
+
+        strukt.serialize_field("settings", &self.rule.settings)?;
+        strukt.end()
+    }
+}
+
+struct SemanticHighlighting<'a> {
+    highlighting: &'a semantic::Highlighting,
+    style_shorthand: bool,
+}
+
+impl Serialize for SemanticHighlighting<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.highlighting {
+            semantic::Highlighting::On { rules } if self.style_shorthand => {
+                let mut strukt = serializer.serialize_struct("SemanticHighlighting", 2)?;
+                strukt.serialize_field("semanticHighlighting", &true)?;
+                strukt.serialize_field("semanticTokenColors", &SemanticRules(rules))?;
+                strukt.end()
+            }
+            highlighting => highlighting.serialize(serializer),
+        }
+    }
+}
+
+struct SemanticRules<'a>(&'a IndexMap<semantic::Selector, semantic::Style>);
+
+impl Serialize for SemanticRules<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let inherit = semantic::FontStyle {
+            bold: semantic::FontStyleSetting::Inherit,
+            italic: semantic::FontStyleSetting::Inherit,
+            underline: semantic::FontStyleSetting::Inherit,
+        };
+
+        serializer.collect_map(self.0.iter().map(|(selector, style)| {
+            let value = match style.foreground {
+                Some(foreground) if style.font_style == inherit => SemanticStyle::Color(foreground),
+                _ => SemanticStyle::Style(style),
+            };
+
+            (selector, value)
+        }))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum SemanticStyle<'a> {
+    Color(proto::Color),
+    Style(&'a semantic::Style),
+}
+
+pub(crate) struct Formatter {
+    indent: Vec<u8>,
+    compact_arrays: bool,
+    containers: Vec<Container>,
+    pending_array_value: bool,
+    notes: VecDeque<Option<Cow<'static, str>>>,
+    pending_note: Option<Cow<'static, str>>,
+    top_level_key: String,
+    in_top_level_key: bool,
+}
+
+struct Container {
+    layout: Layout,
+    has_value: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    Undecided,
+    Inline,
+    Multiline,
+}
+
+impl Formatter {
+    pub(crate) fn new(options: &SerializeOptions) -> Self {
+        let indent = match options.indent {
+            Indent::Spaces(n) => vec![b' '; n],
+            Indent::Tabs => vec![b'\t'],
+        };
+
+        Self {
+            indent,
+            compact_arrays: options.compact_arrays,
+            containers: Vec::new(),
+            pending_array_value: false,
+            notes: VecDeque::new(),
+            pending_note: None,
+            top_level_key: String::new(),
+            in_top_level_key: false,
+        }
+    }
+
+    fn newline<W>(&self, writer: &mut W, depth: usize) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b"\n")?;
+
+        for _ in 0..depth {
+            writer.write_all(&self.indent)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_note<W>(&mut self, writer: &mut W, depth: usize) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        if let Some(note) = self.pending_note.take() {
+            for line in note.lines() {
+                writer.write_all(b"// ")?;
+                writer.write_all(line.as_bytes())?;
+                self.newline(writer, depth)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Arrays in compact mode are laid out once their first value arrives:
+    // scalars stay on one line, while nested containers get one line each.
+    fn start_value<W>(&mut self, writer: &mut W, is_container: bool) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        if !self.pending_array_value {
+            return Ok(());
+        }
+
+        self.pending_array_value = false;
+        let depth = self.containers.len();
+        let container = self.current()?;
+
+        if is_container {
+            container.layout = Layout::Multiline;
+            self.newline(writer, depth)?;
+            self.write_note(writer, depth)?;
+        } else {
+            container.layout = Layout::Inline;
+        }
+
+        Ok(())
+    }
+
+    fn current(&mut self) -> io::Result<&mut Container> {
+        self.containers.last_mut().ok_or_else(unbalanced)
+    }
+
+    fn end_container<W>(&mut self, writer: &mut W, close: &[u8]) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        let container = self.containers.pop().ok_or_else(unbalanced)?;
+
+        if container.has_value && container.layout == Layout::Multiline {
+            self.newline(writer, self.containers.len())?;
+        }
+
+        writer.write_all(close)
+    }
+}
+
+fn unbalanced() -> io::Error {
+    io::Error::other("serializer produced unbalanced containers")
+}
+
+macro_rules! scalars {
+    ($($method:ident($ty:ty)),* $(,)?) => {$(
+        fn $method<W>(&mut self, writer: &mut W, value: $ty) -> io::Result<()>
+        where
+            W: ?Sized + io::Write,
+        {
+            self.start_value(writer, false)?;
+            CompactFormatter.$method(writer, value)
+        }
+    )*};
+}
+
+impl serde_json::ser::Formatter for Formatter {
+    scalars! {
+        write_bool(bool),
+        write_i8(i8),
+        write_i16(i16),
+        write_i32(i32),
+        write_i64(i64),
+        write_i128(i128),
+        write_u8(u8),
+        write_u16(u16),
+        write_u32(u32),
+        write_u64(u64),
+        write_u128(u128),
+        write_f32(f32),
+        write_f64(f64),
+        write_number_str(&str),
+        write_byte_array(&[u8]),
+        write_raw_fragment(&str),
+    }
+
+    fn write_null<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.start_value(writer, false)?;
+        CompactFormatter.write_null(writer)
+    }
+
+    fn begin_string<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.start_value(writer, false)?;
+        CompactFormatter.begin_string(writer)
+    }
+
+    fn write_char_escape<W>(&mut self, writer: &mut W, char_escape: CharEscape) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        CompactFormatter.write_char_escape(writer, char_escape)
+    }
+
+    fn begin_array<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.start_value(writer, true)?;
+        let layout = if self.compact_arrays { Layout::Undecided } else { Layout::Multiline };
+        self.containers.push(Container { layout, has_value: false });
+        writer.write_all(b"[")
+    }
+
+    fn end_array<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.end_container(writer, b"]")
+    }
+
+    fn begin_array_value<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        if self.containers.len() == 2 && self.top_level_key == "tokenColors" {
+            self.pending_note = self.notes.pop_front().flatten();
+        }
+
+        match self.current()?.layout {
+            Layout::Undecided => self.pending_array_value = true,
+            Layout::Inline if first => {}
+            Layout::Inline => writer.write_all(b", ")?,
+            Layout::Multiline => {
+                if !first {
+                    writer.write_all(b",")?;
+                }
+                self.newline(writer, self.containers.len())?;
+                self.write_note(writer, self.containers.len())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn end_array_value<W>(&mut self, _writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.current()?.has_value = true;
+        Ok(())
+    }
+
+    fn begin_object<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.start_value(writer, true)?;
+        self.containers.push(Container { layout: Layout::Multiline, has_value: false });
+        writer.write_all(b"{")
+    }
+
+    fn end_object<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.end_container(writer, b"}")
+    }
+
+    fn begin_object_key<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        if !first {
+            writer.write_all(b",")?;
+        }
+        if self.containers.len() == 1 {
+            self.top_level_key.clear();
+            self.in_top_level_key = true;
+        }
+        self.newline(writer, self.containers.len())
+    }
+
+    fn write_string_fragment<W>(&mut self, writer: &mut W, fragment: &str) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        if self.in_top_level_key {
+            self.top_level_key.push_str(fragment);
+        }
+        writer.write_all(fragment.as_bytes())
+    }
+
+    fn begin_object_value<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.in_top_level_key = false;
+        writer.write_all(b": ")
+    }
+
+    fn end_object_value<W>(&mut self, _writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.current()?.has_value = true;
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use crate::proto::{self, semantic, textmate};
+use crate::{ser, SerializeOptions, SerializeThemeError};
+use indexmap::IndexMap;
+use serde::Serialize;
+
+pub fn semantic_token_color_customizations(
+    theme: &proto::Theme,
+) -> Result<String, SerializeThemeError> {
+    let (enabled, rules) = match &theme.semantic_highlighting {
+        semantic::Highlighting::Inherit => (None, None),
+        semantic::Highlighting::Off => (Some(false), None),
+        semantic::Highlighting::On { rules } => (Some(true), Some(rules)),
+    };
+
+    let mut themes = IndexMap::new();
+    themes.insert(format!("[{}]", theme.name), SemanticTokenColorCustomizations { enabled, rules });
+
+    let mut settings = IndexMap::new();
+    settings.insert("editor.semanticTokenColorCustomizations", themes);
+
+    ser::to_string(&settings, &SerializeOptions::default())
+}
+
+pub fn token_color_customizations(theme: &proto::Theme) -> Result<String, SerializeThemeError> {
+    let mut themes = IndexMap::new();
+    themes.insert(
+        format!("[{}]", theme.name),
+        TokenColorCustomizations { textmate_rules: &theme.textmate_rules },
+    );
+
+    let mut settings = IndexMap::new();
+    settings.insert("editor.tokenColorCustomizations", themes);
+
+    ser::to_string(&settings, &SerializeOptions::default())
+}
+
+#[derive(Serialize)]
+struct TokenColorCustomizations<'a> {
+    #[serde(rename = "textMateRules")]
+    textmate_rules: &'a [textmate::Rule],
+}
+
+#[derive(Serialize)]
+struct SemanticTokenColorCustomizations<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rules: Option<&'a IndexMap<semantic::Selector, semantic::Style>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{s, tm, FontStyle, ThemeBuilder};
+    use expect_test::expect;
+
+    #[test]
+    fn semantic_fragment() {
+        let mut t = ThemeBuilder::default();
+        t.a([s("function"), tm("entity.name.function")], 0x9CDBDE);
+        t.a([s("*.mutable")], FontStyle::Underline);
+
+        expect![[r##"
+            {
+                "editor.semanticTokenColorCustomizations": {
+                    "[My cool theme]": {
+                        "enabled": true,
+                        "rules": {
+                            "function": {
+                                "foreground": "#9CDBDE"
+                            },
+                            "*.mutable": {
+                                "underline": true
+                            }
+                        }
+                    }
+                }
+            }
+        "##]]
+        .assert_eq(&semantic_token_color_customizations(&t.build("My cool theme")).unwrap());
+    }
+
+    #[test]
+    fn textmate_fragment() {
+        let mut t = ThemeBuilder::default();
+        t.a([tm("keyword"), tm("storage"), s("keyword")], (0xEADFAF, FontStyle::Bold));
+
+        expect![[r##"
+            {
+                "editor.tokenColorCustomizations": {
+                    "[My cool theme]": {
+                        "textMateRules": [
+                            {
+                                "scope": [
+                                    "keyword",
+                                    "storage"
+                                ],
+                                "settings": {
+                                    "foreground": "#EADFAF",
+                                    "fontStyle": "bold"
+                                }
+                            }
+                        ]
+                    }
+                }
+            }
+        "##]]
+        .assert_eq(&token_color_customizations(&t.build("My cool theme")).unwrap());
+    }
+}
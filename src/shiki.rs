@@ -0,0 +1,88 @@
+use crate::proto::{self, ThemeType};
+use crate::{serialize_theme_with_options, Header, SerializeOptions, SerializeThemeError};
+
+const DARK_CSS: &str = "\
+@media (prefers-color-scheme: dark) {
+    .shiki,
+    .shiki span {
+        color: var(--shiki-dark) !important;
+        background-color: var(--shiki-dark-bg) !important;
+        font-style: var(--shiki-dark-font-style) !important;
+        font-weight: var(--shiki-dark-font-weight) !important;
+        text-decoration: var(--shiki-dark-text-decoration) !important;
+    }
+}
+";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DualTheme {
+    pub light: String,
+    pub dark: String,
+    pub css: String,
+}
+
+pub fn dual_theme(
+    light: &proto::Theme,
+    dark: &proto::Theme,
+) -> Result<DualTheme, SerializeThemeError> {
+    Ok(DualTheme {
+        light: theme_json(light, ThemeType::Light)?,
+        dark: theme_json(dark, ThemeType::Dark)?,
+        css: DARK_CSS.to_string(),
+    })
+}
+
+fn theme_json(
+    theme: &proto::Theme,
+    default_type: ThemeType,
+) -> Result<String, SerializeThemeError> {
+    let options = SerializeOptions { header: Header::Omit, ..SerializeOptions::default() };
+
+    if theme.theme_type.is_some() {
+        return serialize_theme_with_options(theme, &options);
+    }
+
+    let theme = proto::Theme { theme_type: Some(default_type), ..theme.clone() };
+    serialize_theme_with_options(&theme, &options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{tm, ThemeBuilder};
+    use expect_test::expect;
+
+    #[test]
+    fn dual_theme_bundle() {
+        let theme = |background, keyword| {
+            let mut t = ThemeBuilder::default();
+            t.w(["editor.background"], background);
+            t.a([tm("keyword")], keyword);
+            t
+        };
+
+        let light = theme(0xFFFFFF, 0x0000FF).build("My cool theme light");
+        let dark = theme(0x1E1E1E, 0x569CD6).build_with_type("My cool theme dark", ThemeType::Dark);
+        let bundle = dual_theme(&light, &dark).unwrap();
+
+        let light: serde_json::Value = serde_json::from_str(&bundle.light).unwrap();
+        let dark: serde_json::Value = serde_json::from_str(&bundle.dark).unwrap();
+        assert_eq!(light["type"], "light");
+        assert_eq!(dark["type"], "dark");
+        assert_eq!(dark["tokenColors"][0]["settings"]["foreground"], "#569CD6");
+
+        expect![[r#"
+            @media (prefers-color-scheme: dark) {
+                .shiki,
+                .shiki span {
+                    color: var(--shiki-dark) !important;
+                    background-color: var(--shiki-dark-bg) !important;
+                    font-style: var(--shiki-dark-font-style) !important;
+                    font-weight: var(--shiki-dark-font-weight) !important;
+                    text-decoration: var(--shiki-dark-text-decoration) !important;
+                }
+            }
+        "#]]
+        .assert_eq(&bundle.css);
+    }
+}
@@ -0,0 +1,284 @@
+use crate::dsl::{self, FontStyle, ThemeBuilder};
+use crate::proto::{self, ThemeType};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ThemeSpec {
+    #[serde(rename = "$schema", default)]
+    pub schema: Option<String>,
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub theme_type: Option<SpecThemeType>,
+    #[serde(default)]
+    pub palette: IndexMap<String, String>,
+    #[serde(default)]
+    pub defaults: Option<TokenDefaults>,
+    #[serde(default)]
+    pub colors: IndexMap<String, Option<String>>,
+    #[serde(default)]
+    pub tokens: Vec<TokenRule>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SpecThemeType {
+    Light,
+    Dark,
+    HcDark,
+    HcLight,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenDefaults {
+    pub foreground: String,
+    pub background: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TokenRule {
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub semantic: Vec<String>,
+    #[serde(default)]
+    pub foreground: Option<String>,
+    #[serde(default)]
+    pub font_style: Option<SpecFontStyle>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SpecFontStyle {
+    Bold,
+    Italic,
+    Underline,
+    Clear,
+}
+
+impl ThemeSpec {
+    pub fn from_json(src: &str) -> Result<Self, SpecError> {
+        serde_json::from_str(src).map_err(SpecError::Parse)
+    }
+
+    #[cfg(feature = "toml")]
+    pub fn from_toml(src: &str) -> Result<Self, SpecError> {
+        toml::from_str(src).map_err(SpecError::ParseToml)
+    }
+
+    pub fn build(&self) -> Result<proto::Theme, SpecError> {
+        let mut t = ThemeBuilder::with_capacity(
+            self.tokens.len(),
+            self.tokens.iter().map(|rule| rule.semantic.len()).sum(),
+            self.colors.len(),
+        );
+
+        if let Some(defaults) = &self.defaults {
+            t.global_token_defaults(
+                self.color("defaults.foreground", &defaults.foreground)?,
+                self.color("defaults.background", &defaults.background)?,
+            );
+        }
+
+        for (key, color) in &self.colors {
+            match color {
+                Some(color) => t.w([key.as_str()], self.color(&format!("colors.{key}"), color)?),
+                None => t.unset([key.as_str()]),
+            }
+        }
+
+        for (idx, rule) in self.tokens.iter().enumerate() {
+            let mut selectors = Vec::with_capacity(rule.scopes.len() + rule.semantic.len());
+            selectors.extend(rule.scopes.iter().cloned().map(dsl::tm));
+
+            for selector in &rule.semantic {
+                let selector = selector.parse::<proto::semantic::Selector>().map_err(|e| {
+                    SpecError::InvalidSelector {
+                        selector: selector.clone(),
+                        message: e.to_string(),
+                    }
+                })?;
+                selectors.push(dsl::Selector::Semantic(selector));
+            }
+
+            let path = format!("tokens[{idx}].foreground");
+            let foreground = rule.foreground.as_ref().map(|c| self.color(&path, c)).transpose()?;
+            let font_style = rule.font_style.map(|font_style| match font_style {
+                SpecFontStyle::Bold => FontStyle::Bold,
+                SpecFontStyle::Italic => FontStyle::Italic,
+                SpecFontStyle::Underline => FontStyle::Underline,
+                SpecFontStyle::Clear => FontStyle::Clear,
+            });
+
+            match (foreground, font_style) {
+                (Some(foreground), Some(font_style)) => t.a(selectors, (foreground, font_style)),
+                (Some(foreground), None) => t.a(selectors, foreground),
+                (None, Some(font_style)) => t.a(selectors, font_style),
+                (None, None) => return Err(SpecError::EmptyTokenRule(idx)),
+            }
+        }
+
+        let theme_type = self.theme_type.map(|theme_type| match theme_type {
+            SpecThemeType::Light => ThemeType::Light,
+            SpecThemeType::Dark => ThemeType::Dark,
+            SpecThemeType::HcDark => ThemeType::HighContrast,
+            SpecThemeType::HcLight => ThemeType::HighContrastLight,
+        });
+
+        Ok(match theme_type {
+            Some(theme_type) => t.build_with_type(&self.name, theme_type),
+            None => t.build(&self.name),
+        })
+    }
+
+    fn color(&self, path: &str, value: &str) -> Result<proto::Color, SpecError> {
+        let invalid = |message: String| SpecError::InvalidColor {
+            path: path.to_string(),
+            value: value.to_string(),
+            message,
+        };
+
+        if value.starts_with('#') {
+            return value.parse().map_err(invalid);
+        }
+
+        match self.palette.get(value) {
+            Some(color) => color.parse().map_err(invalid),
+            None => Err(invalid(format!("no palette entry named `{value}`"))),
+        }
+    }
+}
+
+pub fn json_schema() -> Value {
+    let color = json!({
+        "type": "string",
+        "description": "A `#RGB`, `#RGBA`, `#RRGGBB` or `#RRGGBBAA` color, or the name of a palette entry",
+        "pattern": "^(#([0-9a-fA-F]{3,4}|[0-9a-fA-F]{6}|[0-9a-fA-F]{8})|[^#].*)$",
+    });
+
+    let color_or_null = json!({ "oneOf": [color, { "type": "null" }] });
+    let workbench_colors: serde_json::Map<_, _> =
+        crate::workbench::KEYS.iter().map(|key| (key.to_string(), color_or_null.clone())).collect();
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "mottle theme spec",
+        "type": "object",
+        "required": ["name"],
+        "additionalProperties": false,
+        "properties": {
+            "$schema": { "type": "string" },
+            "name": { "type": "string", "description": "The theme’s display name" },
+            "type": { "enum": ["light", "dark", "hcDark", "hcLight"] },
+            "palette": {
+                "type": "object",
+                "description": "Named colors that can be referenced from `colors`, `defaults` and `tokens`",
+                "additionalProperties": {
+                    "type": "string",
+                    "pattern": "^#([0-9a-fA-F]{3,4}|[0-9a-fA-F]{6}|[0-9a-fA-F]{8})$",
+                },
+            },
+            "defaults": {
+                "type": "object",
+                "required": ["foreground", "background"],
+                "additionalProperties": false,
+                "properties": { "foreground": color, "background": color },
+            },
+            "colors": {
+                "type": "object",
+                "description": "Workbench colors; `null` unsets a color",
+                "properties": workbench_colors,
+                "additionalProperties": color_or_null,
+            },
+            "tokens": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "anyOf": [{ "required": ["foreground"] }, { "required": ["fontStyle"] }],
+                    "properties": {
+                        "scopes": { "type": "array", "items": { "type": "string" } },
+                        "semantic": { "type": "array", "items": { "type": "string" } },
+                        "foreground": color,
+                        "fontStyle": { "enum": ["bold", "italic", "underline", "clear"] },
+                    },
+                },
+            },
+        },
+    })
+}
+
+#[derive(Debug, Error)]
+pub enum SpecError {
+    #[error("failed parsing theme spec")]
+    Parse(#[source] serde_json::Error),
+    #[cfg(feature = "toml")]
+    #[error("failed parsing theme spec")]
+    ParseToml(#[source] toml::de::Error),
+    #[error("invalid color `{value}` at `{path}`: {message}")]
+    InvalidColor { path: String, value: String, message: String },
+    #[error("invalid semantic selector `{selector}`: {message}")]
+    InvalidSelector { selector: String, message: String },
+    #[error("token rule {0} sets neither a foreground nor a font style")]
+    EmptyTokenRule(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{s, tm};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn build_from_json() {
+        let spec = ThemeSpec::from_json(
+            r##"{
+                "$schema": "./mottle.schema.json",
+                "name": "My cool theme",
+                "type": "dark",
+                "palette": { "bg": "#1E1E1E", "blue": "#569CD6" },
+                "defaults": { "foreground": "#D4D4D4", "background": "bg" },
+                "colors": { "editor.background": "bg", "editor.lineHighlightBorder": null },
+                "tokens": [
+                    { "scopes": ["keyword"], "semantic": ["keyword"], "foreground": "blue" },
+                    { "scopes": ["comment"], "fontStyle": "italic" }
+                ]
+            }"##,
+        )
+        .unwrap();
+
+        let mut t = ThemeBuilder::default();
+        t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+        t.w(["editor.background"], 0x1E1E1E);
+        t.unset(["editor.lineHighlightBorder"]);
+        t.a([tm("keyword"), s("keyword")], 0x569CD6);
+        t.a([tm("comment")], FontStyle::Italic);
+
+        assert_eq!(spec.build().unwrap(), t.build_with_type("My cool theme", ThemeType::Dark));
+    }
+
+    #[test]
+    fn unknown_palette_entry() {
+        let spec =
+            ThemeSpec::from_json(r#"{ "name": "x", "colors": { "editor.background": "bg" } }"#)
+                .unwrap();
+
+        assert_eq!(
+            spec.build().unwrap_err().to_string(),
+            "invalid color `bg` at `colors.editor.background`: no palette entry named `bg`"
+        );
+    }
+
+    #[test]
+    fn schema_lists_workbench_keys() {
+        let schema = json_schema();
+        let colors = &schema["properties"]["colors"]["properties"];
+
+        assert!(colors.get("editor.background").is_some());
+    }
+}
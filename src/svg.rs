@@ -0,0 +1,167 @@
+use crate::palette::Palette;
+use crate::proto;
+use indexmap::IndexMap;
+use std::fmt::Write;
+
+const COLUMNS: usize = 6;
+const SWATCH_WIDTH: usize = 140;
+const SWATCH_HEIGHT: usize = 60;
+const LABEL_HEIGHT: usize = 36;
+const TITLE_HEIGHT: usize = 40;
+const GAP: usize = 12;
+const MARGIN: usize = 24;
+
+pub fn swatch_sheet(theme: &proto::Theme, palette: Option<&Palette>) -> String {
+    let mut groups = Vec::new();
+
+    if let Some(palette) = palette.filter(|p| !p.is_empty()) {
+        let swatches = palette.iter().map(|(name, color)| (name.to_string(), color)).collect();
+        groups.push(("Palette", swatches));
+    }
+
+    let syntax: Vec<_> = crate::readme::syntax_groups(theme)
+        .into_iter()
+        .map(|(color, selectors)| (summarize(&selectors), color))
+        .collect();
+    if !syntax.is_empty() {
+        groups.push(("Syntax", syntax));
+    }
+
+    let mut workbench: IndexMap<proto::Color, Vec<String>> = IndexMap::new();
+    for (key, color) in &theme.workbench_rules {
+        if let Some(color) = color {
+            workbench.entry(*color).or_default().push(key.to_string());
+        }
+    }
+    if !workbench.is_empty() {
+        let swatches = workbench.into_iter().map(|(color, keys)| (summarize(&keys), color));
+        groups.push(("Workbench", swatches.collect()));
+    }
+
+    let background = match theme.workbench_rules.get("editor.background") {
+        Some(Some(background)) => *background,
+        _ => proto::Color { r: 0xFF, g: 0xFF, b: 0xFF, a: 0xFF },
+    };
+    let foreground = if background.relative_luminance() > 0.5 { "#000000" } else { "#FFFFFF" };
+
+    let width = MARGIN * 2 + COLUMNS * SWATCH_WIDTH + (COLUMNS - 1) * GAP;
+    let cell_height = SWATCH_HEIGHT + LABEL_HEIGHT + GAP;
+    let height = MARGIN * 2
+        + groups
+            .iter()
+            .map(|(_, swatches)| TITLE_HEIGHT + swatches.len().div_ceil(COLUMNS) * cell_height)
+            .sum::<usize>();
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif">"#
+    )
+    .unwrap();
+    writeln!(out, r#"  <title>{}</title>"#, escape(&theme.name)).unwrap();
+    writeln!(out, r#"  <rect width="100%" height="100%" fill="{}"/>"#, hex(background)).unwrap();
+
+    let mut y = MARGIN;
+    for (title, swatches) in &groups {
+        writeln!(
+            out,
+            r#"  <text x="{MARGIN}" y="{}" font-size="18" font-weight="bold" fill="{foreground}">{title}</text>"#,
+            y + 24
+        )
+        .unwrap();
+        y += TITLE_HEIGHT;
+
+        for (row, chunk) in swatches.chunks(COLUMNS).enumerate() {
+            let row_y = y + row * cell_height;
+
+            for (column, (label, color)) in chunk.iter().enumerate() {
+                let x = MARGIN + column * (SWATCH_WIDTH + GAP);
+                let opacity = if color.a == 0xFF {
+                    String::new()
+                } else {
+                    format!(r#" fill-opacity="{:.3}""#, f64::from(color.a) / 255.0)
+                };
+
+                writeln!(
+                    out,
+                    r#"  <rect x="{x}" y="{row_y}" width="{SWATCH_WIDTH}" height="{SWATCH_HEIGHT}" rx="6" fill="{}"{opacity} stroke="{foreground}" stroke-opacity="0.2"/>"#,
+                    hex(*color)
+                )
+                .unwrap();
+                writeln!(
+                    out,
+                    r#"  <text x="{x}" y="{}" font-size="12" fill="{foreground}">{}</text>"#,
+                    row_y + SWATCH_HEIGHT + 16,
+                    escape(label)
+                )
+                .unwrap();
+                writeln!(
+                    out,
+                    r#"  <text x="{x}" y="{}" font-size="11" font-family="monospace" fill="{foreground}" fill-opacity="0.7">{color}</text>"#,
+                    row_y + SWATCH_HEIGHT + 30
+                )
+                .unwrap();
+            }
+        }
+
+        y += swatches.len().div_ceil(COLUMNS) * cell_height;
+    }
+
+    writeln!(out, "</svg>").unwrap();
+
+    out
+}
+
+fn summarize(names: &[String]) -> String {
+    match names {
+        [] => String::new(),
+        [name] => name.clone(),
+        [first, rest @ ..] => format!("{first} (+{})", rest.len()),
+    }
+}
+
+fn hex(color: proto::Color) -> String {
+    format!("#{:02X}{:02X}{:02X}", color.r, color.g, color.b)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{tm, ThemeBuilder};
+    use expect_test::expect;
+
+    #[test]
+    fn groups_by_usage() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background", "sideBar.background"], 0x1E1E1E);
+        t.w(["editor.foreground"], 0xD4D4D4);
+        t.a([tm("keyword"), tm("storage")], 0xFF5555);
+        t.a([tm("comment")], (0xD4D4D4, 0x80));
+
+        expect![[r##"
+            <svg xmlns="http://www.w3.org/2000/svg" width="948" height="344" viewBox="0 0 948 344" font-family="sans-serif">
+              <title>Dark &amp; moody</title>
+              <rect width="100%" height="100%" fill="#1E1E1E"/>
+              <text x="24" y="48" font-size="18" font-weight="bold" fill="#FFFFFF">Syntax</text>
+              <rect x="24" y="64" width="140" height="60" rx="6" fill="#FF5555" stroke="#FFFFFF" stroke-opacity="0.2"/>
+              <text x="24" y="140" font-size="12" fill="#FFFFFF">keyword (+1)</text>
+              <text x="24" y="154" font-size="11" font-family="monospace" fill="#FFFFFF" fill-opacity="0.7">#FF5555</text>
+              <rect x="176" y="64" width="140" height="60" rx="6" fill="#D4D4D4" fill-opacity="0.502" stroke="#FFFFFF" stroke-opacity="0.2"/>
+              <text x="176" y="140" font-size="12" fill="#FFFFFF">comment</text>
+              <text x="176" y="154" font-size="11" font-family="monospace" fill="#FFFFFF" fill-opacity="0.7">#D4D4D480</text>
+              <text x="24" y="196" font-size="18" font-weight="bold" fill="#FFFFFF">Workbench</text>
+              <rect x="24" y="212" width="140" height="60" rx="6" fill="#1E1E1E" stroke="#FFFFFF" stroke-opacity="0.2"/>
+              <text x="24" y="288" font-size="12" fill="#FFFFFF">editor.background (+1)</text>
+              <text x="24" y="302" font-size="11" font-family="monospace" fill="#FFFFFF" fill-opacity="0.7">#1E1E1E</text>
+              <rect x="176" y="212" width="140" height="60" rx="6" fill="#D4D4D4" stroke="#FFFFFF" stroke-opacity="0.2"/>
+              <text x="176" y="288" font-size="12" fill="#FFFFFF">editor.foreground</text>
+              <text x="176" y="302" font-size="11" font-family="monospace" fill="#FFFFFF" fill-opacity="0.7">#D4D4D4</text>
+            </svg>
+        "##]]
+        .assert_eq(&swatch_sheet(&t.build("Dark & moody"), None));
+    }
+}
@@ -0,0 +1,527 @@
+use crate::oklab::Oklch;
+use crate::proto;
+use std::fmt::Write;
+use thiserror::Error;
+
+pub const ANSI_KEYS: [&str; 16] = [
+    "terminal.ansiBlack",
+    "terminal.ansiRed",
+    "terminal.ansiGreen",
+    "terminal.ansiYellow",
+    "terminal.ansiBlue",
+    "terminal.ansiMagenta",
+    "terminal.ansiCyan",
+    "terminal.ansiWhite",
+    "terminal.ansiBrightBlack",
+    "terminal.ansiBrightRed",
+    "terminal.ansiBrightGreen",
+    "terminal.ansiBrightYellow",
+    "terminal.ansiBrightBlue",
+    "terminal.ansiBrightMagenta",
+    "terminal.ansiBrightCyan",
+    "terminal.ansiBrightWhite",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnsiPalette {
+    pub foreground: proto::Color,
+    pub background: proto::Color,
+    pub normal: [proto::Color; 8],
+    pub bright: [proto::Color; 8],
+}
+
+impl AnsiPalette {
+    pub fn from_theme(theme: &proto::Theme) -> Result<Self, TerminalError> {
+        let color = |key: &'static str| match theme.workbench_rules.get(key) {
+            Some(Some(color)) => Ok(*color),
+            _ => Err(TerminalError::MissingColor(key)),
+        };
+        let with_fallback = |key, fallback| color(key).or_else(|_| color(fallback));
+
+        let black = proto::Color { r: 0, g: 0, b: 0, a: 0xFF };
+        let mut normal = [black; 8];
+        let mut bright = [black; 8];
+
+        for (idx, (normal, bright)) in normal.iter_mut().zip(&mut bright).enumerate() {
+            *normal = color(ANSI_KEYS[idx])?;
+            *bright = color(ANSI_KEYS[idx + 8])?;
+        }
+
+        Ok(Self {
+            foreground: with_fallback("terminal.foreground", "editor.foreground")?,
+            background: with_fallback("terminal.background", "editor.background")?,
+            normal,
+            bright,
+        })
+    }
+}
+
+pub fn foot(theme: &proto::Theme) -> Result<String, TerminalError> {
+    let palette = AnsiPalette::from_theme(theme)?;
+    let mut out = String::new();
+
+    writeln!(out, "# Do not edit directly; this file is generated.").unwrap();
+    writeln!(out, "[colors]").unwrap();
+    writeln!(out, "foreground={}", hex(palette.foreground)).unwrap();
+    writeln!(out, "background={}", hex(palette.background)).unwrap();
+
+    for (idx, color) in palette.normal.iter().enumerate() {
+        writeln!(out, "regular{idx}={}", hex(*color)).unwrap();
+    }
+
+    for (idx, color) in palette.bright.iter().enumerate() {
+        writeln!(out, "bright{idx}={}", hex(*color)).unwrap();
+    }
+
+    Ok(out)
+}
+
+pub fn konsole(theme: &proto::Theme) -> Result<String, TerminalError> {
+    let palette = AnsiPalette::from_theme(theme)?;
+    let mut out = String::new();
+
+    writeln!(out, "# Do not edit directly; this file is generated.").unwrap();
+    konsole_section(&mut out, "Background", palette.background, None);
+    for (idx, (normal, bright)) in palette.normal.iter().zip(palette.bright).enumerate() {
+        konsole_section(&mut out, &format!("Color{idx}"), *normal, Some(bright));
+    }
+    konsole_section(&mut out, "Foreground", palette.foreground, None);
+
+    writeln!(out, "\n[General]").unwrap();
+    writeln!(out, "Description={}", theme.name).unwrap();
+    writeln!(out, "Opacity={}", f64::from(palette.background.a) / 255.0).unwrap();
+
+    Ok(out)
+}
+
+pub fn gnome_terminal(theme: &proto::Theme) -> Result<String, TerminalError> {
+    let palette = AnsiPalette::from_theme(theme)?;
+    let color = |color| format!("'#{}'", hex(color));
+    let mut out = String::new();
+
+    writeln!(out, "# Do not edit directly; this file is generated.").unwrap();
+    writeln!(out, "[/]").unwrap();
+    writeln!(out, "visible-name='{}'", theme.name.replace('\\', "\\\\").replace('\'', "\\'"))
+        .unwrap();
+    writeln!(out, "use-theme-colors=false").unwrap();
+    writeln!(out, "foreground-color={}", color(palette.foreground)).unwrap();
+    writeln!(out, "background-color={}", color(palette.background)).unwrap();
+    writeln!(out, "bold-color={}", color(palette.foreground)).unwrap();
+    writeln!(out, "bold-color-same-as-fg=true").unwrap();
+
+    let colors: Vec<_> = palette.normal.iter().chain(&palette.bright).map(|c| color(*c)).collect();
+    writeln!(out, "palette=[{}]", colors.join(", ")).unwrap();
+
+    Ok(out)
+}
+
+const STARSHIP_ROLES: &[(&str, &[&str])] = &[
+    ("accent", &["focusBorder", "button.background", "terminal.ansiBlue"]),
+    ("error", &["errorForeground", "editorError.foreground", "terminal.ansiRed"]),
+    ("success", &["terminal.ansiGreen", "gitDecoration.addedResourceForeground"]),
+    ("warning", &["editorWarning.foreground", "terminal.ansiYellow"]),
+    ("muted", &["descriptionForeground", "terminal.ansiBrightBlack"]),
+];
+
+const STARSHIP_MODULES: &[(&str, &str, &str)] = &[
+    ("character", "success_symbol", "[❯](bold success)"),
+    ("character", "error_symbol", "[❯](bold error)"),
+    ("cmd_duration", "style", "warning"),
+    ("directory", "style", "bold accent"),
+    ("git_branch", "style", "muted"),
+    ("git_status", "style", "warning"),
+    ("status", "style", "bold error"),
+];
+
+const ANSI_NAMES: [&str; 8] =
+    ["black", "red", "green", "yellow", "blue", "purple", "cyan", "white"];
+
+pub fn starship(theme: &proto::Theme) -> Result<String, TerminalError> {
+    let name = crate::slugify(&theme.name).unwrap_or_else(|| "mottle".to_string());
+    let mut out = String::new();
+
+    writeln!(out, "# Do not edit directly; this file is generated.").unwrap();
+    writeln!(out, "palette = {name:?}").unwrap();
+    writeln!(out, "\n[palettes.{name}]").unwrap();
+
+    for (role, keys) in STARSHIP_ROLES {
+        let color = keys
+            .iter()
+            .find_map(|key| theme.workbench_rules.get(*key).copied().flatten())
+            .ok_or(TerminalError::MissingColor(keys[0]))?;
+        writeln!(out, "{role} = \"#{}\"", hex(color)).unwrap();
+    }
+
+    if let Ok(palette) = AnsiPalette::from_theme(theme) {
+        for (name, color) in ANSI_NAMES.iter().zip(palette.normal) {
+            writeln!(out, "{name} = \"#{}\"", hex(color)).unwrap();
+        }
+        for (name, color) in ANSI_NAMES.iter().zip(palette.bright) {
+            writeln!(out, "bright_{name} = \"#{}\"", hex(color)).unwrap();
+        }
+    }
+
+    let mut current = None;
+    for (module, key, style) in STARSHIP_MODULES {
+        if current != Some(module) {
+            writeln!(out, "\n[{module}]").unwrap();
+            current = Some(module);
+        }
+        writeln!(out, "{key} = {style:?}").unwrap();
+    }
+
+    Ok(out)
+}
+
+const HYPER_NAMES: [&str; 8] =
+    ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+pub fn hyper(theme: &proto::Theme) -> Result<String, TerminalError> {
+    let palette = AnsiPalette::from_theme(theme)?;
+    let mut out = String::new();
+
+    writeln!(out, "// Do not edit directly; this file is generated.").unwrap();
+    writeln!(out, "module.exports = {{").unwrap();
+    writeln!(out, "  config: {{").unwrap();
+    writeln!(out, "    foregroundColor: '#{}',", hex(palette.foreground)).unwrap();
+    writeln!(out, "    backgroundColor: '#{}',", hex(palette.background)).unwrap();
+
+    let optional = [
+        ("cursorColor", "terminalCursor.foreground"),
+        ("selectionColor", "terminal.selectionBackground"),
+    ];
+    for (name, key) in optional {
+        if let Some(Some(color)) = theme.workbench_rules.get(key) {
+            writeln!(out, "    {name}: '{}',", color.to_string().to_lowercase()).unwrap();
+        }
+    }
+
+    writeln!(out, "    colors: {{").unwrap();
+    for (name, color) in HYPER_NAMES.iter().zip(palette.normal) {
+        writeln!(out, "      {name}: '#{}',", hex(color)).unwrap();
+    }
+    for (name, color) in HYPER_NAMES.iter().zip(palette.bright) {
+        let (first, rest) = name.split_at(1);
+        writeln!(out, "      light{}{rest}: '#{}',", first.to_uppercase(), hex(color)).unwrap();
+    }
+    writeln!(out, "    }},").unwrap();
+    writeln!(out, "  }},").unwrap();
+    writeln!(out, "}};").unwrap();
+
+    Ok(out)
+}
+
+fn konsole_section(
+    out: &mut String,
+    name: &str,
+    color: proto::Color,
+    intense: Option<proto::Color>,
+) {
+    let intense = intense.unwrap_or_else(|| shift_lightness(color, INTENSE_SHIFT));
+    let faint = shift_lightness(color, FAINT_SHIFT);
+
+    for (suffix, color) in [("", color), ("Faint", faint), ("Intense", intense)] {
+        writeln!(out, "\n[{name}{suffix}]").unwrap();
+        writeln!(out, "Color={},{},{}", color.r, color.g, color.b).unwrap();
+    }
+}
+
+const INTENSE_SHIFT: f64 = 0.1;
+const FAINT_SHIFT: f64 = -0.15;
+
+fn shift_lightness(color: proto::Color, delta: f64) -> proto::Color {
+    let oklch = Oklch::from(color);
+    Oklch { l: oklch.l + delta, ..oklch }.to_color(color.a)
+}
+
+fn hex(color: proto::Color) -> String {
+    format!("{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+#[derive(Debug, Error)]
+pub enum TerminalError {
+    #[error("theme does not set `{0}`")]
+    MissingColor(&'static str),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::ThemeBuilder;
+    use expect_test::expect;
+
+    fn theme() -> proto::Theme {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.foreground"], 0xCCCCCC);
+        t.w(["terminal.background"], 0x1E1E1E);
+        t.w(["terminal.ansiBlack"], 0x000000);
+        t.w(["terminal.ansiRed"], 0xCD3131);
+        t.w(["terminal.ansiGreen"], 0x0DBC79);
+        t.w(["terminal.ansiYellow"], 0xE5E510);
+        t.w(["terminal.ansiBlue"], 0x2472C8);
+        t.w(["terminal.ansiMagenta"], 0xBC3FBC);
+        t.w(["terminal.ansiCyan"], 0x11A8CD);
+        t.w(["terminal.ansiWhite"], 0xE5E5E5);
+        t.w(["terminal.ansiBrightBlack"], 0x666666);
+        t.w(["terminal.ansiBrightRed"], 0xF14C4C);
+        t.w(["terminal.ansiBrightGreen"], 0x23D18B);
+        t.w(["terminal.ansiBrightYellow"], 0xF5F543);
+        t.w(["terminal.ansiBrightBlue"], 0x3B8EEA);
+        t.w(["terminal.ansiBrightMagenta"], 0xD670D6);
+        t.w(["terminal.ansiBrightCyan"], 0x29B8DB);
+        t.w(["terminal.ansiBrightWhite"], 0xE5E5E5);
+        t.build("My cool theme")
+    }
+
+    #[test]
+    fn missing_color() {
+        let mut theme = theme();
+        theme.workbench_rules.shift_remove("terminal.ansiCyan");
+
+        expect!["theme does not set `terminal.ansiCyan`"]
+            .assert_eq(&foot(&theme).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn konsole_colorscheme() {
+        expect![[r#"
+            # Do not edit directly; this file is generated.
+
+            [Background]
+            Color=30,30,30
+
+            [BackgroundFaint]
+            Color=2,2,2
+
+            [BackgroundIntense]
+            Color=55,55,55
+
+            [Color0]
+            Color=0,0,0
+
+            [Color0Faint]
+            Color=0,0,0
+
+            [Color0Intense]
+            Color=102,102,102
+
+            [Color1]
+            Color=205,49,49
+
+            [Color1Faint]
+            Color=143,0,15
+
+            [Color1Intense]
+            Color=241,76,76
+
+            [Color2]
+            Color=13,188,121
+
+            [Color2Faint]
+            Color=0,136,86
+
+            [Color2Intense]
+            Color=35,209,139
+
+            [Color3]
+            Color=229,229,16
+
+            [Color3Faint]
+            Color=179,179,0
+
+            [Color3Intense]
+            Color=245,245,67
+
+            [Color4]
+            Color=36,114,200
+
+            [Color4Faint]
+            Color=0,71,139
+
+            [Color4Intense]
+            Color=59,142,234
+
+            [Color5]
+            Color=188,63,188
+
+            [Color5Faint]
+            Color=136,0,138
+
+            [Color5Intense]
+            Color=214,112,214
+
+            [Color6]
+            Color=17,168,205
+
+            [Color6Faint]
+            Color=0,119,147
+
+            [Color6Intense]
+            Color=41,184,219
+
+            [Color7]
+            Color=229,229,229
+
+            [Color7Faint]
+            Color=181,181,181
+
+            [Color7Intense]
+            Color=229,229,229
+
+            [Foreground]
+            Color=204,204,204
+
+            [ForegroundFaint]
+            Color=157,157,157
+
+            [ForegroundIntense]
+            Color=237,237,237
+
+            [General]
+            Description=My cool theme
+            Opacity=1
+        "#]]
+        .assert_eq(&konsole(&theme()).unwrap());
+    }
+
+    #[test]
+    fn gnome_terminal_profile() {
+        let mut theme = theme();
+        theme.name = "Jane's theme".to_string();
+
+        expect![[r#"
+            # Do not edit directly; this file is generated.
+            [/]
+            visible-name='Jane\'s theme'
+            use-theme-colors=false
+            foreground-color='#cccccc'
+            background-color='#1e1e1e'
+            bold-color='#cccccc'
+            bold-color-same-as-fg=true
+            palette=['#000000', '#cd3131', '#0dbc79', '#e5e510', '#2472c8', '#bc3fbc', '#11a8cd', '#e5e5e5', '#666666', '#f14c4c', '#23d18b', '#f5f543', '#3b8eea', '#d670d6', '#29b8db', '#e5e5e5']
+        "#]].assert_eq(&gnome_terminal(&theme).unwrap());
+    }
+
+    #[test]
+    fn starship_palette() {
+        let mut theme = theme();
+        theme.workbench_rules.insert(
+            "focusBorder".into(),
+            Some(proto::Color { r: 0x00, g: 0x7A, b: 0xCC, a: 0xFF }),
+        );
+
+        expect![[r##"
+            # Do not edit directly; this file is generated.
+            palette = "my-cool-theme"
+
+            [palettes.my-cool-theme]
+            accent = "#007acc"
+            error = "#cd3131"
+            success = "#0dbc79"
+            warning = "#e5e510"
+            muted = "#666666"
+            black = "#000000"
+            red = "#cd3131"
+            green = "#0dbc79"
+            yellow = "#e5e510"
+            blue = "#2472c8"
+            purple = "#bc3fbc"
+            cyan = "#11a8cd"
+            white = "#e5e5e5"
+            bright_black = "#666666"
+            bright_red = "#f14c4c"
+            bright_green = "#23d18b"
+            bright_yellow = "#f5f543"
+            bright_blue = "#3b8eea"
+            bright_purple = "#d670d6"
+            bright_cyan = "#29b8db"
+            bright_white = "#e5e5e5"
+
+            [character]
+            success_symbol = "[❯](bold success)"
+            error_symbol = "[❯](bold error)"
+
+            [cmd_duration]
+            style = "warning"
+
+            [directory]
+            style = "bold accent"
+
+            [git_branch]
+            style = "muted"
+
+            [git_status]
+            style = "warning"
+
+            [status]
+            style = "bold error"
+        "##]]
+        .assert_eq(&starship(&theme).unwrap());
+    }
+
+    #[test]
+    fn hyper_config() {
+        let mut theme = theme();
+        theme.workbench_rules.insert(
+            "terminal.selectionBackground".into(),
+            Some(proto::Color { r: 0x26, g: 0x4F, b: 0x78, a: 0x80 }),
+        );
+
+        expect![[r#"
+            // Do not edit directly; this file is generated.
+            module.exports = {
+              config: {
+                foregroundColor: '#cccccc',
+                backgroundColor: '#1e1e1e',
+                selectionColor: '#264f7880',
+                colors: {
+                  black: '#000000',
+                  red: '#cd3131',
+                  green: '#0dbc79',
+                  yellow: '#e5e510',
+                  blue: '#2472c8',
+                  magenta: '#bc3fbc',
+                  cyan: '#11a8cd',
+                  white: '#e5e5e5',
+                  lightBlack: '#666666',
+                  lightRed: '#f14c4c',
+                  lightGreen: '#23d18b',
+                  lightYellow: '#f5f543',
+                  lightBlue: '#3b8eea',
+                  lightMagenta: '#d670d6',
+                  lightCyan: '#29b8db',
+                  lightWhite: '#e5e5e5',
+                },
+              },
+            };
+        "#]]
+        .assert_eq(&hyper(&theme).unwrap());
+    }
+
+    #[test]
+    fn foot_colors() {
+        expect![[r#"
+            # Do not edit directly; this file is generated.
+            [colors]
+            foreground=cccccc
+            background=1e1e1e
+            regular0=000000
+            regular1=cd3131
+            regular2=0dbc79
+            regular3=e5e510
+            regular4=2472c8
+            regular5=bc3fbc
+            regular6=11a8cd
+            regular7=e5e5e5
+            bright0=666666
+            bright1=f14c4c
+            bright2=23d18b
+            bright3=f5f543
+            bright4=3b8eea
+            bright5=d670d6
+            bright6=29b8db
+            bright7=e5e5e5
+        "#]]
+        .assert_eq(&foot(&theme()).unwrap());
+    }
+}
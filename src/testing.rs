@@ -0,0 +1,208 @@
+use crate::{proto, serialize_theme};
+
+#[cfg(feature = "std-fs")]
+use crate::diff;
+#[cfg(feature = "std-fs")]
+use std::fs;
+#[cfg(feature = "std-fs")]
+use std::path::Path;
+
+#[cfg(feature = "testing")]
+pub use expect_test::{expect, expect_file, Expect, ExpectFile};
+
+#[cfg(feature = "std-fs")]
+const UPDATE_VAR: &str = "MOTTLE_UPDATE_GOLDEN";
+
+#[cfg(feature = "testing")]
+pub fn check(theme: &proto::Theme, expect: Expect) {
+    check_with_options(theme, &crate::SerializeOptions::default(), expect);
+}
+
+#[cfg(feature = "testing")]
+pub fn check_with_options(theme: &proto::Theme, options: &crate::SerializeOptions, expect: Expect) {
+    expect.assert_eq(&crate::serialize_theme_with_options(theme, options).unwrap());
+}
+
+#[cfg(feature = "testing")]
+pub fn check_workbench(theme: &proto::Theme, expect: Expect) {
+    expect.assert_eq(&fragment(theme, "colors"));
+}
+
+#[cfg(feature = "testing")]
+pub fn check_token_colors(theme: &proto::Theme, expect: Expect) {
+    expect.assert_eq(&fragment(theme, "tokenColors"));
+}
+
+#[cfg(feature = "testing")]
+pub fn check_semantic_token_colors(theme: &proto::Theme, expect: Expect) {
+    expect.assert_eq(&fragment(theme, "semanticTokenColors"));
+}
+
+#[cfg(feature = "testing")]
+fn fragment(theme: &proto::Theme, key: &str) -> String {
+    use serde::Serialize;
+
+    let serialized = serialize_theme(theme).unwrap();
+    let value: serde_json::Value =
+        serde_json::from_str(&crate::parse::strip_jsonc(&serialized)).unwrap();
+
+    let mut out = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+    value.get(key).unwrap_or(&serde_json::Value::Null).serialize(&mut serializer).unwrap();
+    out.push(b'\n');
+
+    String::from_utf8(out).unwrap()
+}
+
+#[cfg(feature = "std-fs")]
+#[track_caller]
+pub fn assert_theme_matches_file(theme: &proto::Theme, path: impl AsRef<Path>) {
+    let update = std::env::var_os(UPDATE_VAR).is_some();
+    assert_matches(theme, path.as_ref(), update);
+}
+
+#[cfg(feature = "std-fs")]
+#[track_caller]
+fn assert_matches(theme: &proto::Theme, path: &Path, update: bool) {
+    let actual = match serialize_theme(theme) {
+        Ok(actual) => actual,
+        Err(e) => panic!("failed serializing theme `{}`: {e}", theme.name),
+    };
+
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => Some(expected.replace("\r\n", "\n")),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => panic!("failed reading golden file `{}`: {e}", path.display()),
+    };
+
+    if expected.as_deref() == Some(actual.as_str()) {
+        return;
+    }
+
+    if update {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(path, actual).unwrap();
+        return;
+    }
+
+    match expected {
+        Some(expected) => {
+            let label = path.display().to_string();
+            panic!(
+                "theme `{}` does not match golden file `{label}`; rerun with {UPDATE_VAR}=1 to update it\n\n{}",
+                theme.name,
+                diff::unified(&expected, &actual, &label, &theme.name)
+            )
+        }
+        None => panic!(
+            "golden file `{}` does not exist; rerun with {UPDATE_VAR}=1 to create it",
+            path.display()
+        ),
+    }
+}
+
+#[cfg(all(test, feature = "std-fs"))]
+mod tests {
+    use super::*;
+    use crate::dsl::ThemeBuilder;
+    use crate::tests::temp_dir;
+    use expect_test::expect;
+    use std::panic;
+
+    fn theme(background: u32) -> proto::Theme {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], background);
+        t.build("My cool theme")
+    }
+
+    fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+        let payload = panic::catch_unwind(f).unwrap_err();
+        payload.downcast_ref::<String>().cloned().unwrap()
+    }
+
+    #[test]
+    fn create_compare_update() {
+        let dir = temp_dir("golden");
+        let path = dir.join("my-cool-theme.json");
+
+        let message = panic_message({
+            let path = path.clone();
+            move || assert_matches(&theme(0x111111), &path, false)
+        });
+        assert!(message.starts_with("golden file"));
+        assert!(message.ends_with("does not exist; rerun with MOTTLE_UPDATE_GOLDEN=1 to create it"));
+
+        assert_matches(&theme(0x111111), &path, true);
+        assert_matches(&theme(0x111111), &path, false);
+
+        let message = panic_message({
+            let path = path.clone();
+            move || assert_matches(&theme(0x222222), &path, false)
+        });
+        let diff = message.split_once("\n\n").unwrap().1;
+        let hunk = &diff[diff.find("@@").unwrap()..];
+        assert!(diff.starts_with("--- "));
+        expect![[r##"
+            @@ -5,6 +5,6 @@
+                 "semanticHighlighting": true,
+                 "semanticTokenColors": {},
+                 "colors": {
+            -        "editor.background": "#111111"
+            +        "editor.background": "#222222"
+                 }
+             }
+        "##]]
+        .assert_eq(hunk);
+
+        assert_matches(&theme(0x222222), &path, true);
+        assert_matches(&theme(0x222222), &path, false);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn fragments() {
+        use crate::dsl::{s, tm};
+
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x111111);
+        t.a([tm("keyword"), s("keyword")], 0xFF0000);
+        let theme = t.build("My cool theme");
+
+        check_workbench(
+            &theme,
+            expect![[r##"
+            {
+                "editor.background": "#111111"
+            }
+        "##]],
+        );
+        check_token_colors(
+            &theme,
+            expect![[r##"
+            [
+                {
+                    "scope": [
+                        "keyword"
+                    ],
+                    "settings": {
+                        "foreground": "#FF0000"
+                    }
+                }
+            ]
+        "##]],
+        );
+        check_semantic_token_colors(
+            &theme,
+            expect![[r##"
+            {
+                "keyword": {
+                    "foreground": "#FF0000"
+                }
+            }
+        "##]],
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use crate::proto::{self, textmate};
+use crate::sample;
+use std::io;
+use thiserror::Error;
+
+const WIDTH: usize = 480;
+const HEIGHT: usize = 270;
+const CHAR_WIDTH: usize = 6;
+const LINE_HEIGHT: usize = 20;
+const TOP: usize = 24;
+const GUTTER: usize = 48;
+const BAR_HEIGHT: usize = 8;
+
+pub fn render_png(theme: &proto::Theme, writer: impl io::Write) -> Result<(), ThumbnailError> {
+    let canvas = render(theme);
+
+    let mut encoder = png::Encoder::new(writer, WIDTH as u32, HEIGHT as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header().map_err(ThumbnailError::Encode)?;
+    writer.write_image_data(&canvas.pixels).map_err(ThumbnailError::Encode)?;
+    writer.finish().map_err(ThumbnailError::Encode)
+}
+
+struct Canvas {
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(background: proto::Color) -> Self {
+        let pixels = [background.r, background.g, background.b].repeat(WIDTH * HEIGHT);
+        Self { pixels }
+    }
+
+    fn fill(&mut self, x: usize, y: usize, width: usize, height: usize, color: proto::Color) {
+        for row in y..(y + height).min(HEIGHT) {
+            for column in x..(x + width).min(WIDTH) {
+                let idx = (row * WIDTH + column) * 3;
+                let pixel = &mut self.pixels[idx..idx + 3];
+                let below = proto::Color { r: pixel[0], g: pixel[1], b: pixel[2], a: 0xFF };
+                let blended = color.over(below);
+                pixel.copy_from_slice(&[blended.r, blended.g, blended.b]);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    fn get(&self, x: usize, y: usize) -> proto::Color {
+        let idx = (y * WIDTH + x) * 3;
+        let p = &self.pixels[idx..idx + 3];
+        proto::Color { r: p[0], g: p[1], b: p[2], a: 0xFF }
+    }
+}
+
+fn render(theme: &proto::Theme) -> Canvas {
+    let workbench = |key: &str| match theme.workbench_rules.get(key) {
+        Some(Some(color)) => Some(*color),
+        _ => None,
+    };
+    let defaults = theme.global_token_defaults.unwrap_or_default();
+
+    let background = workbench("editor.background")
+        .or(defaults.background)
+        .unwrap_or(proto::Color { r: 0x1E, g: 0x1E, b: 0x1E, a: 0xFF });
+    let foreground = workbench("editor.foreground")
+        .or(defaults.foreground)
+        .unwrap_or(proto::Color { r: 0xD4, g: 0xD4, b: 0xD4, a: 0xFF });
+    let line_number =
+        workbench("editorLineNumber.foreground").unwrap_or(proto::Color { a: 0x60, ..foreground });
+
+    let mut canvas = Canvas::new(background);
+    let bar_offset = (LINE_HEIGHT - BAR_HEIGHT) / 2;
+
+    for (idx, line) in sample::LINES.iter().enumerate() {
+        let y = TOP + idx * LINE_HEIGHT;
+
+        if idx == sample::HIGHLIGHTED_LINE {
+            if let Some(highlight) = workbench("editor.lineHighlightBackground") {
+                canvas.fill(GUTTER, y, WIDTH - GUTTER, LINE_HEIGHT, highlight);
+            }
+        }
+
+        if idx == sample::SELECTED_LINE {
+            if let Some(selection) = workbench("editor.selectionBackground") {
+                let x = GUTTER + sample::SELECTION.start * CHAR_WIDTH;
+                canvas.fill(x, y, sample::SELECTION.len() * CHAR_WIDTH, LINE_HEIGHT, selection);
+            }
+        }
+
+        let digits = (idx + 1).to_string().len();
+        let number_x = GUTTER - 12 - digits * CHAR_WIDTH;
+        canvas.fill(number_x, y + bar_offset, digits * CHAR_WIDTH - 1, BAR_HEIGHT, line_number);
+
+        let mut x = GUTTER;
+        for (text, scope) in *line {
+            let color = if scope.is_empty() {
+                foreground
+            } else {
+                textmate::resolve_foreground(&theme.textmate_rules, &[sample::ROOT_SCOPE, scope])
+                    .unwrap_or(foreground)
+            };
+
+            for word in split_words(text) {
+                let start = x + word.start * CHAR_WIDTH;
+                canvas.fill(start, y + bar_offset, word.len() * CHAR_WIDTH - 1, BAR_HEIGHT, color);
+            }
+
+            x += text.len() * CHAR_WIDTH;
+        }
+    }
+
+    canvas
+}
+
+fn split_words(text: &str) -> impl Iterator<Item = std::ops::Range<usize>> + '_ {
+    let mut start = None;
+
+    text.char_indices().chain(std::iter::once((text.len(), ' '))).filter_map(
+        move |(idx, c)| match (c.is_whitespace(), start) {
+            (false, None) => {
+                start = Some(idx);
+                None
+            }
+            (true, Some(s)) => {
+                start = None;
+                Some(s..idx)
+            }
+            _ => None,
+        },
+    )
+}
+
+#[derive(Debug, Error)]
+pub enum ThumbnailError {
+    #[error("failed encoding PNG")]
+    Encode(#[source] png::EncodingError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{tm, ThemeBuilder};
+
+    fn color(rgb: u32) -> proto::Color {
+        let [_, r, g, b] = rgb.to_be_bytes();
+        proto::Color { r, g, b, a: 0xFF }
+    }
+
+    #[test]
+    fn draws_tokens_with_theme_colors() {
+        let mut t = ThemeBuilder::default();
+        t.w(["editor.background"], 0x101010);
+        t.w(["editor.foreground"], 0xEEEEEE);
+        t.w(["editor.selectionBackground"], 0x0000FF);
+        t.a([tm("comment")], 0x00FF00);
+        t.a([tm("keyword.control")], 0xFF0000);
+        let canvas = render(&t.build("My cool theme"));
+
+        let bar_y = |line: usize| TOP + line * LINE_HEIGHT + LINE_HEIGHT / 2;
+
+        assert_eq!(canvas.get(0, 0), color(0x101010));
+        assert_eq!(canvas.get(GUTTER + 1, bar_y(0)), color(0x00FF00));
+        assert_eq!(canvas.get(GUTTER + 4 * CHAR_WIDTH + 1, bar_y(3)), color(0xFF0000));
+        assert_eq!(canvas.get(GUTTER + 8 * CHAR_WIDTH + 1, bar_y(3)), color(0xEEEEEE));
+        assert_eq!(canvas.get(GUTTER + 17 * CHAR_WIDTH, TOP + 4 * LINE_HEIGHT), color(0x0000FF));
+    }
+
+    #[test]
+    fn encodes_png() {
+        let mut png = Vec::new();
+        render_png(&ThemeBuilder::default().build("My cool theme"), &mut png).unwrap();
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
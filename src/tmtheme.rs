@@ -0,0 +1,155 @@
+use crate::proto::{self, textmate};
+use std::fmt::Write;
+
+const GLOBAL_SETTINGS: &[(&str, &str)] = &[
+    ("caret", "editorCursor.foreground"),
+    ("selection", "editor.selectionBackground"),
+    ("lineHighlight", "editor.lineHighlightBackground"),
+    ("gutterForeground", "editorLineNumber.foreground"),
+    ("findHighlight", "editor.findMatchHighlightBackground"),
+    ("invisibles", "editorWhitespace.foreground"),
+];
+
+pub fn tm_theme(theme: &proto::Theme) -> String {
+    let mut out = String::new();
+
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(
+        out,
+        r#"<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">"#
+    )
+    .unwrap();
+    writeln!(out, "<!-- Do not edit directly; this file is generated. -->").unwrap();
+    writeln!(out, r#"<plist version="1.0">"#).unwrap();
+    writeln!(out, "<dict>").unwrap();
+    writeln!(out, "\t<key>name</key>").unwrap();
+    writeln!(out, "\t<string>{}</string>", escape(&theme.name)).unwrap();
+    writeln!(out, "\t<key>settings</key>").unwrap();
+    writeln!(out, "\t<array>").unwrap();
+
+    let defaults = theme.global_token_defaults.unwrap_or_default();
+    let workbench = |key: &str| theme.workbench_rules.get(key).copied().flatten();
+    let mut globals = vec![
+        ("foreground", defaults.foreground.or_else(|| workbench("editor.foreground"))),
+        ("background", defaults.background.or_else(|| workbench("editor.background"))),
+    ];
+    globals.extend(GLOBAL_SETTINGS.iter().map(|(name, key)| (*name, workbench(key))));
+
+    writeln!(out, "\t\t<dict>").unwrap();
+    writeln!(out, "\t\t\t<key>settings</key>").unwrap();
+    writeln!(out, "\t\t\t<dict>").unwrap();
+    for (name, color) in globals {
+        if let Some(color) = color {
+            entry(&mut out, name, &color.to_string());
+        }
+    }
+    writeln!(out, "\t\t\t</dict>").unwrap();
+    writeln!(out, "\t\t</dict>").unwrap();
+
+    for rule in &theme.textmate_rules {
+        writeln!(out, "\t\t<dict>").unwrap();
+        writeln!(out, "\t\t\t<key>scope</key>").unwrap();
+        writeln!(out, "\t\t\t<string>{}</string>", escape(&rule.scope.join(", "))).unwrap();
+        writeln!(out, "\t\t\t<key>settings</key>").unwrap();
+        writeln!(out, "\t\t\t<dict>").unwrap();
+        if let Some(foreground) = rule.settings.foreground {
+            entry(&mut out, "foreground", &foreground.to_string());
+        }
+        if let textmate::FontStyle::Set { bold, italic, underline } = rule.settings.font_style {
+            let styles = [(italic, "italic"), (bold, "bold"), (underline, "underline")];
+            let styles: Vec<_> =
+                styles.iter().filter(|(set, _)| *set).map(|(_, style)| *style).collect();
+            entry(&mut out, "fontStyle", &styles.join(" "));
+        }
+        writeln!(out, "\t\t\t</dict>").unwrap();
+        writeln!(out, "\t\t</dict>").unwrap();
+    }
+
+    writeln!(out, "\t</array>").unwrap();
+    writeln!(out, "</dict>").unwrap();
+    writeln!(out, "</plist>").unwrap();
+
+    out
+}
+
+fn entry(out: &mut String, key: &str, value: &str) {
+    writeln!(out, "\t\t\t\t<key>{key}</key>").unwrap();
+    writeln!(out, "\t\t\t\t<string>{}</string>", escape(value)).unwrap();
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{tm, FontStyle, ThemeBuilder};
+    use expect_test::expect;
+
+    #[test]
+    fn plist() {
+        let mut t = ThemeBuilder::default();
+        t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+        t.w(["editorCursor.foreground"], 0xAEAFAD);
+        t.a([tm("keyword"), tm("storage")], (0x569CD6, FontStyle::Bold));
+        t.a([tm("comment")], FontStyle::Italic);
+        t.a([tm("markup.bold")], FontStyle::Clear);
+        let theme = t.build("Code & Coffee");
+
+        expect![[r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+            <!-- Do not edit directly; this file is generated. -->
+            <plist version="1.0">
+            <dict>
+            	<key>name</key>
+            	<string>Code &amp; Coffee</string>
+            	<key>settings</key>
+            	<array>
+            		<dict>
+            			<key>settings</key>
+            			<dict>
+            				<key>foreground</key>
+            				<string>#D4D4D4</string>
+            				<key>background</key>
+            				<string>#1E1E1E</string>
+            				<key>caret</key>
+            				<string>#AEAFAD</string>
+            			</dict>
+            		</dict>
+            		<dict>
+            			<key>scope</key>
+            			<string>keyword, storage</string>
+            			<key>settings</key>
+            			<dict>
+            				<key>foreground</key>
+            				<string>#569CD6</string>
+            				<key>fontStyle</key>
+            				<string>bold</string>
+            			</dict>
+            		</dict>
+            		<dict>
+            			<key>scope</key>
+            			<string>comment</string>
+            			<key>settings</key>
+            			<dict>
+            				<key>fontStyle</key>
+            				<string>italic</string>
+            			</dict>
+            		</dict>
+            		<dict>
+            			<key>scope</key>
+            			<string>markup.bold</string>
+            			<key>settings</key>
+            			<dict>
+            				<key>fontStyle</key>
+            				<string></string>
+            			</dict>
+            		</dict>
+            	</array>
+            </dict>
+            </plist>
+        "#]].assert_eq(&tm_theme(&theme));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::oklab::Oklch;
+use crate::palette::Palette;
+use crate::{preview, proto};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::{cursor, execute, queue, terminal};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+const LIGHTNESS_STEP: f64 = 0.01;
+const CHROMA_STEP: f64 = 0.005;
+const HUE_STEP: f64 = 2.0;
+
+pub fn run(
+    palette: &Palette,
+    build: impl Fn(&Palette) -> proto::Theme,
+) -> io::Result<Option<Palette>> {
+    let mut tweaker = Tweaker::new(palette);
+    let mut stdout = io::stdout();
+
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let result = (|| loop {
+        queue!(stdout, cursor::MoveTo(0, 0), terminal::Clear(terminal::ClearType::All))?;
+        tweaker.render(&mut stdout, &build)?;
+        stdout.flush()?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+
+            match tweaker.handle(key) {
+                Action::Continue => {}
+                Action::Save => return Ok(Some(tweaker.palette())),
+                Action::Quit => return Ok(None),
+            }
+        }
+    })();
+
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+
+    result
+}
+
+pub fn to_rust(palette: &Palette) -> String {
+    let mut out = String::new();
+
+    for (name, color) in palette.iter() {
+        let rgb = format!("0x{:02X}{:02X}{:02X}", color.r, color.g, color.b);
+
+        if color.a == 0xFF {
+            writeln!(out, "palette.insert({name:?}, {rgb});").unwrap();
+        } else {
+            writeln!(out, "palette.insert({name:?}, ({rgb}, 0x{:02X}));", color.a).unwrap();
+        }
+    }
+
+    out
+}
+
+pub fn to_toml(palette: &Palette) -> String {
+    let mut out = String::new();
+
+    for (name, color) in palette.iter() {
+        writeln!(out, "{name:?} = \"{color}\"").unwrap();
+    }
+
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Continue,
+    Save,
+    Quit,
+}
+
+struct Tweaker {
+    entries: Vec<(String, Oklch, u8)>,
+    selected: usize,
+}
+
+impl Tweaker {
+    fn new(palette: &Palette) -> Self {
+        let entries =
+            palette.iter().map(|(name, color)| (name.to_string(), color.into(), color.a)).collect();
+
+        Self { entries, selected: 0 }
+    }
+
+    fn palette(&self) -> Palette {
+        let mut palette = Palette::new();
+
+        for (name, oklch, alpha) in &self.entries {
+            palette.insert(name.clone(), oklch.to_color(*alpha));
+        }
+
+        palette
+    }
+
+    fn handle(&mut self, key: KeyEvent) -> Action {
+        let last = self.entries.len().saturating_sub(1);
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(last),
+            KeyCode::Char('w') | KeyCode::Enter => return Action::Save,
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Char(c) => {
+                if let Some((_, oklch, _)) = self.entries.get_mut(self.selected) {
+                    match c {
+                        'l' => oklch.l = (oklch.l - LIGHTNESS_STEP).max(0.0),
+                        'L' => oklch.l = (oklch.l + LIGHTNESS_STEP).min(1.0),
+                        'c' => oklch.c = (oklch.c - CHROMA_STEP).max(0.0),
+                        'C' => oklch.c += CHROMA_STEP,
+                        'h' => oklch.h = (oklch.h - HUE_STEP).rem_euclid(360.0),
+                        'H' => oklch.h = (oklch.h + HUE_STEP).rem_euclid(360.0),
+                        _ => {}
+                    }
+                    *oklch = oklch.clamp_chroma();
+                }
+            }
+            _ => {}
+        }
+
+        Action::Continue
+    }
+
+    fn render(
+        &self,
+        w: &mut impl Write,
+        build: &impl Fn(&Palette) -> proto::Theme,
+    ) -> io::Result<()> {
+        write!(w, "j/k select  l/L lightness  c/C chroma  h/H hue  w save  q quit\r\n\r\n")?;
+
+        for (idx, (name, oklch, alpha)) in self.entries.iter().enumerate() {
+            let color = oklch.to_color(*alpha);
+            let marker = if idx == self.selected { '>' } else { ' ' };
+
+            write!(
+                w,
+                "{marker} \x1b[48;2;{};{};{}m    \x1b[0m {name:<16} {:<10} L {:.2}  C {:.3}  H {:>5.1}\r\n",
+                color.r,
+                color.g,
+                color.b,
+                color.to_string(),
+                oklch.l,
+                oklch.c,
+                oklch.h
+            )?;
+        }
+
+        let mut preview = Vec::new();
+        preview::write_preview(&mut preview, &build(&self.palette()))?;
+
+        write!(w, "\r\n")?;
+        for line in String::from_utf8_lossy(&preview).lines() {
+            write!(w, "{line}\r\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use expect_test::expect;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn nudges_selected_entry() {
+        let mut palette = Palette::new();
+        palette.insert("red", 0xFF5555);
+        palette.insert("shadow", (0x000000, 0x80));
+
+        let mut tweaker = Tweaker::new(&palette);
+        tweaker.handle(key('L'));
+        tweaker.handle(key('L'));
+        tweaker.handle(key('j'));
+        tweaker.handle(key('H'));
+        assert_eq!(tweaker.handle(key('w')), Action::Save);
+
+        let adjusted = tweaker.palette();
+
+        expect![[r#"
+            palette.insert("red", 0xFF6562);
+            palette.insert("shadow", (0x000000, 0x80));
+        "#]]
+        .assert_eq(&to_rust(&adjusted));
+
+        expect![[r##"
+            "red" = "#FF6562"
+            "shadow" = "#00000080"
+        "##]]
+        .assert_eq(&to_toml(&adjusted));
+    }
+}
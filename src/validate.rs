@@ -0,0 +1,271 @@
+use crate::parse::strip_jsonc;
+use crate::{proto, serialize_theme, Error};
+use serde_json::{Map, Value};
+use std::fmt;
+
+const TOP_LEVEL_KEYS: &[&str] = &[
+    "$schema",
+    "name",
+    "type",
+    "include",
+    "colors",
+    "tokenColors",
+    "semanticHighlighting",
+    "semanticTokenColors",
+    "author",
+    "maintainers",
+    "semanticVersion",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub path: String,
+    pub message: String,
+}
+
+pub fn validate_theme_json(src: &str) -> Vec<Violation> {
+    let mut v = Validator { violations: Vec::new() };
+
+    match serde_json::from_str::<Value>(&strip_jsonc(src)) {
+        Ok(Value::Object(theme)) => v.theme(&theme),
+        Ok(_) => v.report("", "expected an object"),
+        Err(e) => v.report("", format!("invalid JSON: {e}")),
+    }
+
+    v.violations
+}
+
+pub fn validate_theme(theme: &proto::Theme) -> Result<(), Error> {
+    let violations = validate_theme_json(&serialize_theme(theme)?);
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Validation(violations))
+    }
+}
+
+struct Validator {
+    violations: Vec<Violation>,
+}
+
+impl Validator {
+    fn theme(&mut self, theme: &Map<String, Value>) {
+        for (key, value) in theme {
+            match key.as_str() {
+                "$schema" | "name" | "include" | "author" | "semanticVersion" => {
+                    self.string(key, value)
+                }
+                "type" => match value.as_str() {
+                    Some("light" | "dark" | "hcDark" | "hcLight") => {}
+                    _ => self.report(key, "expected one of `light`, `dark`, `hcDark` or `hcLight`"),
+                },
+                "maintainers" => self.array(key, value, |v, path, item| v.string(path, item)),
+                "colors" => self.object(key, value, |v, path, color| {
+                    if !color.is_null() {
+                        v.color(path, color)
+                    }
+                }),
+                "tokenColors" => match value {
+                    Value::String(_) => {}
+                    _ => self.array(key, value, Self::token_color),
+                },
+                "semanticHighlighting" => {
+                    if !value.is_boolean() {
+                        self.report(key, "expected a boolean")
+                    }
+                }
+                "semanticTokenColors" => self.object(key, value, Self::semantic_token_color),
+                _ => self.report(
+                    key,
+                    format!("unknown property; expected one of {}", TOP_LEVEL_KEYS.join(", ")),
+                ),
+            }
+        }
+    }
+
+    fn token_color(&mut self, path: &str, rule: &Value) {
+        let Some(rule) = rule.as_object() else {
+            return self.report(path, "expected an object");
+        };
+
+        for (key, value) in rule {
+            let path = format!("{path}.{key}");
+            match key.as_str() {
+                "name" => self.string(&path, value),
+                "scope" => match value {
+                    Value::String(_) => {}
+                    _ => self.array(&path, value, |v, path, scope| v.string(path, scope)),
+                },
+                "settings" => {
+                    self.object(&path, value, |v, path, setting| match path.rsplit('.').next() {
+                        Some("foreground" | "background") => v.color(path, setting),
+                        Some("fontStyle") => v.font_style(path, setting),
+                        _ => v.report(path, "unknown setting"),
+                    })
+                }
+                _ => self.report(&path, "unknown property"),
+            }
+        }
+
+        if !rule.contains_key("settings") {
+            self.report(path, "missing required property `settings`");
+        }
+    }
+
+    fn semantic_token_color(&mut self, path: &str, style: &Value) {
+        match style {
+            Value::String(_) => self.color(path, style),
+            Value::Object(style) => {
+                for (key, value) in style {
+                    let path = format!("{path}.{key}");
+                    match key.as_str() {
+                        "foreground" => self.color(&path, value),
+                        "fontStyle" => self.font_style(&path, value),
+                        "bold" | "italic" | "underline" | "strikethrough" => {
+                            if !value.is_boolean() {
+                                self.report(&path, "expected a boolean")
+                            }
+                        }
+                        _ => self.report(&path, "unknown property"),
+                    }
+                }
+            }
+            _ => self.report(path, "expected a color string or an object"),
+        }
+    }
+
+    fn color(&mut self, path: &str, value: &Value) {
+        let Some(s) = value.as_str() else {
+            return self.report(path, "expected a color string");
+        };
+
+        let digits = s.strip_prefix('#').unwrap_or("");
+        let valid =
+            matches!(digits.len(), 3 | 4 | 6 | 8) && digits.chars().all(|c| c.is_ascii_hexdigit());
+
+        if !valid {
+            self.report(
+                path,
+                format!("`{s}` is not a `#RGB`, `#RGBA`, `#RRGGBB` or `#RRGGBBAA` color"),
+            );
+        }
+    }
+
+    fn font_style(&mut self, path: &str, value: &Value) {
+        let Some(s) = value.as_str() else {
+            return self.report(path, "expected a string");
+        };
+
+        for word in s.split_whitespace() {
+            if !matches!(word, "italic" | "bold" | "underline" | "strikethrough") {
+                self.report(path, format!("unknown font style `{word}`"));
+            }
+        }
+    }
+
+    fn string(&mut self, path: &str, value: &Value) {
+        if !value.is_string() {
+            self.report(path, "expected a string");
+        }
+    }
+
+    fn array(&mut self, path: &str, value: &Value, mut item: impl FnMut(&mut Self, &str, &Value)) {
+        match value {
+            Value::Array(items) => {
+                for (idx, value) in items.iter().enumerate() {
+                    item(self, &format!("{path}[{idx}]"), value);
+                }
+            }
+            _ => self.report(path, "expected an array"),
+        }
+    }
+
+    fn object(
+        &mut self,
+        path: &str,
+        value: &Value,
+        mut entry: impl FnMut(&mut Self, &str, &Value),
+    ) {
+        match value {
+            Value::Object(entries) => {
+                for (key, value) in entries {
+                    entry(self, &format!("{path}.{key}"), value);
+                }
+            }
+            _ => self.report(path, "expected an object"),
+        }
+    }
+
+    fn report(&mut self, path: &str, message: impl Into<String>) {
+        self.violations.push(Violation { path: path.to_string(), message: message.into() });
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "`{}`: {}", self.path, self.message)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{s, tm, FontStyle, ThemeBuilder};
+    use crate::proto::{self, ThemeType};
+    use expect_test::expect;
+
+    #[test]
+    fn serialized_themes_are_valid() {
+        let mut t = ThemeBuilder::default();
+        t.global_token_defaults(0xD4D4D4, 0x1E1E1E);
+        t.w(["editor.background"], (0x1E1E1E, 0x80));
+        t.unset(["editor.lineHighlightBorder"]);
+        t.a([tm("keyword"), s("keyword")], (0x569CD6, FontStyle::Bold));
+        t.a([tm("comment"), s("*.documentation")], FontStyle::Clear);
+        let mut theme = t.build_with_type("My cool theme", ThemeType::HighContrastLight);
+        theme.metadata = proto::Metadata {
+            author: Some("Jane".to_string()),
+            maintainers: vec!["Joe".to_string()],
+            semantic_version: Some("1.0.0".to_string()),
+        };
+
+        let serialized = crate::serialize_theme(&theme).unwrap();
+
+        assert_eq!(validate_theme_json(&serialized), []);
+        assert!(validate_theme(&theme).is_ok());
+    }
+
+    #[test]
+    fn violations() {
+        let violations = validate_theme_json(
+            r##"{
+                "type": "dim",
+                "colors": { "editor.background": "#12345", "editor.foreground": 3 },
+                "tokenColors": [
+                    { "scope": ["comment", 1], "settings": { "fontStyle": "oblique" } },
+                    { "scope": "keyword" }
+                ],
+                "semanticTokenColors": { "keyword": { "foreground": "red", "bold": "yes" } },
+                "tokenColours": []
+            }"##,
+        );
+        let actual: String = violations.iter().map(|v| format!("{v}\n")).collect();
+
+        expect![[r#"
+            `type`: expected one of `light`, `dark`, `hcDark` or `hcLight`
+            `colors.editor.background`: `#12345` is not a `#RGB`, `#RGBA`, `#RRGGBB` or `#RRGGBBAA` color
+            `colors.editor.foreground`: expected a color string
+            `tokenColors[0].scope[1]`: expected a string
+            `tokenColors[0].settings.fontStyle`: unknown font style `oblique`
+            `tokenColors[1]`: missing required property `settings`
+            `semanticTokenColors.keyword.foreground`: `red` is not a `#RGB`, `#RGBA`, `#RRGGBB` or `#RRGGBBAA` color
+            `semanticTokenColors.keyword.bold`: expected a boolean
+            `tokenColours`: unknown property; expected one of $schema, name, type, include, colors, tokenColors, semanticHighlighting, semanticTokenColors, author, maintainers, semanticVersion
+        "#]].assert_eq(&actual);
+    }
+}
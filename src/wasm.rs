@@ -0,0 +1,92 @@
+use crate::proto::semantic::Selector;
+use crate::spec::ThemeSpec;
+use crate::{serialize_theme, Error};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(js_name = buildThemeFromSpec)]
+pub fn build_theme_from_spec(json_or_toml: &str) -> Result<String, JsError> {
+    build(json_or_toml).map_err(|e| JsError::new(&error_chain(&e)))
+}
+
+#[wasm_bindgen(js_name = parseSelector)]
+pub fn parse_selector(selector: &str) -> Result<String, JsError> {
+    normalize_selector(selector).map_err(|e| JsError::new(&e))
+}
+
+fn build(src: &str) -> Result<String, Error> {
+    let spec = if src.trim_start().starts_with('{') {
+        ThemeSpec::from_json(src)?
+    } else {
+        ThemeSpec::from_toml(src)?
+    };
+
+    Ok(serialize_theme(&spec.build()?)?)
+}
+
+fn normalize_selector(selector: &str) -> Result<String, String> {
+    selector.parse::<Selector>().map(|selector| selector.to_string()).map_err(|e| e.to_string())
+}
+
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+
+    while let Some(error) = source {
+        message.push_str(&format!(": {error}"));
+        source = error.source();
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::expect;
+
+    #[test]
+    fn build_from_json_and_toml() {
+        let json =
+            build(r##"{ "name": "My cool theme", "colors": { "editor.background": "#1E1E1E" } }"##)
+                .unwrap();
+        let toml = build(
+            r##"
+            name = "My cool theme"
+
+            [colors]
+            "editor.background" = "#1E1E1E"
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(json, toml);
+        expect![[r##"
+            // Do not edit directly; this file is generated.
+            {
+                "name": "My cool theme",
+                "tokenColors": [],
+                "semanticHighlighting": true,
+                "semanticTokenColors": {},
+                "colors": {
+                    "editor.background": "#1E1E1E"
+                }
+            }
+        "##]]
+        .assert_eq(&json);
+    }
+
+    #[test]
+    fn errors() {
+        let e = build("name = \"x\"\ncolors = 1").unwrap_err();
+        expect![[r#"
+            failed parsing theme spec: invalid type: integer `1`, expected a map for key `colors` at line 2 column 10"#]]
+        .assert_eq(&error_chain(&e));
+
+        assert_eq!(normalize_selector("variable.readonly:rust").unwrap(), "variable.readonly:rust");
+        expect![[r#"
+            empty modifier
+              variable..readonly
+                      ^"#]]
+        .assert_eq(&normalize_selector("variable..readonly").unwrap_err());
+    }
+}
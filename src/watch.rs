@@ -0,0 +1,88 @@
+use crate::{proto, save_all_to, FileNames, SaveStatus, StaleFiles};
+use notify::{RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+use thiserror::Error;
+
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+pub fn watch<P: AsRef<Path>>(
+    paths: &[P],
+    rebuild: impl FnMut() -> Vec<proto::Theme>,
+) -> Result<(), WatchError> {
+    watch_to("themes", paths, FileNames::ThemeName, rebuild)
+}
+
+pub fn watch_to<P: AsRef<Path>>(
+    dir: impl AsRef<Path>,
+    paths: &[P],
+    file_names: FileNames,
+    mut rebuild: impl FnMut() -> Vec<proto::Theme>,
+) -> Result<(), WatchError> {
+    let dir = dir.as_ref();
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(WatchError::CreateWatcher)?;
+
+    for path in paths {
+        let path = path.as_ref();
+        watcher
+            .watch(path, RecursiveMode::Recursive)
+            .map_err(|e| WatchError::Watch(e, path.to_path_buf()))?;
+    }
+
+    rebuild_and_save(dir, file_names, &mut rebuild);
+
+    while let Ok(event) = rx.recv() {
+        let mut changed = is_change(&event);
+
+        while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+            changed |= is_change(&event);
+        }
+
+        if changed {
+            rebuild_and_save(dir, file_names, &mut rebuild);
+        }
+    }
+
+    Ok(())
+}
+
+fn is_change(event: &notify::Result<notify::Event>) -> bool {
+    match event {
+        Ok(event) => !event.kind.is_access(),
+        Err(e) => {
+            eprintln!("error: {e}");
+            false
+        }
+    }
+}
+
+fn rebuild_and_save(
+    dir: &Path,
+    file_names: FileNames,
+    rebuild: &mut impl FnMut() -> Vec<proto::Theme>,
+) {
+    let themes = rebuild();
+
+    match save_all_to(dir, &themes, StaleFiles::Keep, file_names) {
+        Ok(report) => {
+            for (path, status) in report.saved {
+                match status {
+                    SaveStatus::Created => eprintln!("created {}", path.display()),
+                    SaveStatus::Updated => eprintln!("updated {}", path.display()),
+                    SaveStatus::Unchanged => {}
+                }
+            }
+        }
+        Err(e) => eprintln!("error: {e}"),
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum WatchError {
+    #[error("failed creating filesystem watcher")]
+    CreateWatcher(#[source] notify::Error),
+    #[error("failed watching `{1}`")]
+    Watch(#[source] notify::Error, PathBuf),
+}
@@ -0,0 +1,885 @@
+pub const KEYS: &[&str] = &[
+    "contrastActiveBorder",
+    "contrastBorder",
+    "focusBorder",
+    "foreground",
+    "disabledForeground",
+    "widget.border",
+    "widget.shadow",
+    "selection.background",
+    "descriptionForeground",
+    "errorForeground",
+    "icon.foreground",
+    "sash.hoverBorder",
+    "window.activeBorder",
+    "window.inactiveBorder",
+    "textBlockQuote.background",
+    "textBlockQuote.border",
+    "textCodeBlock.background",
+    "textLink.activeForeground",
+    "textLink.foreground",
+    "textPreformat.foreground",
+    "textPreformat.background",
+    "textSeparator.foreground",
+    "toolbar.hoverBackground",
+    "toolbar.hoverOutline",
+    "toolbar.activeBackground",
+    "button.background",
+    "button.foreground",
+    "button.border",
+    "button.separator",
+    "button.hoverBackground",
+    "button.secondaryForeground",
+    "button.secondaryBackground",
+    "button.secondaryHoverBackground",
+    "checkbox.background",
+    "checkbox.foreground",
+    "checkbox.border",
+    "checkbox.selectBackground",
+    "checkbox.selectBorder",
+    "dropdown.background",
+    "dropdown.listBackground",
+    "dropdown.border",
+    "dropdown.foreground",
+    "input.background",
+    "input.border",
+    "input.foreground",
+    "input.placeholderForeground",
+    "inputOption.activeBackground",
+    "inputOption.activeBorder",
+    "inputOption.activeForeground",
+    "inputOption.hoverBackground",
+    "inputValidation.errorBackground",
+    "inputValidation.errorForeground",
+    "inputValidation.errorBorder",
+    "inputValidation.infoBackground",
+    "inputValidation.infoForeground",
+    "inputValidation.infoBorder",
+    "inputValidation.warningBackground",
+    "inputValidation.warningForeground",
+    "inputValidation.warningBorder",
+    "scrollbar.shadow",
+    "scrollbarSlider.activeBackground",
+    "scrollbarSlider.background",
+    "scrollbarSlider.hoverBackground",
+    "badge.foreground",
+    "badge.background",
+    "progressBar.background",
+    "list.activeSelectionBackground",
+    "list.activeSelectionForeground",
+    "list.activeSelectionIconForeground",
+    "list.dropBackground",
+    "list.focusBackground",
+    "list.focusForeground",
+    "list.focusHighlightForeground",
+    "list.focusOutline",
+    "list.focusAndSelectionOutline",
+    "list.highlightForeground",
+    "list.hoverBackground",
+    "list.hoverForeground",
+    "list.inactiveSelectionBackground",
+    "list.inactiveSelectionForeground",
+    "list.inactiveSelectionIconForeground",
+    "list.inactiveFocusBackground",
+    "list.inactiveFocusOutline",
+    "list.invalidItemForeground",
+    "list.errorForeground",
+    "list.warningForeground",
+    "listFilterWidget.background",
+    "listFilterWidget.outline",
+    "listFilterWidget.noMatchesOutline",
+    "listFilterWidget.shadow",
+    "list.filterMatchBackground",
+    "list.filterMatchBorder",
+    "list.deemphasizedForeground",
+    "list.dropBetweenBackground",
+    "tree.indentGuidesStroke",
+    "tree.inactiveIndentGuidesStroke",
+    "tree.tableColumnsBorder",
+    "tree.tableOddRowsBackground",
+    "activityBar.background",
+    "activityBar.dropBorder",
+    "activityBar.foreground",
+    "activityBar.inactiveForeground",
+    "activityBar.border",
+    "activityBarBadge.background",
+    "activityBarBadge.foreground",
+    "activityBar.activeBorder",
+    "activityBar.activeBackground",
+    "activityBar.activeFocusBorder",
+    "activityBarTop.foreground",
+    "activityBarTop.activeBorder",
+    "activityBarTop.inactiveForeground",
+    "activityBarTop.dropBorder",
+    "activityBarTop.background",
+    "activityBarTop.activeBackground",
+    "profileBadge.background",
+    "profileBadge.foreground",
+    "sideBar.background",
+    "sideBar.foreground",
+    "sideBar.border",
+    "sideBar.dropBackground",
+    "sideBarTitle.foreground",
+    "sideBarTitle.background",
+    "sideBarSectionHeader.background",
+    "sideBarSectionHeader.foreground",
+    "sideBarSectionHeader.border",
+    "sideBarActivityBarTop.border",
+    "sideBarStickyScroll.background",
+    "sideBarStickyScroll.border",
+    "sideBarStickyScroll.shadow",
+    "minimap.findMatchHighlight",
+    "minimap.selectionHighlight",
+    "minimap.errorHighlight",
+    "minimap.warningHighlight",
+    "minimap.background",
+    "minimap.selectionOccurrenceHighlight",
+    "minimap.foregroundOpacity",
+    "minimap.infoHighlight",
+    "minimap.chatEditHighlight",
+    "minimapSlider.background",
+    "minimapSlider.hoverBackground",
+    "minimapSlider.activeBackground",
+    "minimapGutter.addedBackground",
+    "minimapGutter.modifiedBackground",
+    "minimapGutter.deletedBackground",
+    "editorGroup.border",
+    "editorGroup.dropBackground",
+    "editorGroupHeader.noTabsBackground",
+    "editorGroupHeader.tabsBackground",
+    "editorGroupHeader.tabsBorder",
+    "editorGroupHeader.border",
+    "editorGroup.emptyBackground",
+    "editorGroup.focusedEmptyBorder",
+    "editorGroup.dropIntoPromptForeground",
+    "editorGroup.dropIntoPromptBackground",
+    "editorGroup.dropIntoPromptBorder",
+    "tab.activeBackground",
+    "tab.unfocusedActiveBackground",
+    "tab.activeForeground",
+    "tab.border",
+    "tab.activeBorder",
+    "tab.selectedBorderTop",
+    "tab.selectedBackground",
+    "tab.selectedForeground",
+    "tab.dragAndDropBorder",
+    "tab.unfocusedActiveBorder",
+    "tab.activeBorderTop",
+    "tab.unfocusedActiveBorderTop",
+    "tab.lastPinnedBorder",
+    "tab.inactiveBackground",
+    "tab.unfocusedInactiveBackground",
+    "tab.inactiveForeground",
+    "tab.unfocusedActiveForeground",
+    "tab.unfocusedInactiveForeground",
+    "tab.hoverBackground",
+    "tab.unfocusedHoverBackground",
+    "tab.hoverForeground",
+    "tab.unfocusedHoverForeground",
+    "tab.hoverBorder",
+    "tab.unfocusedHoverBorder",
+    "tab.activeModifiedBorder",
+    "tab.inactiveModifiedBorder",
+    "tab.unfocusedActiveModifiedBorder",
+    "tab.unfocusedInactiveModifiedBorder",
+    "editorPane.background",
+    "sideBySideEditor.horizontalBorder",
+    "sideBySideEditor.verticalBorder",
+    "editor.background",
+    "editor.foreground",
+    "editorLineNumber.foreground",
+    "editorLineNumber.activeForeground",
+    "editorLineNumber.dimmedForeground",
+    "editorCursor.background",
+    "editorCursor.foreground",
+    "editorMultiCursor.primary.foreground",
+    "editorMultiCursor.primary.background",
+    "editorMultiCursor.secondary.foreground",
+    "editorMultiCursor.secondary.background",
+    "editor.placeholder.foreground",
+    "editor.compositionBorder",
+    "editor.selectionBackground",
+    "editor.selectionForeground",
+    "editor.inactiveSelectionBackground",
+    "editor.selectionHighlightBackground",
+    "editor.selectionHighlightBorder",
+    "editor.wordHighlightBackground",
+    "editor.wordHighlightBorder",
+    "editor.wordHighlightStrongBackground",
+    "editor.wordHighlightStrongBorder",
+    "editor.wordHighlightTextBackground",
+    "editor.wordHighlightTextBorder",
+    "editor.findMatchBackground",
+    "editor.findMatchForeground",
+    "editor.findMatchHighlightForeground",
+    "editor.findMatchHighlightBackground",
+    "editor.findRangeHighlightBackground",
+    "editor.findMatchBorder",
+    "editor.findMatchHighlightBorder",
+    "editor.findRangeHighlightBorder",
+    "search.resultsInfoForeground",
+    "searchEditor.findMatchBackground",
+    "searchEditor.findMatchBorder",
+    "searchEditor.textInputBorder",
+    "editor.hoverHighlightBackground",
+    "editor.lineHighlightBackground",
+    "editor.lineHighlightBorder",
+    "editorWatermark.foreground",
+    "editorUnicodeHighlight.border",
+    "editorUnicodeHighlight.background",
+    "editorLink.activeForeground",
+    "editor.rangeHighlightBackground",
+    "editor.rangeHighlightBorder",
+    "editor.symbolHighlightBackground",
+    "editor.symbolHighlightBorder",
+    "editorWhitespace.foreground",
+    "editorIndentGuide.background",
+    "editorIndentGuide.background1",
+    "editorIndentGuide.background2",
+    "editorIndentGuide.background3",
+    "editorIndentGuide.background4",
+    "editorIndentGuide.background5",
+    "editorIndentGuide.background6",
+    "editorIndentGuide.activeBackground",
+    "editorIndentGuide.activeBackground1",
+    "editorIndentGuide.activeBackground2",
+    "editorIndentGuide.activeBackground3",
+    "editorIndentGuide.activeBackground4",
+    "editorIndentGuide.activeBackground5",
+    "editorIndentGuide.activeBackground6",
+    "editorInlayHint.background",
+    "editorInlayHint.foreground",
+    "editorInlayHint.typeForeground",
+    "editorInlayHint.typeBackground",
+    "editorInlayHint.parameterForeground",
+    "editorInlayHint.parameterBackground",
+    "editorRuler.foreground",
+    "editor.linkedEditingBackground",
+    "editorCodeLens.foreground",
+    "editorLightBulb.foreground",
+    "editorLightBulbAutoFix.foreground",
+    "editorLightBulbAi.foreground",
+    "editorBracketMatch.background",
+    "editorBracketMatch.border",
+    "editorBracketHighlight.foreground1",
+    "editorBracketHighlight.foreground2",
+    "editorBracketHighlight.foreground3",
+    "editorBracketHighlight.foreground4",
+    "editorBracketHighlight.foreground5",
+    "editorBracketHighlight.foreground6",
+    "editorBracketHighlight.unexpectedBracket.foreground",
+    "editorBracketPairGuide.activeBackground1",
+    "editorBracketPairGuide.activeBackground2",
+    "editorBracketPairGuide.activeBackground3",
+    "editorBracketPairGuide.activeBackground4",
+    "editorBracketPairGuide.activeBackground5",
+    "editorBracketPairGuide.activeBackground6",
+    "editorBracketPairGuide.background1",
+    "editorBracketPairGuide.background2",
+    "editorBracketPairGuide.background3",
+    "editorBracketPairGuide.background4",
+    "editorBracketPairGuide.background5",
+    "editorBracketPairGuide.background6",
+    "editor.foldBackground",
+    "editor.foldPlaceholderForeground",
+    "editorOverviewRuler.background",
+    "editorOverviewRuler.border",
+    "editorOverviewRuler.findMatchForeground",
+    "editorOverviewRuler.rangeHighlightForeground",
+    "editorOverviewRuler.selectionHighlightForeground",
+    "editorOverviewRuler.wordHighlightForeground",
+    "editorOverviewRuler.wordHighlightStrongForeground",
+    "editorOverviewRuler.wordHighlightTextForeground",
+    "editorOverviewRuler.modifiedForeground",
+    "editorOverviewRuler.addedForeground",
+    "editorOverviewRuler.deletedForeground",
+    "editorOverviewRuler.errorForeground",
+    "editorOverviewRuler.warningForeground",
+    "editorOverviewRuler.infoForeground",
+    "editorOverviewRuler.bracketMatchForeground",
+    "editorOverviewRuler.inlineChatInserted",
+    "editorOverviewRuler.inlineChatRemoved",
+    "editorOverviewRuler.commentForeground",
+    "editorOverviewRuler.commentUnresolvedForeground",
+    "editorOverviewRuler.commonContentForeground",
+    "editorOverviewRuler.currentContentForeground",
+    "editorOverviewRuler.incomingContentForeground",
+    "editorError.foreground",
+    "editorError.border",
+    "editorError.background",
+    "editorWarning.foreground",
+    "editorWarning.border",
+    "editorWarning.background",
+    "editorInfo.foreground",
+    "editorInfo.border",
+    "editorInfo.background",
+    "editorHint.foreground",
+    "editorHint.border",
+    "problemsErrorIcon.foreground",
+    "problemsWarningIcon.foreground",
+    "problemsInfoIcon.foreground",
+    "editorUnnecessaryCode.border",
+    "editorUnnecessaryCode.opacity",
+    "editorGutter.background",
+    "editorGutter.modifiedBackground",
+    "editorGutter.modifiedSecondaryBackground",
+    "editorGutter.addedBackground",
+    "editorGutter.addedSecondaryBackground",
+    "editorGutter.deletedBackground",
+    "editorGutter.deletedSecondaryBackground",
+    "editorGutter.commentRangeForeground",
+    "editorGutter.commentGlyphForeground",
+    "editorGutter.commentUnresolvedGlyphForeground",
+    "editorGutter.foldingControlForeground",
+    "editorGutter.itemGlyphForeground",
+    "editorGutter.itemBackground",
+    "editorCommentsWidget.resolvedBorder",
+    "editorCommentsWidget.unresolvedBorder",
+    "editorCommentsWidget.rangeBackground",
+    "editorCommentsWidget.rangeActiveBackground",
+    "editorCommentsWidget.replyInputBackground",
+    "diffEditor.insertedTextBackground",
+    "diffEditor.insertedTextBorder",
+    "diffEditor.removedTextBackground",
+    "diffEditor.removedTextBorder",
+    "diffEditor.border",
+    "diffEditor.diagonalFill",
+    "diffEditor.insertedLineBackground",
+    "diffEditor.removedLineBackground",
+    "diffEditorGutter.insertedLineBackground",
+    "diffEditorGutter.removedLineBackground",
+    "diffEditorOverview.insertedForeground",
+    "diffEditorOverview.removedForeground",
+    "diffEditor.unchangedRegionBackground",
+    "diffEditor.unchangedRegionForeground",
+    "diffEditor.unchangedRegionShadow",
+    "diffEditor.unchangedCodeBackground",
+    "diffEditor.move.border",
+    "diffEditor.moveActive.border",
+    "multiDiffEditor.headerBackground",
+    "multiDiffEditor.background",
+    "multiDiffEditor.border",
+    "chat.requestBorder",
+    "chat.requestBackground",
+    "chat.slashCommandBackground",
+    "chat.slashCommandForeground",
+    "chat.avatarBackground",
+    "chat.avatarForeground",
+    "chat.editedFileForeground",
+    "inlineChat.background",
+    "inlineChat.foreground",
+    "inlineChat.border",
+    "inlineChat.shadow",
+    "inlineChatInput.border",
+    "inlineChatInput.focusBorder",
+    "inlineChatInput.placeholderForeground",
+    "inlineChatInput.background",
+    "inlineChatDiff.inserted",
+    "inlineChatDiff.removed",
+    "interactive.activeCodeBorder",
+    "interactive.inactiveCodeBorder",
+    "editorWidget.foreground",
+    "editorWidget.background",
+    "editorWidget.border",
+    "editorWidget.resizeBorder",
+    "editorSuggestWidget.background",
+    "editorSuggestWidget.border",
+    "editorSuggestWidget.foreground",
+    "editorSuggestWidget.focusHighlightForeground",
+    "editorSuggestWidget.highlightForeground",
+    "editorSuggestWidget.selectedBackground",
+    "editorSuggestWidget.selectedForeground",
+    "editorSuggestWidget.selectedIconForeground",
+    "editorSuggestWidgetStatus.foreground",
+    "editorHoverWidget.foreground",
+    "editorHoverWidget.background",
+    "editorHoverWidget.border",
+    "editorHoverWidget.highlightForeground",
+    "editorHoverWidget.statusBarBackground",
+    "editorGhostText.border",
+    "editorGhostText.background",
+    "editorGhostText.foreground",
+    "editorStickyScroll.background",
+    "editorStickyScroll.border",
+    "editorStickyScroll.shadow",
+    "editorStickyScrollHover.background",
+    "editorStickyScrollGutter.background",
+    "debugExceptionWidget.background",
+    "debugExceptionWidget.border",
+    "editorMarkerNavigation.background",
+    "editorMarkerNavigationError.background",
+    "editorMarkerNavigationWarning.background",
+    "editorMarkerNavigationInfo.background",
+    "editorMarkerNavigationError.headerBackground",
+    "editorMarkerNavigationWarning.headerBackground",
+    "editorMarkerNavigationInfo.headerBackground",
+    "peekView.border",
+    "peekViewEditor.background",
+    "peekViewEditorGutter.background",
+    "peekViewEditor.matchHighlightBackground",
+    "peekViewEditor.matchHighlightBorder",
+    "peekViewResult.background",
+    "peekViewResult.fileForeground",
+    "peekViewResult.lineForeground",
+    "peekViewResult.matchHighlightBackground",
+    "peekViewResult.selectionBackground",
+    "peekViewResult.selectionForeground",
+    "peekViewTitle.background",
+    "peekViewTitleDescription.foreground",
+    "peekViewTitleLabel.foreground",
+    "peekViewEditorStickyScroll.background",
+    "peekViewEditorStickyScrollGutter.background",
+    "merge.currentHeaderBackground",
+    "merge.currentContentBackground",
+    "merge.incomingHeaderBackground",
+    "merge.incomingContentBackground",
+    "merge.border",
+    "merge.commonContentBackground",
+    "merge.commonHeaderBackground",
+    "mergeEditor.change.background",
+    "mergeEditor.change.word.background",
+    "mergeEditor.conflict.unhandledUnfocused.border",
+    "mergeEditor.conflict.unhandledFocused.border",
+    "mergeEditor.conflict.handledUnfocused.border",
+    "mergeEditor.conflict.handledFocused.border",
+    "mergeEditor.conflict.handled.minimapOverViewRuler",
+    "mergeEditor.conflict.unhandled.minimapOverViewRuler",
+    "mergeEditor.conflictingLines.background",
+    "mergeEditor.changeBase.background",
+    "mergeEditor.changeBase.word.background",
+    "mergeEditor.conflict.input1.background",
+    "mergeEditor.conflict.input2.background",
+    "panel.background",
+    "panel.border",
+    "panel.dropBorder",
+    "panelTitle.activeBorder",
+    "panelTitle.activeForeground",
+    "panelTitle.inactiveForeground",
+    "panelTitle.border",
+    "panelTitleBadge.background",
+    "panelTitleBadge.foreground",
+    "panelInput.border",
+    "panelSection.border",
+    "panelSection.dropBackground",
+    "panelSectionHeader.background",
+    "panelSectionHeader.foreground",
+    "panelSectionHeader.border",
+    "panelStickyScroll.background",
+    "panelStickyScroll.border",
+    "panelStickyScroll.shadow",
+    "outputView.background",
+    "outputViewStickyScroll.background",
+    "statusBar.background",
+    "statusBar.foreground",
+    "statusBar.border",
+    "statusBar.debuggingBackground",
+    "statusBar.debuggingForeground",
+    "statusBar.debuggingBorder",
+    "statusBar.noFolderForeground",
+    "statusBar.noFolderBackground",
+    "statusBar.noFolderBorder",
+    "statusBar.focusBorder",
+    "statusBarItem.activeBackground",
+    "statusBarItem.focusBorder",
+    "statusBarItem.hoverBackground",
+    "statusBarItem.hoverForeground",
+    "statusBarItem.compactHoverBackground",
+    "statusBarItem.prominentForeground",
+    "statusBarItem.prominentBackground",
+    "statusBarItem.prominentHoverForeground",
+    "statusBarItem.prominentHoverBackground",
+    "statusBarItem.remoteBackground",
+    "statusBarItem.remoteForeground",
+    "statusBarItem.remoteHoverBackground",
+    "statusBarItem.remoteHoverForeground",
+    "statusBarItem.errorBackground",
+    "statusBarItem.errorForeground",
+    "statusBarItem.errorHoverBackground",
+    "statusBarItem.errorHoverForeground",
+    "statusBarItem.warningBackground",
+    "statusBarItem.warningForeground",
+    "statusBarItem.warningHoverBackground",
+    "statusBarItem.warningHoverForeground",
+    "statusBarItem.offlineBackground",
+    "statusBarItem.offlineForeground",
+    "statusBarItem.offlineHoverForeground",
+    "statusBarItem.offlineHoverBackground",
+    "titleBar.activeBackground",
+    "titleBar.activeForeground",
+    "titleBar.inactiveBackground",
+    "titleBar.inactiveForeground",
+    "titleBar.border",
+    "menubar.selectionForeground",
+    "menubar.selectionBackground",
+    "menubar.selectionBorder",
+    "menu.foreground",
+    "menu.background",
+    "menu.selectionForeground",
+    "menu.selectionBackground",
+    "menu.selectionBorder",
+    "menu.separatorBackground",
+    "menu.border",
+    "commandCenter.foreground",
+    "commandCenter.activeForeground",
+    "commandCenter.background",
+    "commandCenter.activeBackground",
+    "commandCenter.border",
+    "commandCenter.inactiveForeground",
+    "commandCenter.inactiveBorder",
+    "commandCenter.activeBorder",
+    "commandCenter.debuggingBackground",
+    "notificationCenter.border",
+    "notificationCenterHeader.foreground",
+    "notificationCenterHeader.background",
+    "notificationToast.border",
+    "notifications.foreground",
+    "notifications.background",
+    "notifications.border",
+    "notificationLink.foreground",
+    "notificationsErrorIcon.foreground",
+    "notificationsWarningIcon.foreground",
+    "notificationsInfoIcon.foreground",
+    "banner.background",
+    "banner.foreground",
+    "banner.iconForeground",
+    "extensionButton.prominentForeground",
+    "extensionButton.prominentBackground",
+    "extensionButton.prominentHoverBackground",
+    "extensionButton.background",
+    "extensionButton.foreground",
+    "extensionButton.hoverBackground",
+    "extensionButton.separator",
+    "extensionBadge.remoteBackground",
+    "extensionBadge.remoteForeground",
+    "extensionIcon.starForeground",
+    "extensionIcon.verifiedForeground",
+    "extensionIcon.preReleaseForeground",
+    "extensionIcon.sponsorForeground",
+    "pickerGroup.border",
+    "pickerGroup.foreground",
+    "quickInput.background",
+    "quickInput.foreground",
+    "quickInputList.focusBackground",
+    "quickInputList.focusForeground",
+    "quickInputList.focusIconForeground",
+    "quickInputTitle.background",
+    "keybindingLabel.background",
+    "keybindingLabel.foreground",
+    "keybindingLabel.border",
+    "keybindingLabel.bottomBorder",
+    "keybindingTable.headerBackground",
+    "keybindingTable.rowsBackground",
+    "terminal.background",
+    "terminal.border",
+    "terminal.foreground",
+    "terminal.ansiBlack",
+    "terminal.ansiBlue",
+    "terminal.ansiBrightBlack",
+    "terminal.ansiBrightBlue",
+    "terminal.ansiBrightCyan",
+    "terminal.ansiBrightGreen",
+    "terminal.ansiBrightMagenta",
+    "terminal.ansiBrightRed",
+    "terminal.ansiBrightWhite",
+    "terminal.ansiBrightYellow",
+    "terminal.ansiCyan",
+    "terminal.ansiGreen",
+    "terminal.ansiMagenta",
+    "terminal.ansiRed",
+    "terminal.ansiWhite",
+    "terminal.ansiYellow",
+    "terminal.selectionBackground",
+    "terminal.selectionForeground",
+    "terminal.inactiveSelectionBackground",
+    "terminal.findMatchBackground",
+    "terminal.findMatchBorder",
+    "terminal.findMatchHighlightBackground",
+    "terminal.findMatchHighlightBorder",
+    "terminal.hoverHighlightBackground",
+    "terminalCursor.background",
+    "terminalCursor.foreground",
+    "terminal.dropBackground",
+    "terminal.tab.activeBorder",
+    "terminalCommandDecoration.defaultBackground",
+    "terminalCommandDecoration.successBackground",
+    "terminalCommandDecoration.errorBackground",
+    "terminalOverviewRuler.cursorForeground",
+    "terminalOverviewRuler.findMatchForeground",
+    "terminalStickyScroll.background",
+    "terminalStickyScroll.border",
+    "terminalStickyScrollHover.background",
+    "terminal.initialHintForeground",
+    "terminalOverviewRuler.border",
+    "terminalCommandGuide.foreground",
+    "terminalSymbolIcon.aliasForeground",
+    "terminalSymbolIcon.flagForeground",
+    "debugToolBar.background",
+    "debugToolBar.border",
+    "editor.stackFrameHighlightBackground",
+    "editor.focusedStackFrameHighlightBackground",
+    "editor.inlineValuesForeground",
+    "editor.inlineValuesBackground",
+    "debugView.exceptionLabelForeground",
+    "debugView.exceptionLabelBackground",
+    "debugView.stateLabelForeground",
+    "debugView.stateLabelBackground",
+    "debugView.valueChangedHighlight",
+    "debugTokenExpression.name",
+    "debugTokenExpression.value",
+    "debugTokenExpression.string",
+    "debugTokenExpression.boolean",
+    "debugTokenExpression.number",
+    "debugTokenExpression.error",
+    "debugTokenExpression.type",
+    "testing.iconFailed",
+    "testing.iconErrored",
+    "testing.iconPassed",
+    "testing.runAction",
+    "testing.iconQueued",
+    "testing.iconUnset",
+    "testing.iconSkipped",
+    "testing.peekBorder",
+    "testing.peekHeaderBackground",
+    "testing.message.error.lineBackground",
+    "testing.message.info.decorationForeground",
+    "testing.message.info.lineBackground",
+    "testing.messagePeekBorder",
+    "testing.messagePeekHeaderBackground",
+    "testing.coveredBackground",
+    "testing.coveredBorder",
+    "testing.coveredGutterBackground",
+    "testing.uncoveredBranchBackground",
+    "testing.uncoveredBackground",
+    "testing.uncoveredBorder",
+    "testing.uncoveredGutterBackground",
+    "testing.coverCountBadgeBackground",
+    "testing.coverCountBadgeForeground",
+    "testing.message.error.badgeBackground",
+    "testing.message.error.badgeBorder",
+    "testing.message.error.badgeForeground",
+    "testing.iconErrored.retired",
+    "testing.iconFailed.retired",
+    "testing.iconPassed.retired",
+    "testing.iconQueued.retired",
+    "testing.iconUnset.retired",
+    "testing.iconSkipped.retired",
+    "welcomePage.background",
+    "welcomePage.progress.background",
+    "welcomePage.progress.foreground",
+    "welcomePage.tileBackground",
+    "welcomePage.tileHoverBackground",
+    "welcomePage.tileBorder",
+    "walkThrough.embeddedEditorBackground",
+    "walkthrough.stepTitle.foreground",
+    "gitDecoration.addedResourceForeground",
+    "gitDecoration.modifiedResourceForeground",
+    "gitDecoration.deletedResourceForeground",
+    "gitDecoration.renamedResourceForeground",
+    "gitDecoration.stageModifiedResourceForeground",
+    "gitDecoration.stageDeletedResourceForeground",
+    "gitDecoration.untrackedResourceForeground",
+    "gitDecoration.ignoredResourceForeground",
+    "gitDecoration.conflictingResourceForeground",
+    "gitDecoration.submoduleResourceForeground",
+    "settings.headerForeground",
+    "settings.modifiedItemIndicator",
+    "settings.dropdownBackground",
+    "settings.dropdownForeground",
+    "settings.dropdownBorder",
+    "settings.dropdownListBorder",
+    "settings.checkboxBackground",
+    "settings.checkboxForeground",
+    "settings.checkboxBorder",
+    "settings.rowHoverBackground",
+    "settings.textInputBackground",
+    "settings.textInputForeground",
+    "settings.textInputBorder",
+    "settings.numberInputBackground",
+    "settings.numberInputForeground",
+    "settings.numberInputBorder",
+    "settings.focusedRowBackground",
+    "settings.focusedRowBorder",
+    "settings.headerBorder",
+    "settings.sashBorder",
+    "settings.settingsHeaderHoverForeground",
+    "breadcrumb.foreground",
+    "breadcrumb.background",
+    "breadcrumb.focusForeground",
+    "breadcrumb.activeSelectionForeground",
+    "breadcrumbPicker.background",
+    "editor.snippetTabstopHighlightBackground",
+    "editor.snippetTabstopHighlightBorder",
+    "editor.snippetFinalTabstopHighlightBackground",
+    "editor.snippetFinalTabstopHighlightBorder",
+    "symbolIcon.arrayForeground",
+    "symbolIcon.booleanForeground",
+    "symbolIcon.classForeground",
+    "symbolIcon.colorForeground",
+    "symbolIcon.constantForeground",
+    "symbolIcon.constructorForeground",
+    "symbolIcon.enumeratorForeground",
+    "symbolIcon.enumeratorMemberForeground",
+    "symbolIcon.eventForeground",
+    "symbolIcon.fieldForeground",
+    "symbolIcon.fileForeground",
+    "symbolIcon.folderForeground",
+    "symbolIcon.functionForeground",
+    "symbolIcon.interfaceForeground",
+    "symbolIcon.keyForeground",
+    "symbolIcon.keywordForeground",
+    "symbolIcon.methodForeground",
+    "symbolIcon.moduleForeground",
+    "symbolIcon.namespaceForeground",
+    "symbolIcon.nullForeground",
+    "symbolIcon.numberForeground",
+    "symbolIcon.objectForeground",
+    "symbolIcon.operatorForeground",
+    "symbolIcon.packageForeground",
+    "symbolIcon.propertyForeground",
+    "symbolIcon.referenceForeground",
+    "symbolIcon.snippetForeground",
+    "symbolIcon.stringForeground",
+    "symbolIcon.structForeground",
+    "symbolIcon.textForeground",
+    "symbolIcon.typeParameterForeground",
+    "symbolIcon.unitForeground",
+    "symbolIcon.variableForeground",
+    "debugIcon.breakpointForeground",
+    "debugIcon.breakpointDisabledForeground",
+    "debugIcon.breakpointUnverifiedForeground",
+    "debugIcon.breakpointCurrentStackframeForeground",
+    "debugIcon.breakpointStackframeForeground",
+    "debugIcon.startForeground",
+    "debugIcon.pauseForeground",
+    "debugIcon.stopForeground",
+    "debugIcon.disconnectForeground",
+    "debugIcon.restartForeground",
+    "debugIcon.stepOverForeground",
+    "debugIcon.stepIntoForeground",
+    "debugIcon.stepOutForeground",
+    "debugIcon.continueForeground",
+    "debugIcon.stepBackForeground",
+    "debugConsole.infoForeground",
+    "debugConsole.warningForeground",
+    "debugConsole.errorForeground",
+    "debugConsole.sourceForeground",
+    "debugConsoleInputIcon.foreground",
+    "notebook.editorBackground",
+    "notebook.cellBorderColor",
+    "notebook.cellHoverBackground",
+    "notebook.cellInsertionIndicator",
+    "notebook.cellStatusBarItemHoverBackground",
+    "notebook.cellToolbarSeparator",
+    "notebook.cellEditorBackground",
+    "notebook.focusedCellBackground",
+    "notebook.focusedCellBorder",
+    "notebook.focusedEditorBorder",
+    "notebook.inactiveFocusedCellBorder",
+    "notebook.inactiveSelectedCellBorder",
+    "notebook.outputContainerBackgroundColor",
+    "notebook.outputContainerBorderColor",
+    "notebook.selectedCellBackground",
+    "notebook.selectedCellBorder",
+    "notebook.symbolHighlightBackground",
+    "notebookScrollbarSlider.activeBackground",
+    "notebookScrollbarSlider.background",
+    "notebookScrollbarSlider.hoverBackground",
+    "notebookStatusErrorIcon.foreground",
+    "notebookStatusRunningIcon.foreground",
+    "notebookStatusSuccessIcon.foreground",
+    "notebookEditorOverviewRuler.runningCellForeground",
+    "charts.foreground",
+    "charts.lines",
+    "charts.red",
+    "charts.blue",
+    "charts.yellow",
+    "charts.orange",
+    "charts.green",
+    "charts.purple",
+    "ports.iconRunningProcessForeground",
+    "commentsView.resolvedIcon",
+    "commentsView.unresolvedIcon",
+    "actionBar.toggledBackground",
+    "simpleFindWidget.sashBorder",
+    "scm.historyItemAdditionsForeground",
+    "scm.historyItemDeletionsForeground",
+    "scm.historyItemStatisticsBorder",
+    "scm.historyItemSelectedStatisticsBorder",
+    "scmGraph.foreground1",
+    "scmGraph.foreground2",
+    "scmGraph.foreground3",
+    "scmGraph.foreground4",
+    "scmGraph.foreground5",
+    "scmGraph.historyItemHoverLabelForeground",
+    "scmGraph.historyItemHoverAdditionsForeground",
+    "scmGraph.historyItemHoverDeletionsForeground",
+    "scmGraph.historyItemRefColor",
+    "scmGraph.historyItemRemoteRefColor",
+    "scmGraph.historyItemBaseRefColor",
+    "scmGraph.historyItemHoverDefaultLabelForeground",
+    "scmGraph.historyItemHoverDefaultLabelBackground",
+    "editorActionList.background",
+    "editorActionList.foreground",
+    "editorActionList.focusForeground",
+    "editorActionList.focusBackground",
+    "radio.activeForeground",
+    "radio.activeBackground",
+    "radio.activeBorder",
+    "radio.inactiveForeground",
+    "radio.inactiveBackground",
+    "radio.inactiveBorder",
+    "radio.inactiveHoverBackground",
+];
+
+pub fn is_known_key(key: &str) -> bool {
+    KEYS.contains(&key)
+}
+
+const ALPHA_IGNORED: &[&str] = &["editor.background", "terminal.background"];
+
+const ALPHA_REQUIRED: &[&str] = &[
+    "diffEditor.insertedLineBackground",
+    "diffEditor.insertedTextBackground",
+    "diffEditor.removedLineBackground",
+    "diffEditor.removedTextBackground",
+    "editor.findMatchHighlightBackground",
+    "editor.findRangeHighlightBackground",
+    "editor.hoverHighlightBackground",
+    "editor.inactiveSelectionBackground",
+    "editor.linkedEditingBackground",
+    "editor.rangeHighlightBackground",
+    "editor.selectionHighlightBackground",
+    "editor.snippetFinalTabstopHighlightBackground",
+    "editor.snippetTabstopHighlightBackground",
+    "editor.symbolHighlightBackground",
+    "editor.wordHighlightBackground",
+    "editor.wordHighlightStrongBackground",
+    "editor.wordHighlightTextBackground",
+    "merge.commonContentBackground",
+    "merge.commonHeaderBackground",
+    "merge.currentContentBackground",
+    "merge.currentHeaderBackground",
+    "merge.incomingContentBackground",
+    "merge.incomingHeaderBackground",
+    "minimap.foregroundOpacity",
+    "terminal.findMatchHighlightBackground",
+    "terminal.inactiveSelectionBackground",
+    "terminal.selectionBackground",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Alpha {
+    Supported,
+    Ignored,
+    Required,
+}
+
+pub fn alpha_support(key: &str) -> Alpha {
+    if ALPHA_IGNORED.contains(&key) {
+        Alpha::Ignored
+    } else if ALPHA_REQUIRED.contains(&key) {
+        Alpha::Required
+    } else {
+        Alpha::Supported
+    }
+}